@@ -1,21 +1,16 @@
-use color_eyre::Result;
+use hopsworks_core::Result;
 
 use hopsworks_core::feature_store::{
     embedding::EmbeddingIndex, query::Query, FeatureGroup, FeatureStore, FeatureView,
 };
-use hopsworks_core::get_hopsworks_runtime;
 
 #[cfg(feature = "blocking")]
 pub fn get_feature_group_blocking(
     fs: &FeatureStore,
     name: &str,
     version: Option<i32>,
-    multithreaded: bool,
 ) -> Result<Option<FeatureGroup>> {
-    let rt = get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-
-    rt.block_on(fs.get_feature_group(name, version))
+    hopsworks_core::runtime::block_on(fs.hopsworks_client(), fs.get_feature_group(name, version))
 }
 
 #[cfg(feature = "blocking")]
@@ -28,12 +23,11 @@ pub fn get_or_create_feature_group_blocking(
     event_time: Option<&str>,
     online_enabled: bool,
     embedding_index: Option<EmbeddingIndex>,
-    multithreaded: bool,
 ) -> Result<FeatureGroup> {
-    let rt = get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-
-    if let Some(fgroup) = rt.block_on(fs.get_feature_group(name, version))? {
+    if let Some(fgroup) = hopsworks_core::runtime::block_on(
+        fs.hopsworks_client(),
+        fs.get_feature_group(name, version),
+    )? {
         Ok(fgroup)
     } else {
         Ok(fs
@@ -54,12 +48,8 @@ pub fn get_feature_view_blocking(
     fs: &FeatureStore,
     name: &str,
     version: Option<i32>,
-    multithreaded: bool,
 ) -> Result<Option<FeatureView>> {
-    let rt = get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-
-    rt.block_on(fs.get_feature_view(name, version))
+    hopsworks_core::runtime::block_on(fs.hopsworks_client(), fs.get_feature_view(name, version))
 }
 
 #[cfg(feature = "blocking")]
@@ -69,10 +59,9 @@ pub fn create_feature_view_blocking(
     version: i32,
     query: Query,
     description: Option<&str>,
-    multithreaded: bool,
 ) -> Result<FeatureView> {
-    let rt = get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-
-    rt.block_on(fs.create_feature_view(name, version, query, None, description))
+    hopsworks_core::runtime::block_on(
+        fs.hopsworks_client(),
+        fs.create_feature_view(name, version, query, None, description),
+    )
 }
@@ -1,12 +1,9 @@
-use color_eyre::Result;
+use hopsworks_core::Result;
 
 use hopsworks_core::feature_store::FeatureStore;
 use hopsworks_core::Project;
 
 #[cfg(feature = "blocking")]
-pub fn get_feature_store_blocking(project: &Project, multithreaded: bool) -> Result<FeatureStore> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-
-    rt.block_on(project.get_feature_store())
-}
\ No newline at end of file
+pub fn get_feature_store_blocking(project: &Project) -> Result<FeatureStore> {
+    hopsworks_core::runtime::block_on(project.hopsworks_client(), project.get_feature_store())
+}
@@ -1,29 +1,231 @@
-use color_eyre::Result;
+use futures::{Stream, StreamExt};
+use hopsworks_core::{HopsworksError, Result};
 use polars::prelude::DataFrame;
 
-use hopsworks_core::{feature_store::FeatureGroup, get_hopsworks_client, platform::job_execution::JobExecution};
-use hopsworks_kafka::insert::insert_in_registered_feature_group;
-
-
-pub async fn insert_polars_df_into_kafka(dataframe: &mut DataFrame, fg: &FeatureGroup) -> Result<JobExecution> {
-    let cert_dir_path = get_hopsworks_client().await.get_cert_dir().lock().await.clone();
-    insert_in_registered_feature_group(
-        dataframe,
-        fg.feature_store_id(),
-        fg.id().expect("Register the Feature Group first"),
-        fg.name(),
-        fg.version(),
-        fg.online_topic_name().expect("Register the Feature Group first"),
-        fg.primary_keys().as_slice(),
-        cert_dir_path.as_str(),
+use crate::polars::{
+    complex_features::decode_complex_features,
+    expectations::{save_ingested_validation_report, validate_for_insert},
+    statistics::{save_inserted_statistics, statistics_for_insert},
+    validation::validate_polars_df,
+};
+use hopsworks_core::{
+    feature_store::FeatureGroup, get_hopsworks_client, platform::job_execution::JobExecution,
+    with_hopsworks_client,
+};
+use hopsworks_kafka::consume::FeatureGroupConsumer;
+#[cfg(feature = "arrow")]
+use hopsworks_kafka::insert::{
+    cast_to_feature_types, insert_record_batches_in_registered_feature_group, project_to_features,
+};
+use hopsworks_kafka::insert::{
+    delete_records_in_registered_feature_group, insert_in_registered_feature_group,
+};
+use hopsworks_kafka::target::FeatureGroupTarget;
+
+mod writer;
+
+pub use hopsworks_kafka::consume::{ConsumerOptions, StartOffset};
+pub use hopsworks_kafka::kafka_configuration::{Acks, CompressionCodec, KafkaProducerOptions};
+pub use hopsworks_kafka::report::{
+    DeliveryRetryPolicy, FailedDelivery, InsertReport, PartitionOffsets,
+};
+pub use writer::{FeatureGroupWriter, MaterializationSchedule};
+
+/// Insert the rows of `dataframe` in the Feature Group. Returns the execution of the job materializing
+/// them in the offline feature store and the [`InsertReport`] of their delivery to the online topic.
+/// Deliveries failing with a retriable error are retried following `retry_policy`,
+/// by default [`DeliveryRetryPolicy::default`]. The producers are configured with `producer_options`,
+/// falling back to the `HOPSWORKS_KAFKA_*` environment variables, see [`KafkaProducerOptions`].
+///
+/// The materialization job is started even if some deliveries failed after their retries, so that
+/// the delivered rows reach the offline feature store. Check [`InsertReport::is_complete`] and insert
+/// the rows listed in [`InsertReport::failed`] again if needed.
+pub async fn insert_polars_df_into_kafka(
+    dataframe: &mut DataFrame,
+    fg: &FeatureGroup,
+    retry_policy: Option<DeliveryRetryPolicy>,
+    producer_options: Option<KafkaProducerOptions>,
+) -> Result<(JobExecution, InsertReport)> {
+    with_hopsworks_client(fg.hopsworks_client(), async {
+        let target = registered_kafka_target(fg, "inserted").await?;
+        validate_polars_df(dataframe, fg)?.ensure_valid()?;
+        let validation_report = validate_for_insert(dataframe, fg).await?;
+        let statistics = statistics_for_insert(dataframe, fg)?;
+        let (job_execution, report) = insert_in_registered_feature_group(
+            dataframe,
+            &target,
+            &retry_policy.unwrap_or_default(),
+            &producer_options.unwrap_or_default(),
+        )
+        .await?;
+        if let Some(validation_report) = validation_report {
+            save_ingested_validation_report(fg, validation_report).await;
+        }
+        if let Some(statistics) = statistics {
+            save_inserted_statistics(fg, &statistics).await;
+        }
+        Ok((job_execution, report))
+    })
+    .await
+}
+
+#[cfg(feature = "blocking")]
+pub fn insert_polars_df_into_kafka_blocking(
+    dataframe: &mut DataFrame,
+    fg: &FeatureGroup,
+    retry_policy: Option<DeliveryRetryPolicy>,
+    producer_options: Option<KafkaProducerOptions>,
+) -> Result<(JobExecution, InsertReport)> {
+    hopsworks_core::runtime::block_on(
+        fg.hopsworks_client(),
+        insert_polars_df_into_kafka(dataframe, fg, retry_policy, producer_options),
+    )
+}
+
+/// Insert arrow-rs record batches in the Feature Group, e.g. read from the offline store with
+/// `read_arrow_from_offline_feature_store` or from a
+/// [`RecordBatchReader`](arrow::record_batch::RecordBatchReader), without converting them to polars.
+///
+/// Every feature of the Feature Group must have a column, every column must be a feature.
+/// The columns are reordered following the features and cast to their types, e.g. a Float64 column
+/// of a `float` feature to Float32, a column which cannot be cast is rejected with a
+/// [`HopsworksError::Validation`]. The rows are neither checked against
+/// the expectation suite of the Feature Group nor used to compute its statistics.
+/// See [`insert_polars_df_into_kafka`] for the returned [`InsertReport`], the `retry_policy` and the `producer_options`.
+#[cfg(feature = "arrow")]
+pub async fn insert_record_batches_into_kafka<I>(
+    batches: I,
+    fg: &FeatureGroup,
+    retry_policy: Option<DeliveryRetryPolicy>,
+    producer_options: Option<KafkaProducerOptions>,
+) -> Result<(JobExecution, InsertReport)>
+where
+    I: IntoIterator<
+        Item = std::result::Result<arrow::record_batch::RecordBatch, arrow::error::ArrowError>,
+    >,
+{
+    let feature_names = fg.feature_names();
+    let feature_types: Vec<&str> = fg.features().iter().map(|f| f.data_type()).collect();
+    let batches = batches.into_iter().map(move |batch| {
+        let batch = project_to_features(batch?, feature_names.as_slice())?;
+        cast_to_feature_types(batch, feature_types.as_slice())
+    });
+    with_hopsworks_client(fg.hopsworks_client(), async {
+        let target = registered_kafka_target(fg, "inserted").await?;
+        Ok(insert_record_batches_in_registered_feature_group(
+            batches,
+            &target,
+            &retry_policy.unwrap_or_default(),
+            &producer_options.unwrap_or_default(),
+        )
+        .await?)
+    })
+    .await
+}
+
+#[cfg(all(feature = "arrow", feature = "blocking"))]
+pub fn insert_record_batches_into_kafka_blocking<I>(
+    batches: I,
+    fg: &FeatureGroup,
+    retry_policy: Option<DeliveryRetryPolicy>,
+    producer_options: Option<KafkaProducerOptions>,
+) -> Result<(JobExecution, InsertReport)>
+where
+    I: IntoIterator<
+        Item = std::result::Result<arrow::record_batch::RecordBatch, arrow::error::ArrowError>,
+    >,
+{
+    hopsworks_core::runtime::block_on(
+        fg.hopsworks_client(),
+        insert_record_batches_into_kafka(batches, fg, retry_policy, producer_options),
     )
+}
+
+/// Delete the rows of the Feature Group with the primary keys of the `dataframe`.
+///
+/// Tombstones are produced on the online topic of the Feature Group and the offline
+/// materialization job is started in delete mode, the returned [`JobExecution`] tracks it.
+pub async fn delete_records_from_kafka(
+    dataframe: &DataFrame,
+    fg: &FeatureGroup,
+    producer_options: Option<KafkaProducerOptions>,
+) -> Result<JobExecution> {
+    with_hopsworks_client(fg.hopsworks_client(), async {
+        let target = registered_kafka_target(fg, "deleted").await?;
+        Ok(delete_records_in_registered_feature_group(
+            dataframe,
+            &target,
+            &producer_options.unwrap_or_default(),
+        )
+        .await?)
+    })
     .await
 }
 
-#[cfg(feature="blocking")]
-pub fn insert_polars_df_into_kafka_blocking(dataframe: &mut DataFrame, fg: &FeatureGroup, multithreaded: bool) -> Result<JobExecution> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
+#[cfg(feature = "blocking")]
+pub fn delete_records_from_kafka_blocking(
+    dataframe: &DataFrame,
+    fg: &FeatureGroup,
+    producer_options: Option<KafkaProducerOptions>,
+) -> Result<JobExecution> {
+    hopsworks_core::runtime::block_on(
+        fg.hopsworks_client(),
+        delete_records_from_kafka(dataframe, fg, producer_options),
+    )
+}
+
+/// Tail the online topic of the Feature Group, yielding the rows written on it in micro-batches,
+/// from the position and with the batching of `options`, by default [`ConsumerOptions::default`].
+///
+/// The stream never ends, drop it to stop consuming. Complex features are decoded into nested columns.
+pub async fn consume_polars_df_from_kafka(
+    fg: &FeatureGroup,
+    options: Option<ConsumerOptions>,
+) -> Result<impl Stream<Item = Result<DataFrame>>> {
+    let consumer = with_hopsworks_client(fg.hopsworks_client(), async {
+        let target = registered_kafka_target(fg, "consumed").await?;
+        Ok::<_, HopsworksError>(
+            FeatureGroupConsumer::new(&target, options.unwrap_or_default()).await?,
+        )
+    })
+    .await?;
 
-    rt.block_on(insert_polars_df_into_kafka(dataframe, fg))
-}
\ No newline at end of file
+    let features = fg.features().to_vec();
+    Ok(consumer.into_stream().map(move |batch| {
+        let mut dataframe = batch?;
+        decode_complex_features(&mut dataframe, features.as_slice())?;
+        Ok(dataframe)
+    }))
+}
+
+/// Target of the Feature Group whose rows are to be `action` through Kafka, e.g. `"inserted"`,
+/// with the certificate directory of the client. Must run in the scope of the client of `fg`.
+async fn registered_kafka_target(fg: &FeatureGroup, action: &str) -> Result<FeatureGroupTarget> {
+    if fg.is_external() {
+        return Err(HopsworksError::Validation(format!(
+            "Feature Group {} is external, its data is read from its storage connector and cannot be {action}.",
+            fg.name()
+        )));
+    }
+    let (Some(feature_group_id), Some(online_topic_name)) = (fg.id(), fg.online_topic_name())
+    else {
+        return Err(HopsworksError::Validation(
+            "Register the Feature Group first".to_string(),
+        ));
+    };
+    let cert_dir_path = get_hopsworks_client()
+        .await?
+        .get_cert_dir()
+        .lock()
+        .await
+        .clone();
+    Ok(FeatureGroupTarget {
+        feature_store_id: fg.feature_store_id(),
+        feature_group_id,
+        feature_group_name: fg.name().to_string(),
+        feature_group_version: fg.version(),
+        online_topic_name: online_topic_name.to_string(),
+        primary_keys: fg.primary_keys().iter().map(|pk| pk.to_string()).collect(),
+        cert_dir: cert_dir_path,
+    })
+}
@@ -0,0 +1,211 @@
+use futures::{Stream, StreamExt};
+use polars::prelude::DataFrame;
+use std::time::{Duration, Instant};
+
+use hopsworks_core::{
+    feature_store::feature_group::expectation_suite::ExpectationSuite, feature_store::FeatureGroup,
+    platform::job_execution::JobExecution, with_hopsworks_client, HopsworksError, Result,
+};
+use hopsworks_kafka::feature_group_producer::FeatureGroupProducer;
+
+use super::{DeliveryRetryPolicy, InsertReport, KafkaProducerOptions};
+use crate::polars::{
+    expectations::{save_ingested_validation_report, validate_with_expectation_suite},
+    validation::validate_polars_df,
+};
+
+/// When a [`FeatureGroupWriter`] starts the job materializing the written rows in the offline feature store.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MaterializationSchedule {
+    /// Only on [`FeatureGroupWriter::materialize`] or [`FeatureGroupWriter::close`].
+    #[default]
+    Manual,
+    /// After every `n` writes.
+    EveryWrites(usize),
+    /// On the first write once `interval` has elapsed since the previous materialization.
+    Interval(Duration),
+}
+
+/// Long-lived writer streaming DataFrames into the online topic of a Feature Group.
+///
+/// The Kafka producer, the message headers and the expectation suite of the Feature Group are
+/// fetched once in [`FeatureGroupWriter::new`], each write is validated against the schema and
+/// the expectation suite before being produced. Unlike [`insert_polars_df_into_kafka`](super::insert_polars_df_into_kafka),
+/// writes do not start the offline materialization job, it is started following the
+/// [`MaterializationSchedule`] of the writer. Statistics are not computed for streamed rows.
+///
+/// # Example
+///
+/// ```no_run
+/// # use hopsworks_api::{FeatureGroup, Result};
+/// # use hopsworks_api::kafka::{FeatureGroupWriter, MaterializationSchedule};
+/// # use std::time::Duration;
+/// # async fn run(fg: &FeatureGroup, batches: impl futures::Stream<Item = polars::prelude::DataFrame> + Unpin) -> Result<()> {
+/// let schedule = MaterializationSchedule::Interval(Duration::from_secs(3600));
+/// let mut writer = FeatureGroupWriter::new(fg, None, None, schedule).await?;
+/// let report = writer.write_stream(batches).await?;
+/// writer.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FeatureGroupWriter {
+    fg: FeatureGroup,
+    expectation_suite: Option<ExpectationSuite>,
+    producer: FeatureGroupProducer,
+    retry_policy: DeliveryRetryPolicy,
+    schedule: MaterializationSchedule,
+    pending_writes: usize,
+    last_materialization: Instant,
+    job_executions: Vec<JobExecution>,
+}
+
+impl FeatureGroupWriter {
+    /// Set up the producer writing into the registered Feature Group `fg`. Deliveries failing with
+    /// a retriable error are retried following `retry_policy`, by default [`DeliveryRetryPolicy::default`],
+    /// the producer is configured with `producer_options`, see [`KafkaProducerOptions`].
+    pub async fn new(
+        fg: &FeatureGroup,
+        retry_policy: Option<DeliveryRetryPolicy>,
+        producer_options: Option<KafkaProducerOptions>,
+        schedule: MaterializationSchedule,
+    ) -> Result<Self> {
+        with_hopsworks_client(fg.hopsworks_client(), async {
+            let target = super::registered_kafka_target(fg, "inserted").await?;
+            let producer =
+                FeatureGroupProducer::new(target, &producer_options.unwrap_or_default()).await?;
+            Ok(Self {
+                fg: fg.clone(),
+                expectation_suite: fg.get_expectation_suite().await?,
+                producer,
+                retry_policy: retry_policy.unwrap_or_default(),
+                schedule,
+                pending_writes: 0,
+                last_materialization: Instant::now(),
+                job_executions: vec![],
+            })
+        })
+        .await
+    }
+
+    pub fn feature_group(&self) -> &FeatureGroup {
+        &self.fg
+    }
+
+    pub fn schedule(&self) -> MaterializationSchedule {
+        self.schedule
+    }
+
+    /// Number of writes since the offline materialization job was last started.
+    pub fn pending_writes(&self) -> usize {
+        self.pending_writes
+    }
+
+    /// Executions of the offline materialization job started by the writer, oldest first.
+    pub fn job_executions(&self) -> &[JobExecution] {
+        self.job_executions.as_slice()
+    }
+
+    /// Produce the rows of `dataframe` on the online topic of the Feature Group and start the
+    /// offline materialization job if it is due following the [`MaterializationSchedule`].
+    pub async fn write(&mut self, dataframe: &mut DataFrame) -> Result<InsertReport> {
+        validate_polars_df(dataframe, &self.fg)?.ensure_valid()?;
+        let report = with_hopsworks_client(self.fg.hopsworks_client(), async {
+            let validation_report = validate_with_expectation_suite(
+                dataframe,
+                &self.fg,
+                self.expectation_suite.as_ref(),
+            )
+            .await?;
+            let report = self.producer.produce(dataframe, &self.retry_policy).await?;
+            if let Some(validation_report) = validation_report {
+                save_ingested_validation_report(&self.fg, validation_report).await;
+            }
+            Ok::<_, HopsworksError>(report)
+        })
+        .await?;
+        if report.rows > 0 {
+            self.pending_writes += 1;
+        }
+        if self.is_materialization_due() {
+            self.materialize().await?;
+        }
+        Ok(report)
+    }
+
+    /// Write every DataFrame of `stream` until it ends, see [`FeatureGroupWriter::write`].
+    /// Returns the merged [`InsertReport`] of the writes, the first failing write stops the stream.
+    pub async fn write_stream<S>(&mut self, mut stream: S) -> Result<InsertReport>
+    where
+        S: Stream<Item = DataFrame> + Unpin,
+    {
+        let start_time = Instant::now();
+        let mut report = InsertReport::default();
+        while let Some(mut dataframe) = stream.next().await {
+            report.merge(self.write(&mut dataframe).await?);
+        }
+        report.elapsed = start_time.elapsed();
+        Ok(report)
+    }
+
+    /// Start the job materializing the rows written so far in the offline feature store,
+    /// regardless of the [`MaterializationSchedule`].
+    pub async fn materialize(&mut self) -> Result<JobExecution> {
+        let job_execution = with_hopsworks_client(self.fg.hopsworks_client(), async {
+            Ok::<_, HopsworksError>(self.producer.start_offline_materialization().await?)
+        })
+        .await?;
+        self.pending_writes = 0;
+        self.last_materialization = Instant::now();
+        self.job_executions.push(job_execution.clone());
+        Ok(job_execution)
+    }
+
+    /// Close the writer, materializing the rows written since the previous materialization if any.
+    pub async fn close(mut self) -> Result<Option<JobExecution>> {
+        if self.pending_writes == 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.materialize().await?))
+    }
+
+    fn is_materialization_due(&self) -> bool {
+        self.pending_writes > 0
+            && match self.schedule {
+                MaterializationSchedule::Manual => false,
+                MaterializationSchedule::EveryWrites(n) => self.pending_writes >= n.max(1),
+                MaterializationSchedule::Interval(interval) => {
+                    self.last_materialization.elapsed() >= interval
+                }
+            }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl FeatureGroupWriter {
+    pub fn new_blocking(
+        fg: &FeatureGroup,
+        retry_policy: Option<DeliveryRetryPolicy>,
+        producer_options: Option<KafkaProducerOptions>,
+        schedule: MaterializationSchedule,
+    ) -> Result<Self> {
+        hopsworks_core::runtime::block_on(
+            fg.hopsworks_client(),
+            Self::new(fg, retry_policy, producer_options, schedule),
+        )
+    }
+
+    pub fn write_blocking(&mut self, dataframe: &mut DataFrame) -> Result<InsertReport> {
+        let client = self.fg.hopsworks_client().cloned();
+        hopsworks_core::runtime::block_on(client.as_ref(), self.write(dataframe))
+    }
+
+    pub fn materialize_blocking(&mut self) -> Result<JobExecution> {
+        let client = self.fg.hopsworks_client().cloned();
+        hopsworks_core::runtime::block_on(client.as_ref(), self.materialize())
+    }
+
+    pub fn close_blocking(self) -> Result<Option<JobExecution>> {
+        let client = self.fg.hopsworks_client().cloned();
+        hopsworks_core::runtime::block_on(client.as_ref(), self.close())
+    }
+}
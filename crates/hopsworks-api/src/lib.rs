@@ -30,17 +30,22 @@
 //! ### Connect to Hopsworks Serverless App
 //! ```no_run
 //! use color_eyre::Result;
+//! use hopsworks_api::kafka::insert_polars_df_into_kafka;
+//! use hopsworks_api::offline_store::get_batch_data;
+//! use hopsworks_core::feature_store::query::builder::BatchQueryOptions;
 //! use polars::prelude::*;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<()> {
 //!  // The api key will be read from the environment variable HOPSWORKS_API_KEY
-//!  let project = hopsworks::login(None).await?;
+//!  let project = hopsworks_api::login(None, hopsworks_api::RuntimeConfig::Current).await?;
 //!  // Get the default feature store for the project
 //!  let fs = project.get_feature_store().await?;
 //!
 //!  // Create a new feature group and ingest local data to the Feature Store
-//!  let mut df = CsvReader::from_path("./examples/data/transactions.csv")?.finish()?;
+//!  let mut df = CsvReadOptions::default()
+//!    .try_into_reader_with_file_path(Some("./examples/data/transactions.csv".into()))?
+//!    .finish()?;
 //!  let mut fg = fs.create_feature_group(
 //!    "my_fg",
 //!    1,
@@ -49,7 +54,8 @@
 //!    Some("event_time_feature_name"),
 //!    false
 //!  )?;
-//!  fg.insert(&mut df).await?;
+//!  hopsworks_api::polars::register_feature_group_from(&mut fg, &df, &Default::default()).await?;
+//!  insert_polars_df_into_kafka(&mut df, &fg, None, None).await?;
 //!
 //!  // Create a feature view to read data from the Feature Store,
 //!  // see Feature View page for more complex examples
@@ -58,10 +64,11 @@
 //!    1,
 //!    fg.select(&["feature1", "feature2"])?,
 //!    None,
+//!    None,
 //!  ).await?;
 //!
 //!  // Read data from the Feature View
-//!  let df = fv.read_from_offline_feature_store(None).await?;
+//!  let df = get_batch_data(&fv, &BatchQueryOptions::default(), None).await?;
 //!  
 //!  Ok(())
 //! }
@@ -71,7 +78,7 @@
 //!
 //! ```no_run
 //! # use color_eyre::Result;
-//! use hopsworks::HopsworksClientBuilder;
+//! use hopsworks_api::HopsworksClientBuilder;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<()> {
@@ -81,17 +88,22 @@
 //!    .with_url(my_hopsworks_domain)
 //!    .with_api_key(&api_key);
 //!
-//!  let project = hopsworks::login(Some(builder)).await?;
+//!  let project = hopsworks_api::login(Some(builder), hopsworks_api::RuntimeConfig::Current).await?;
 //!  let fs = project.get_feature_store().await?;
 //!  Ok(())
 //! }
 //! ```
-use color_eyre::Result;
-
+pub use hopsworks_core::feature_store::feature_group::expectation_suite::{
+    ExpectationConfiguration, ExpectationSuite, ValidationIngestionPolicy, ValidationReport,
+    ValidationResult,
+};
+pub use hopsworks_core::feature_store::feature_group::{
+    statistics::Statistics, statistics_config::StatisticsConfig, TimeTravelFormat,
+};
 pub use hopsworks_core::feature_store::{
-    embedding::EmbeddingFeature, embedding::EmbeddingIndex,
-    feature_view::training_dataset::TrainingDataset, query::Query, FeatureGroup, FeatureStore,
-    FeatureView,
+    embedding::EmbeddingFeature, embedding::EmbeddingIndex, feature_group::feature::Feature,
+    feature_view::training_dataset::TrainingDataset, query::Query,
+    storage_connector::StorageConnector, FeatureGroup, FeatureStore, FeatureView,
 };
 pub use hopsworks_core::platform::{job::Job, job_execution::JobExecution, project::Project};
 
@@ -111,12 +123,18 @@ pub mod blocking;
 #[cfg(feature = "polars")]
 pub mod polars;
 
-pub use hopsworks_core::HopsworksClientBuilder;
+pub use hopsworks_core::{
+    HopsworksClient, HopsworksClientBuilder, HopsworksError, ListOptions, Result, RetryPolicy,
+    RuntimeConfig, TlsConfig,
+};
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Login to Hopsworks and return the chosen project.
 /// If no client builder is provided, a default client builder to connect to [Hopsworks Serverless App](https://app.hopsworks.ai) is used.
 ///
+/// The `*_blocking` functions of the session run on `runtime`, use [`RuntimeConfig::Current`]
+/// to reuse the tokio runtime of the application instead of starting one managed by the SDK.
+///
 /// # Requirements
 /// You must provide an API key to login into Hopsworks either via the `HOPSWORKS_API_KEY`
 /// environment variable or via the `api_key` field in the client builder. Login will panic if
@@ -130,7 +148,7 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// #[tokio::main]
 /// async fn main() -> Result<()> {
 ///    // The api key will be read from the environment variable HOPSWORKS_API_KEY
-///    let project = hopsworks::login(None).await?;
+///    let project = hopsworks_api::login(None, hopsworks_api::RuntimeConfig::Current).await?;
 ///    Ok(())
 /// }
 /// ```
@@ -138,7 +156,7 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// # Example with custom client builder
 /// ```no_run
 /// use color_eyre::Result;
-/// use hopsworks::HopsworksClientBuilder;
+/// use hopsworks_api::HopsworksClientBuilder;
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<()> {
@@ -148,7 +166,7 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 ///      .with_api_key(&api_key)
 ///      .with_url(my_hopsworks_domain);
 ///
-///   let project = hopsworks::login(Some(builder)).await?;
+///   let project = hopsworks_api::login(Some(builder), hopsworks_api::RuntimeConfig::Current).await?;
 ///   Ok(())
 /// }
 /// ```
@@ -157,18 +175,64 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// If no API key is provided via the `HOPSWORKS_API_KEY` environment variable or via the `api_key` field in the client builder.
 pub async fn login(
     client_builder: Option<HopsworksClientBuilder>,
-    multithreaded: bool,
+    runtime: RuntimeConfig,
 ) -> Result<Project> {
-    hopsworks_core::login(client_builder, multithreaded).await
+    hopsworks_core::login(client_builder, runtime).await
 }
 
+/// Login to Hopsworks with a new client, independent of the one initialized by [`login`].
+/// The returned project and all entities obtained from it use this client, allowing to work
+/// with several projects or clusters at once.
+///
+/// # Example
+/// ```no_run
+/// use color_eyre::Result;
+/// use hopsworks_api::{HopsworksClientBuilder, RuntimeConfig};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///   let source = hopsworks_api::connect(
+///     Some(HopsworksClientBuilder::new().with_project_name("source_project")),
+///     RuntimeConfig::Current,
+///   ).await?;
+///   let target = hopsworks_api::connect(
+///     Some(HopsworksClientBuilder::new().with_project_name("target_project")),
+///     RuntimeConfig::Current,
+///   ).await?;
+///
+///   let source_fs = source.get_feature_store().await?;
+///   let target_fs = target.get_feature_store().await?;
+///   Ok(())
+/// }
+/// ```
+pub async fn connect(
+    client_builder: Option<HopsworksClientBuilder>,
+    runtime: RuntimeConfig,
+) -> Result<Project> {
+    hopsworks_core::connect(client_builder, runtime).await
+}
+
+/// Log out of Hopsworks, removing the API key of the client and the certificates
+/// written locally on login.
+pub async fn logout() -> Result<()> {
+    hopsworks_core::logout().await
+}
+
+/// Blocking version of [`login`], blocks on `runtime` which must not be the runtime of the caller
+/// if it is a current-thread runtime.
 #[cfg(feature = "blocking")]
 pub fn login_blocking(
     client_builder: Option<HopsworksClientBuilder>,
-    multithreaded: bool,
+    runtime: RuntimeConfig,
 ) -> Result<Project> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded);
-    let _guard = rt.enter();
+    runtime.block_on(hopsworks_core::login(client_builder, runtime.clone()))
+}
 
-    rt.block_on(hopsworks_core::login(client_builder, multithreaded))
+/// Blocking version of [`connect`].
+#[cfg(feature = "blocking")]
+pub fn connect_blocking(
+    client_builder: Option<HopsworksClientBuilder>,
+    runtime: RuntimeConfig,
+) -> Result<Project> {
+    runtime.block_on(hopsworks_core::connect(client_builder, runtime.clone()))
 }
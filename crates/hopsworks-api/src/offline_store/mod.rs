@@ -1,16 +1,23 @@
-use color_eyre::Result;
-use tracing::debug;
-use polars::prelude::DataFrame;
 use arrow::record_batch::RecordBatch;
+use hopsworks_core::Result;
+use polars::prelude::DataFrame;
+use tracing::debug;
 
-use hopsworks_core::feature_store::FeatureGroup;
-use hopsworks_core::feature_store::{FeatureView, query::builder::BatchQueryOptions};
 use hopsworks_core::controller::feature_store::feature_view::get_batch_query;
+use hopsworks_core::feature_store::FeatureGroup;
+use hopsworks_core::feature_store::{query::builder::BatchQueryOptions, FeatureView};
+use hopsworks_core::with_hopsworks_client;
+
+use crate::polars::complex_features::{decode_complex_features, query_result_features};
 
-use hopsworks_offline_store::read::{flight_to_polars::read_with_arrow_flight_client, flight_to_record_batch::read_to_record_batch_with_arrow_flight_client};
 pub use hopsworks_offline_store::read::read_options::ArrowFlightReadOptions;
+use hopsworks_offline_store::read::{
+    flight_to_polars::read_with_arrow_flight_client,
+    flight_to_record_batch::read_to_record_batch_with_arrow_flight_client,
+};
 
 /// Reads feature group data from Hopsworks via the Arrow Flight client.
+/// Complex features read as binary columns of their Avro encoding are decoded into nested columns.
 ///
 /// # Example
 /// ```no_run
@@ -42,12 +49,19 @@ pub async fn read_polars_from_offline_feature_store(
         "Reading data from feature group {} with Arrow Flight client",
         fgroup.name()
     );
-    let read_df = read_with_arrow_flight_client(query, _offline_read_options, vec![]).await?;
+    let mut read_df = with_hopsworks_client(
+        fgroup.hopsworks_client(),
+        read_with_arrow_flight_client(query, _offline_read_options, vec![]),
+    )
+    .await?;
+    decode_complex_features(&mut read_df, fgroup.features())?;
 
     Ok(read_df)
 }
 
 /// Reads feature group data from Hopsworks via the Arrow Flight client.
+/// The record batches are returned as read, complex features read as binary columns are not
+/// decoded, use [`read_polars_from_offline_feature_store`] to decode them.
 ///
 /// # Example
 /// ```no_run
@@ -79,42 +93,49 @@ pub async fn read_arrow_from_offline_feature_store(
         "Reading data from feature group {} with Arrow Flight client",
         fgroup.name()
     );
-    let read_df = read_to_record_batch_with_arrow_flight_client(query, _offline_read_options, vec![]).await?;
+    let read_df = with_hopsworks_client(
+        fgroup.hopsworks_client(),
+        read_to_record_batch_with_arrow_flight_client(query, _offline_read_options, vec![]),
+    )
+    .await?;
 
     Ok(read_df)
 }
 
-
 pub async fn get_batch_data(
     feature_view: &FeatureView,
     batch_query_options: &BatchQueryOptions,
     offline_read_options: Option<ArrowFlightReadOptions>,
 ) -> Result<DataFrame> {
-    let batch_query = get_batch_query(feature_view, batch_query_options).await?;
+    with_hopsworks_client(feature_view.hopsworks_client(), async {
+        let batch_query = get_batch_query(feature_view, batch_query_options).await?;
 
-    read_with_arrow_flight_client(batch_query, offline_read_options, vec![]).await
+        let mut batch_df =
+            read_with_arrow_flight_client(batch_query, offline_read_options, vec![]).await?;
+        decode_complex_features(&mut batch_df, &query_result_features(feature_view.query()))?;
+        Ok(batch_df)
+    })
+    .await
 }
 
 #[cfg(feature = "blocking")]
 pub fn read_polars_from_offline_feature_store_blocking(
     fgroup: &FeatureGroup,
     offline_read_options: Option<ArrowFlightReadOptions>,
-    multithreaded: bool,
 ) -> Result<DataFrame> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-
-    rt.block_on(read_polars_from_offline_feature_store(fgroup, offline_read_options))
+    hopsworks_core::runtime::block_on(
+        fgroup.hopsworks_client(),
+        read_polars_from_offline_feature_store(fgroup, offline_read_options),
+    )
 }
 
 #[cfg(feature = "blocking")]
 pub fn read_arrow_from_offline_feature_store_blocking(
     fgroup: &FeatureGroup,
     offline_read_options: Option<ArrowFlightReadOptions>,
-    multithreaded: bool,
 ) -> Result<Vec<RecordBatch>> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-
-    rt.block_on(read_arrow_from_offline_feature_store(fgroup, offline_read_options))
+    hopsworks_core::runtime::block_on(
+        fgroup.hopsworks_client(),
+        read_arrow_from_offline_feature_store(fgroup, offline_read_options),
+    )
 }
@@ -1,43 +1,57 @@
+use hopsworks_core::Result;
 use std::sync::Arc;
-use color_eyre::Result;
 
-use arrow::record_batch::RecordBatch;
 use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
 
-use hopsworks_core::feature_store::FeatureGroup;
+use hopsworks_core::{feature_store::FeatureGroup, with_hopsworks_client};
 use hopsworks_online_store_sql::read::{
     read_to_arrow::read_query_from_online_feature_store,
     read_to_polars::read_polars_from_online_feature_store,
 };
 use polars::frame::DataFrame;
 
-#[cfg(feature="read_rest_online_store")]
+use crate::polars::complex_features::decode_complex_features;
+
+#[cfg(feature = "read_rest_online_store")]
 pub mod rest_read;
 
-pub async fn read_arrow_from_online_store_via_sql(fg: &FeatureGroup) -> Result<(Vec<RecordBatch>, Arc<Schema>)> {
+pub async fn read_arrow_from_online_store_via_sql(
+    fg: &FeatureGroup,
+) -> Result<(Vec<RecordBatch>, Arc<Schema>)> {
     let query = fg.select_all();
-    read_query_from_online_feature_store(&query, None).await
+    with_hopsworks_client(fg.hopsworks_client(), async {
+        Ok(read_query_from_online_feature_store(&query, None).await?)
+    })
+    .await
 }
 
+/// Read the rows of `fg` from the online feature store, complex features are decoded into nested columns.
 pub async fn read_polars_from_online_store_via_sql(fg: &FeatureGroup) -> Result<DataFrame> {
     let query = fg.select_all();
-    read_polars_from_online_feature_store(&query, None).await
+    let mut df = with_hopsworks_client(
+        fg.hopsworks_client(),
+        read_polars_from_online_feature_store(&query, None),
+    )
+    .await?;
+    decode_complex_features(&mut df, fg.features())?;
+    Ok(df)
 }
 
-#[cfg(feature="blocking")]
-pub fn read_arrow_from_online_store_via_sql_blocking(fg: &FeatureGroup, multithreaded: bool) -> Result<(Vec<RecordBatch>, Arc<Schema>)> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-    
-    rt.block_on(read_arrow_from_online_store_via_sql(fg))
+#[cfg(feature = "blocking")]
+pub fn read_arrow_from_online_store_via_sql_blocking(
+    fg: &FeatureGroup,
+) -> Result<(Vec<RecordBatch>, Arc<Schema>)> {
+    hopsworks_core::runtime::block_on(
+        fg.hopsworks_client(),
+        read_arrow_from_online_store_via_sql(fg),
+    )
 }
 
-#[cfg(feature="blocking")]
-pub fn read_polars_from_online_store_via_sql_blocking(fg: &FeatureGroup, multithreaded: bool) -> Result<DataFrame> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded).clone();
-    let _guard = rt.enter();
-    
-    rt.block_on(read_polars_from_online_store_via_sql(fg))
+#[cfg(feature = "blocking")]
+pub fn read_polars_from_online_store_via_sql_blocking(fg: &FeatureGroup) -> Result<DataFrame> {
+    hopsworks_core::runtime::block_on(
+        fg.hopsworks_client(),
+        read_polars_from_online_store_via_sql(fg),
+    )
 }
-
-
@@ -1,48 +1,217 @@
-use color_eyre::Result;
+use base64::Engine;
+use hopsworks_core::{HopsworksError, Result};
 use reqwest::header;
+use serde_json::Value;
+use std::collections::HashMap;
 
-use hopsworks_core::{controller::platform::variables::get_loadbalancer_external_domain, feature_store::FeatureView};
+use hopsworks_core::{
+    controller::platform::variables::get_loadbalancer_external_domain, feature_store::FeatureView,
+};
 use hopsworks_online_store_rest::controller;
 
-pub use hopsworks_online_store_rest::entities::{BatchFeatureVectors, SingleFeatureVector};
-pub use hopsworks_online_store_rest::{EntryValuesPayload, PassedValuesPayload};
-pub use hopsworks_online_store_rest::rest_read_options::FeatureVectorRestReadOptions;
+use crate::polars::{
+    complex_features::{decode_complex_feature_value, query_result_features},
+    is_complex_type,
+};
 
+pub use hopsworks_online_store_rest::entities::{
+    BatchFeatureVectors, MetadataFeatureVector, SingleFeatureVector,
+};
+pub use hopsworks_online_store_rest::rest_read_options::FeatureVectorRestReadOptions;
+pub use hopsworks_online_store_rest::{EntryValuesPayload, PassedValuesPayload};
 
-pub async fn init_online_store_rest_client(api_key: &str, api_version: &str, reqwest_client: Option<reqwest::Client>) -> Result<()> {
+pub async fn init_online_store_rest_client(
+    api_key: &str,
+    api_version: &str,
+    reqwest_client: Option<reqwest::Client>,
+) -> Result<()> {
     let url = get_loadbalancer_external_domain("online_store_rest_server").await?;
     let header_value = header::HeaderValue::from_str(api_key)?;
 
-    hopsworks_online_store_rest::init_online_store_rest_client(&url, header_value, api_version, reqwest_client)
+    Ok(hopsworks_online_store_rest::init_online_store_rest_client(
+        &url,
+        header_value,
+        api_version,
+        reqwest_client,
+    )?)
 }
 
-pub async fn get_feature_vector(fview_obj: &FeatureView, entry: EntryValuesPayload, passed_values: Option<PassedValuesPayload>, rest_read_options: Option<FeatureVectorRestReadOptions>) -> Result<SingleFeatureVector> {
-    controller::get_feature_vector(fview_obj.feature_store_id(), fview_obj.name(), fview_obj.version(), entry, passed_values, rest_read_options.unwrap_or_default()).await
+/// Read the feature vector of `entry` from the online store REST server. Complex features are
+/// decoded into JSON arrays and objects, their names are then always part of the metadata.
+pub async fn get_feature_vector(
+    fview_obj: &FeatureView,
+    entry: EntryValuesPayload,
+    passed_values: Option<PassedValuesPayload>,
+    rest_read_options: Option<FeatureVectorRestReadOptions>,
+) -> Result<SingleFeatureVector> {
+    let complex_feature_types = complex_feature_types(fview_obj);
+    let mut feature_vector = controller::get_feature_vector(
+        fview_obj.feature_store_id(),
+        fview_obj.name(),
+        fview_obj.version(),
+        entry,
+        passed_values,
+        with_feature_names(rest_read_options, &complex_feature_types),
+    )
+    .await?;
+    if !complex_feature_types.is_empty() {
+        let feature_names = feature_names(feature_vector.metadata())?;
+        decode_complex_feature_values(
+            &complex_feature_types,
+            &feature_names,
+            feature_vector.features_mut(),
+        )?;
+    }
+    Ok(feature_vector)
 }
 
-pub async fn get_feature_vectors(fview_obj: &FeatureView, entries: Vec<EntryValuesPayload>, passed_values: Option<Vec<PassedValuesPayload>>, rest_read_options: Option<FeatureVectorRestReadOptions>) -> Result<BatchFeatureVectors> {
-    controller::get_feature_vectors(fview_obj.feature_store_id(), fview_obj.name(), fview_obj.version(), entries, passed_values, rest_read_options.unwrap_or_default()).await
+/// Read the feature vectors of `entries` from the online store REST server,
+/// see [`get_feature_vector`] for the complex features.
+pub async fn get_feature_vectors(
+    fview_obj: &FeatureView,
+    entries: Vec<EntryValuesPayload>,
+    passed_values: Option<Vec<PassedValuesPayload>>,
+    rest_read_options: Option<FeatureVectorRestReadOptions>,
+) -> Result<BatchFeatureVectors> {
+    let complex_feature_types = complex_feature_types(fview_obj);
+    let mut feature_vectors = controller::get_feature_vectors(
+        fview_obj.feature_store_id(),
+        fview_obj.name(),
+        fview_obj.version(),
+        entries,
+        passed_values,
+        with_feature_names(rest_read_options, &complex_feature_types),
+    )
+    .await?;
+    if !complex_feature_types.is_empty() {
+        let feature_names = feature_names(feature_vectors.metadata())?;
+        for feature_values in feature_vectors.features_mut() {
+            decode_complex_feature_values(&complex_feature_types, &feature_names, feature_values)?;
+        }
+    }
+    Ok(feature_vectors)
 }
 
-#[cfg(feature = "blocking")]
-pub fn init_online_store_rest_client_blocking(api_key: &str, api_version: &str, reqwest_client: Option<reqwest::Client>, multithreaded: bool) -> Result<()> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded);
-    rt.block_on(init_online_store_rest_client(api_key, api_version, reqwest_client))
+// Hopsworks type of the complex features of the view, by name in the feature vectors
+fn complex_feature_types(fview_obj: &FeatureView) -> HashMap<String, String> {
+    query_result_features(fview_obj.query())
+        .into_iter()
+        .filter(|feature| is_complex_type(feature.data_type()))
+        .map(|feature| (feature.name().to_string(), feature.data_type().to_string()))
+        .collect()
 }
 
-#[cfg(feature = "blocking")]
-pub fn get_feature_vector_blocking(fview_obj: &FeatureView, entry: EntryValuesPayload, passed_values: Option<PassedValuesPayload>, rest_read_options: Option<FeatureVectorRestReadOptions>, multithreaded: bool) -> Result<SingleFeatureVector> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded);
-    let _guard = rt.enter();
+// Request the feature names needed to find the complex features in the feature vectors
+fn with_feature_names(
+    rest_read_options: Option<FeatureVectorRestReadOptions>,
+    complex_feature_types: &HashMap<String, String>,
+) -> FeatureVectorRestReadOptions {
+    let rest_read_options = rest_read_options.unwrap_or_default();
+    if complex_feature_types.is_empty() {
+        rest_read_options
+    } else {
+        rest_read_options.with_feature_name(true)
+    }
+}
+
+fn feature_names(metadata: Option<&[MetadataFeatureVector]>) -> Result<Vec<String>> {
+    metadata
+        .ok_or_else(|| {
+            HopsworksError::Validation(
+                "The online store REST server did not return the feature names.".to_string(),
+            )
+        })?
+        .iter()
+        .map(|metadata| {
+            metadata.feature_name().map(String::from).ok_or_else(|| {
+                HopsworksError::Validation(
+                    "The online store REST server returned a feature without name.".to_string(),
+                )
+            })
+        })
+        .collect()
+}
 
-    rt.block_on(get_feature_vector(fview_obj, entry, passed_values, rest_read_options))
+/// The REST server returns complex features as base64 strings of their Avro encoding,
+/// decode them into JSON arrays and objects. The values follow the order of `feature_names`.
+fn decode_complex_feature_values(
+    complex_feature_types: &HashMap<String, String>,
+    feature_names: &[String],
+    values: &mut [Value],
+) -> Result<()> {
+    for (name, value) in feature_names.iter().zip(values.iter_mut()) {
+        let Some(hopsworks_type) = complex_feature_types.get(name) else {
+            continue;
+        };
+        let Value::String(encoded) = value else {
+            continue;
+        };
+        let encoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded.as_bytes())
+            .map_err(|e| HopsworksError::Serialization(Box::new(e)))?;
+        *value = decode_complex_feature_value(&encoded, hopsworks_type)?;
+    }
+    Ok(())
 }
 
 #[cfg(feature = "blocking")]
-pub fn get_feature_vectors_blocking(fview_obj: &FeatureView, entries: Vec<EntryValuesPayload>, passed_values: Option<Vec<PassedValuesPayload>>, rest_read_options: Option<FeatureVectorRestReadOptions>, multithreaded: bool) -> Result<BatchFeatureVectors> {
-    let rt = hopsworks_core::get_hopsworks_runtime(multithreaded);
-    let _guard = rt.enter();
+pub fn init_online_store_rest_client_blocking(
+    api_key: &str,
+    api_version: &str,
+    reqwest_client: Option<reqwest::Client>,
+) -> Result<()> {
+    hopsworks_core::runtime::block_on(
+        None,
+        init_online_store_rest_client(api_key, api_version, reqwest_client),
+    )
+}
 
-    rt.block_on(get_feature_vectors(fview_obj, entries, passed_values, rest_read_options))
+#[cfg(feature = "blocking")]
+pub fn get_feature_vector_blocking(
+    fview_obj: &FeatureView,
+    entry: EntryValuesPayload,
+    passed_values: Option<PassedValuesPayload>,
+    rest_read_options: Option<FeatureVectorRestReadOptions>,
+) -> Result<SingleFeatureVector> {
+    hopsworks_core::runtime::block_on(
+        fview_obj.hopsworks_client(),
+        get_feature_vector(fview_obj, entry, passed_values, rest_read_options),
+    )
+}
+
+#[cfg(feature = "blocking")]
+pub fn get_feature_vectors_blocking(
+    fview_obj: &FeatureView,
+    entries: Vec<EntryValuesPayload>,
+    passed_values: Option<Vec<PassedValuesPayload>>,
+    rest_read_options: Option<FeatureVectorRestReadOptions>,
+) -> Result<BatchFeatureVectors> {
+    hopsworks_core::runtime::block_on(
+        fview_obj.hopsworks_client(),
+        get_feature_vectors(fview_obj, entries, passed_values, rest_read_options),
+    )
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decode_complex_feature_values_by_name() {
+        let complex_feature_types =
+            HashMap::from([("scores".to_string(), "array<bigint>".to_string())]);
+        // [1, 2] encoded in a union with null, with nullable items
+        let encoded = base64::engine::general_purpose::STANDARD.encode([2u8, 4, 2, 2, 2, 4, 0]);
+        let mut values = vec![json!(encoded), json!(7)];
+
+        decode_complex_feature_values(
+            &complex_feature_types,
+            &["scores".to_string(), "id".to_string()],
+            &mut values,
+        )
+        .unwrap();
+
+        assert_eq!(values, vec![json!([1, 2]), json!(7)]);
+    }
+}
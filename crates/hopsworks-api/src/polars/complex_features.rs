@@ -0,0 +1,206 @@
+//! Decode complex features, i.e. `array<...>` and `struct<...>` features, read from the feature store.
+//!
+//! Complex features are stored online as binary values holding the Avro encoding of each value,
+//! with the schema of the feature in a union with null, as produced when inserting into the feature group.
+use hopsworks_core::feature_store::feature_group::feature::Feature;
+use hopsworks_core::feature_store::query::Query;
+use hopsworks_core::{HopsworksError, Result};
+use polars::prelude::{AnyValue, ArrowField, ArrowSchema, DataFrame, DataType, NamedFrom, Series};
+use polars_arrow::datatypes::ArrowDataType;
+use polars_arrow::io::avro::avro_schema::file::Block;
+use polars_arrow::io::avro::{read, write};
+use serde_json::{json, Value};
+
+use super::{is_complex_type, polars_data_type};
+
+/// Replace the binary columns of `dataframe` holding complex features by nested columns,
+/// columns of other features or already decoded are left untouched.
+pub fn decode_complex_features(dataframe: &mut DataFrame, features: &[Feature]) -> Result<()> {
+    for feature in features
+        .iter()
+        .filter(|feature| is_complex_type(feature.data_type()))
+    {
+        let Ok(column) = dataframe.column(feature.name()) else {
+            continue;
+        };
+        if column.dtype() != &DataType::Binary {
+            continue;
+        }
+        let decoded = decode_complex_feature(column, feature.data_type())?;
+        dataframe
+            .replace(feature.name(), decoded)
+            .map_err(HopsworksError::other)?;
+    }
+    Ok(())
+}
+
+/// Features of `query` named as in its results, i.e. with the prefix of the join they come from.
+pub(crate) fn query_result_features(query: &Query) -> Vec<Feature> {
+    let mut features = query.left_features().clone();
+    for join in query.joins().into_iter().flatten() {
+        let prefix = join.prefix().unwrap_or_default();
+        features.extend(query_result_features(join.query()).iter().map(|feature| {
+            Feature::new(&format!("{prefix}{}", feature.name()), feature.data_type())
+        }));
+    }
+    features
+}
+
+/// Decode the Avro encoded values of the binary `series` into a nested series
+/// with the polars type of the complex Hopsworks type `hopsworks_type`.
+pub fn decode_complex_feature(series: &Series, hopsworks_type: &str) -> Result<Series> {
+    let data_type = polars_data_type(hopsworks_type).ok_or_else(|| {
+        HopsworksError::Validation(format!(
+            "Feature {} has type {hopsworks_type} which has no polars counterpart.",
+            series.name()
+        ))
+    })?;
+    let field = ArrowField::new(
+        series.name(),
+        avro_readable_data_type(&data_type.to_arrow(false)),
+        true,
+    );
+    let record = write::to_record(&ArrowSchema::from(vec![field.clone()]), "".to_string())
+        .map_err(HopsworksError::other)?;
+
+    let mut data = vec![];
+    for value in series.binary().map_err(HopsworksError::other)? {
+        match value {
+            Some(encoded) => data.extend_from_slice(encoded),
+            // Union index of null
+            None => data.push(0),
+        }
+    }
+    let block = Block::new(series.len(), data);
+    let array = read::deserialize(&block, &[field], &record.fields, &[true])
+        .map_err(|e| HopsworksError::Serialization(Box::new(e)))?
+        .into_arrays()
+        .pop()
+        .expect("a single field is deserialized");
+
+    Series::from_arrow(series.name(), array)
+        .and_then(|decoded| decoded.cast(&data_type))
+        .map_err(HopsworksError::other)
+}
+
+/// Decode a single Avro encoded value of a complex feature of type `hopsworks_type` into JSON,
+/// e.g. a complex feature of a feature vector read from the online store REST server.
+pub fn decode_complex_feature_value(encoded: &[u8], hopsworks_type: &str) -> Result<Value> {
+    let series = Series::new("value", [encoded]);
+    let decoded = decode_complex_feature(&series, hopsworks_type)?;
+    let value = decoded.get(0).map_err(HopsworksError::other)?;
+    Ok(any_value_to_json(value))
+}
+
+fn any_value_to_json(value: AnyValue) -> Value {
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(v) => json!(v),
+        AnyValue::Int8(v) => json!(v),
+        AnyValue::Int16(v) => json!(v),
+        AnyValue::Int32(v) => json!(v),
+        AnyValue::Int64(v) => json!(v),
+        AnyValue::UInt8(v) => json!(v),
+        AnyValue::UInt16(v) => json!(v),
+        AnyValue::UInt32(v) => json!(v),
+        AnyValue::UInt64(v) => json!(v),
+        AnyValue::Float32(v) => json!(v),
+        AnyValue::Float64(v) => json!(v),
+        AnyValue::String(v) => json!(v),
+        AnyValue::StringOwned(v) => json!(v.as_str()),
+        AnyValue::Binary(v) => json!(v),
+        AnyValue::BinaryOwned(v) => json!(v),
+        AnyValue::List(series) => Value::Array(series.iter().map(any_value_to_json).collect()),
+        AnyValue::Struct(_, _, fields) => {
+            let values = value._iter_struct_av().map(any_value_to_json);
+            Value::Object(
+                fields
+                    .iter()
+                    .map(|field| field.name().to_string())
+                    .zip(values)
+                    .collect(),
+            )
+        }
+        AnyValue::StructOwned(payload) => {
+            let (values, fields) = *payload;
+            Value::Object(
+                fields
+                    .iter()
+                    .map(|field| field.name().to_string())
+                    .zip(values.into_iter().map(any_value_to_json))
+                    .collect(),
+            )
+        }
+        // Dates, timestamps and decimals in their string representation
+        other => json!(other.to_string()),
+    }
+}
+
+// The Avro reader only builds arrays with 32-bit offsets
+fn avro_readable_data_type(data_type: &ArrowDataType) -> ArrowDataType {
+    match data_type {
+        ArrowDataType::LargeList(inner) | ArrowDataType::List(inner) => {
+            ArrowDataType::List(Box::new(ArrowField::new(
+                inner.name.as_str(),
+                avro_readable_data_type(inner.data_type()),
+                true,
+            )))
+        }
+        ArrowDataType::Struct(fields) => ArrowDataType::Struct(
+            fields
+                .iter()
+                .map(|field| {
+                    ArrowField::new(
+                        field.name.as_str(),
+                        avro_readable_data_type(field.data_type()),
+                        true,
+                    )
+                })
+                .collect(),
+        ),
+        ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View => ArrowDataType::Utf8,
+        ArrowDataType::LargeBinary | ArrowDataType::BinaryView => ArrowDataType::Binary,
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    // Avro encoding of the list [1, 2] with the schema ["null", {"type": "array", "items": ["null", "long"]}]
+    const ENCODED_LIST: [u8; 7] = [2, 4, 2, 2, 2, 4, 0];
+
+    #[test]
+    fn test_decode_complex_features() {
+        let mut df = df!(
+            "id" => [1i64, 2i64],
+            "values" => [Some(ENCODED_LIST.as_slice()), None],
+        )
+        .unwrap();
+        let features = vec![
+            Feature::new("id", "bigint"),
+            Feature::new("values", "array<bigint>"),
+        ];
+
+        decode_complex_features(&mut df, &features).unwrap();
+
+        let values = df.column("values").unwrap();
+        assert_eq!(values.dtype(), &DataType::List(Box::new(DataType::Int64)));
+        assert_eq!(values.null_count(), 1);
+        assert_eq!(
+            values.list().unwrap().get_as_series(0).unwrap(),
+            Series::new("", [1i64, 2i64])
+        );
+    }
+
+    #[test]
+    fn test_decode_complex_feature_value() {
+        assert_eq!(
+            decode_complex_feature_value(&ENCODED_LIST, "array<bigint>").unwrap(),
+            json!([1, 2])
+        );
+        assert!(decode_complex_feature_value(&ENCODED_LIST, "array<unknown>").is_err());
+    }
+}
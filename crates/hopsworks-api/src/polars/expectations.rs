@@ -0,0 +1,645 @@
+//! Validate polars [`DataFrame`]s against the [`ExpectationSuite`] of a [`FeatureGroup`].
+//!
+//! Expectations follow the Great Expectations naming used by Hopsworks, so a suite attached with the
+//! Python client is evaluated the same way here. The built-in expectations are evaluated with polars
+//! expressions, other expectation types can be implemented with [`register_expectation`].
+//! Expectation types which are neither built-in nor registered are skipped, they are left out of the report.
+use hopsworks_core::feature_store::feature_group::expectation_suite::{
+    ExpectationConfiguration, ExpectationSuite, IngestionResult, ValidationIngestionPolicy,
+    ValidationReport, ValidationResult,
+};
+use hopsworks_core::feature_store::FeatureGroup;
+use hopsworks_core::{HopsworksError, Result};
+use polars::prelude::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Check evaluated on a dataframe, stored in an [`ExpectationSuite`] as an [`ExpectationConfiguration`].
+pub trait Expectation: Send + Sync {
+    /// Configuration saved in the expectation suite of the feature group.
+    fn configuration(&self) -> ExpectationConfiguration;
+
+    /// Evaluate the expectation, a missing column is reported in the exception info of the result.
+    fn validate(&self, dataframe: &DataFrame) -> Result<ValidationResult>;
+}
+
+/// `expect_column_values_to_be_between`, bounds are inclusive and null values are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnValuesToBeBetween {
+    pub column: String,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+}
+
+/// `expect_column_values_to_not_be_null`, at least `mostly` of the values must be set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnValuesToNotBeNull {
+    pub column: String,
+    pub mostly: f64,
+}
+
+/// `expect_column_values_to_be_unique`, null values are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnValuesToBeUnique {
+    pub column: String,
+}
+
+/// `expect_column_values_to_be_in_set`, values are compared with their string representation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnValuesToBeInSet {
+    pub column: String,
+    pub value_set: Vec<String>,
+}
+
+/// `expect_column_values_to_match_regex`, the regex may match any part of the value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnValuesToMatchRegex {
+    pub column: String,
+    pub regex: String,
+}
+
+/// `expect_table_row_count_to_be_between`, bounds are inclusive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableRowCountToBeBetween {
+    pub min_value: Option<u64>,
+    pub max_value: Option<u64>,
+}
+
+impl ColumnValuesToBeBetween {
+    pub fn new(column: &str, min_value: Option<f64>, max_value: Option<f64>) -> Self {
+        Self {
+            column: column.to_string(),
+            min_value,
+            max_value,
+        }
+    }
+}
+
+impl ColumnValuesToNotBeNull {
+    pub fn new(column: &str) -> Self {
+        Self {
+            column: column.to_string(),
+            mostly: 1.0,
+        }
+    }
+
+    /// Allow a ratio `1 - mostly` of null values.
+    pub fn with_mostly(mut self, mostly: f64) -> Self {
+        self.mostly = mostly;
+        self
+    }
+}
+
+impl ColumnValuesToBeUnique {
+    pub fn new(column: &str) -> Self {
+        Self {
+            column: column.to_string(),
+        }
+    }
+}
+
+impl ColumnValuesToBeInSet {
+    pub fn new(column: &str, value_set: &[&str]) -> Self {
+        Self {
+            column: column.to_string(),
+            value_set: value_set.iter().map(|value| value.to_string()).collect(),
+        }
+    }
+}
+
+impl ColumnValuesToMatchRegex {
+    pub fn new(column: &str, regex: &str) -> Self {
+        Self {
+            column: column.to_string(),
+            regex: regex.to_string(),
+        }
+    }
+}
+
+impl TableRowCountToBeBetween {
+    pub fn new(min_value: Option<u64>, max_value: Option<u64>) -> Self {
+        Self {
+            min_value,
+            max_value,
+        }
+    }
+}
+
+impl Expectation for ColumnValuesToBeBetween {
+    fn configuration(&self) -> ExpectationConfiguration {
+        ExpectationConfiguration::new(
+            "expect_column_values_to_be_between",
+            json!({ "column": self.column, "min_value": self.min_value, "max_value": self.max_value }),
+        )
+    }
+
+    fn validate(&self, dataframe: &DataFrame) -> Result<ValidationResult> {
+        let value = col(&self.column).cast(DataType::Float64);
+        let mut out_of_range = lit(false);
+        if let Some(min_value) = self.min_value {
+            out_of_range = out_of_range.or(value.clone().lt(lit(min_value)));
+        }
+        if let Some(max_value) = self.max_value {
+            out_of_range = out_of_range.or(value.gt(lit(max_value)));
+        }
+        column_values_result(self.configuration(), dataframe, &self.column, out_of_range)
+    }
+}
+
+impl Expectation for ColumnValuesToNotBeNull {
+    fn configuration(&self) -> ExpectationConfiguration {
+        ExpectationConfiguration::new(
+            "expect_column_values_to_not_be_null",
+            json!({ "column": self.column, "mostly": self.mostly }),
+        )
+    }
+
+    fn validate(&self, dataframe: &DataFrame) -> Result<ValidationResult> {
+        let configuration = self.configuration();
+        let Some(series) = column_or_missing(dataframe, &self.column) else {
+            return Ok(missing_column_result(configuration, &self.column));
+        };
+        let element_count = series.len();
+        let null_count = series.null_count();
+        let null_percent = percent(null_count, element_count);
+        Ok(ValidationResult {
+            success: element_count == 0
+                || (element_count - null_count) as f64 / element_count as f64 >= self.mostly,
+            expectation_config: configuration,
+            result: json!({
+                "element_count": element_count,
+                "unexpected_count": null_count,
+                "unexpected_percent": null_percent,
+            }),
+            exception_info: no_exception(),
+        })
+    }
+}
+
+impl Expectation for ColumnValuesToBeUnique {
+    fn configuration(&self) -> ExpectationConfiguration {
+        ExpectationConfiguration::new(
+            "expect_column_values_to_be_unique",
+            json!({ "column": self.column }),
+        )
+    }
+
+    fn validate(&self, dataframe: &DataFrame) -> Result<ValidationResult> {
+        let duplicated = col(&self.column).is_duplicated();
+        column_values_result(self.configuration(), dataframe, &self.column, duplicated)
+    }
+}
+
+impl Expectation for ColumnValuesToBeInSet {
+    fn configuration(&self) -> ExpectationConfiguration {
+        ExpectationConfiguration::new(
+            "expect_column_values_to_be_in_set",
+            json!({ "column": self.column, "value_set": self.value_set }),
+        )
+    }
+
+    fn validate(&self, dataframe: &DataFrame) -> Result<ValidationResult> {
+        let value_set = Series::new("value_set", self.value_set.as_slice());
+        let not_in_set = col(&self.column)
+            .cast(DataType::String)
+            .is_in(lit(value_set))
+            .not();
+        column_values_result(self.configuration(), dataframe, &self.column, not_in_set)
+    }
+}
+
+impl Expectation for ColumnValuesToMatchRegex {
+    fn configuration(&self) -> ExpectationConfiguration {
+        ExpectationConfiguration::new(
+            "expect_column_values_to_match_regex",
+            json!({ "column": self.column, "regex": self.regex }),
+        )
+    }
+
+    fn validate(&self, dataframe: &DataFrame) -> Result<ValidationResult> {
+        let not_matching = col(&self.column)
+            .cast(DataType::String)
+            .str()
+            .contains(lit(self.regex.as_str()), true)
+            .not();
+        column_values_result(self.configuration(), dataframe, &self.column, not_matching)
+    }
+}
+
+impl Expectation for TableRowCountToBeBetween {
+    fn configuration(&self) -> ExpectationConfiguration {
+        ExpectationConfiguration::new(
+            "expect_table_row_count_to_be_between",
+            json!({ "min_value": self.min_value, "max_value": self.max_value }),
+        )
+    }
+
+    fn validate(&self, dataframe: &DataFrame) -> Result<ValidationResult> {
+        let row_count = dataframe.height() as u64;
+        Ok(ValidationResult {
+            success: self.min_value.map_or(true, |min| row_count >= min)
+                && self.max_value.map_or(true, |max| row_count <= max),
+            expectation_config: self.configuration(),
+            result: json!({ "observed_value": row_count }),
+            exception_info: no_exception(),
+        })
+    }
+}
+
+/// Build an [`Expectation`] from its configuration, `None` if the configuration is not valid.
+pub type ExpectationFactory =
+    Arc<dyn Fn(&ExpectationConfiguration) -> Option<Box<dyn Expectation>> + Send + Sync>;
+
+static EXPECTATION_REGISTRY: OnceLock<RwLock<HashMap<String, ExpectationFactory>>> =
+    OnceLock::new();
+
+fn expectation_registry() -> &'static RwLock<HashMap<String, ExpectationFactory>> {
+    EXPECTATION_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Evaluate the expectations of type `expectation_type` with the [`Expectation`] built by `factory`,
+/// replacing the built-in or previously registered implementation of the type.
+///
+/// # Example
+/// ```no_run
+/// use hopsworks_api::polars::expectations::{register_expectation, Expectation};
+/// use hopsworks_api::{ExpectationConfiguration, Result, ValidationResult};
+/// use polars::prelude::*;
+/// use serde_json::json;
+/// use std::sync::Arc;
+///
+/// struct ColumnMaxToBeAtMost {
+///     column: String,
+///     max_value: f64,
+/// }
+///
+/// impl Expectation for ColumnMaxToBeAtMost {
+///     fn configuration(&self) -> ExpectationConfiguration {
+///         ExpectationConfiguration::new(
+///             "expect_column_max_to_be_between",
+///             json!({ "column": self.column, "max_value": self.max_value }),
+///         )
+///     }
+///
+///     fn validate(&self, dataframe: &DataFrame) -> Result<ValidationResult> {
+///         let max = dataframe.column(&self.column).ok().and_then(|series| series.max::<f64>().ok().flatten());
+///         Ok(ValidationResult {
+///             success: max.map_or(true, |max| max <= self.max_value),
+///             expectation_config: self.configuration(),
+///             result: json!({ "observed_value": max }),
+///             exception_info: json!({ "raised_exception": false }),
+///         })
+///     }
+/// }
+///
+/// register_expectation(
+///     "expect_column_max_to_be_between",
+///     Arc::new(|configuration| {
+///         Some(Box::new(ColumnMaxToBeAtMost {
+///             column: configuration.column()?.to_string(),
+///             max_value: configuration.kwargs.get("max_value")?.as_f64()?,
+///         }) as Box<dyn Expectation>)
+///     }),
+/// );
+/// ```
+pub fn register_expectation(expectation_type: &str, factory: ExpectationFactory) {
+    expectation_registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(expectation_type.to_string(), factory);
+}
+
+/// Registered or built-in expectation matching the configuration, `None` if its type is not supported.
+pub fn expectation_from_configuration(
+    configuration: &ExpectationConfiguration,
+) -> Option<Box<dyn Expectation>> {
+    let registered = expectation_registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(configuration.expectation_type.as_str())
+        .cloned();
+    match registered {
+        Some(factory) => factory(configuration),
+        None => built_in_expectation(configuration),
+    }
+}
+
+fn built_in_expectation(configuration: &ExpectationConfiguration) -> Option<Box<dyn Expectation>> {
+    let kwargs = &configuration.kwargs;
+    let column = configuration.column().map(String::from);
+    let f64_kwarg = |name: &str| kwargs.get(name).and_then(Value::as_f64);
+    let u64_kwarg = |name: &str| kwargs.get(name).and_then(Value::as_u64);
+
+    match configuration.expectation_type.as_str() {
+        "expect_column_values_to_be_between" => Some(Box::new(ColumnValuesToBeBetween {
+            column: column?,
+            min_value: f64_kwarg("min_value"),
+            max_value: f64_kwarg("max_value"),
+        })),
+        "expect_column_values_to_not_be_null" => Some(Box::new(ColumnValuesToNotBeNull {
+            column: column?,
+            mostly: f64_kwarg("mostly").unwrap_or(1.0),
+        })),
+        "expect_column_values_to_be_unique" => {
+            Some(Box::new(ColumnValuesToBeUnique { column: column? }))
+        }
+        "expect_column_values_to_be_in_set" => Some(Box::new(ColumnValuesToBeInSet {
+            column: column?,
+            value_set: kwargs
+                .get("value_set")?
+                .as_array()?
+                .iter()
+                .map(|value| match value {
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                })
+                .collect(),
+        })),
+        "expect_column_values_to_match_regex" => Some(Box::new(ColumnValuesToMatchRegex {
+            column: column?,
+            regex: kwargs.get("regex")?.as_str()?.to_string(),
+        })),
+        "expect_table_row_count_to_be_between" => Some(Box::new(TableRowCountToBeBetween {
+            min_value: u64_kwarg("min_value"),
+            max_value: u64_kwarg("max_value"),
+        })),
+        _ => None,
+    }
+}
+
+/// Validate `dataframe` against every expectation of the suite, skipping the expectations whose
+/// type is not supported, see [`expectation_from_configuration`].
+///
+/// # Example
+/// ```no_run
+/// # use color_eyre::Result;
+/// use hopsworks_api::polars::expectations::{validate_expectation_suite, ColumnValuesToBeBetween, Expectation};
+/// use hopsworks_api::ExpectationSuite;
+/// use polars::prelude::*;
+///
+/// # fn run() -> Result<()> {
+/// let suite = ExpectationSuite::new("demo_suite")
+///     .with_expectation(ColumnValuesToBeBetween::new("value", Some(0.0), Some(1.0)).configuration());
+///
+/// let df = df!("id" => [1i64, 2i64], "value" => [0.5f64, 1.5f64])?;
+/// let report = validate_expectation_suite(&df, &suite)?;
+/// assert!(!report.success());
+/// # Ok(())
+/// # }
+/// ```
+pub fn validate_expectation_suite(
+    dataframe: &DataFrame,
+    expectation_suite: &ExpectationSuite,
+) -> Result<ValidationReport> {
+    let results = expectation_suite
+        .expectations()
+        .iter()
+        .filter_map(
+            |configuration| match expectation_from_configuration(configuration) {
+                Some(expectation) => Some(expectation.validate(dataframe)),
+                None => {
+                    tracing::warn!(
+                        "Skipping expectation {} of suite {}, its type is not supported by the Rust client.",
+                        configuration.expectation_type,
+                        expectation_suite.name()
+                    );
+                    None
+                }
+            },
+        )
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ValidationReport::new(results))
+}
+
+/// Validate `dataframe` against the [`ExpectationSuite`] attached to `fg` before inserting it.
+///
+/// Returns `None` if no suite is attached or its validation is disabled. If the validation fails and
+/// the [`ValidationIngestionPolicy`] of the suite is [`ValidationIngestionPolicy::Strict`], the report
+/// is uploaded as rejected and an error is returned. Otherwise the report is returned to be uploaded
+/// with [`save_ingested_validation_report`] once the rows are inserted.
+pub async fn validate_for_insert(
+    dataframe: &DataFrame,
+    fg: &FeatureGroup,
+) -> Result<Option<ValidationReport>> {
+    let expectation_suite = fg.get_expectation_suite().await?;
+    validate_with_expectation_suite(dataframe, fg, expectation_suite.as_ref()).await
+}
+
+/// Same as [`validate_for_insert`] with the `expectation_suite` of `fg` fetched beforehand,
+/// e.g. once for all the writes of a [`FeatureGroupWriter`](crate::kafka::FeatureGroupWriter).
+pub async fn validate_with_expectation_suite(
+    dataframe: &DataFrame,
+    fg: &FeatureGroup,
+    expectation_suite: Option<&ExpectationSuite>,
+) -> Result<Option<ValidationReport>> {
+    let Some(expectation_suite) = expectation_suite else {
+        return Ok(None);
+    };
+    if !expectation_suite.run_validation() {
+        return Ok(None);
+    }
+
+    let report = validate_expectation_suite(dataframe, expectation_suite)?;
+    if !report.success()
+        && expectation_suite.validation_ingestion_policy() == ValidationIngestionPolicy::Strict
+    {
+        let report = fg
+            .save_validation_report(&report.with_ingestion_result(IngestionResult::Rejected))
+            .await?;
+        let failed: Vec<&str> = report
+            .failed_results()
+            .map(|result| result.expectation_config.expectation_type.as_str())
+            .collect();
+        return Err(HopsworksError::Validation(format!(
+            "Data rejected by the expectation suite {} of Feature Group {}, failed expectations: {:?}",
+            expectation_suite.name(),
+            fg.name(),
+            failed
+        )));
+    }
+    Ok(Some(report))
+}
+
+/// Upload the report returned by [`validate_for_insert`] once the validated rows are inserted.
+/// The rows are already in the feature group, so a failing upload is logged rather than returned.
+pub async fn save_ingested_validation_report(
+    fg: &FeatureGroup,
+    report: ValidationReport,
+) -> Option<ValidationReport> {
+    match fg
+        .save_validation_report(&report.with_ingestion_result(IngestionResult::Ingested))
+        .await
+    {
+        Ok(report) => Some(report),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to upload the validation report of Feature Group {}: {}",
+                fg.name(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Result of an expectation on the values of `column`, failing if any non-null value is unexpected.
+fn column_values_result(
+    configuration: ExpectationConfiguration,
+    dataframe: &DataFrame,
+    column: &str,
+    unexpected: Expr,
+) -> Result<ValidationResult> {
+    if column_or_missing(dataframe, column).is_none() {
+        return Ok(missing_column_result(configuration, column));
+    }
+    let counts = dataframe
+        .clone()
+        .lazy()
+        .select([
+            col(column).null_count().alias("missing_count"),
+            unexpected
+                .and(col(column).is_not_null())
+                .sum()
+                .alias("unexpected_count"),
+        ])
+        .collect()
+        .map_err(HopsworksError::other)?;
+    let count = |name: &str| -> Result<usize> {
+        Ok(counts
+            .column(name)
+            .and_then(|series| series.cast(&DataType::UInt64))
+            .map_err(HopsworksError::other)?
+            .u64()
+            .map_err(HopsworksError::other)?
+            .get(0)
+            .unwrap_or(0) as usize)
+    };
+    let element_count = dataframe.height();
+    let missing_count = count("missing_count")?;
+    let unexpected_count = count("unexpected_count")?;
+    let non_missing_count = element_count - missing_count;
+
+    Ok(ValidationResult {
+        success: unexpected_count == 0,
+        expectation_config: configuration,
+        result: json!({
+            "element_count": element_count,
+            "missing_count": missing_count,
+            "unexpected_count": unexpected_count,
+            "unexpected_percent": percent(unexpected_count, non_missing_count),
+        }),
+        exception_info: no_exception(),
+    })
+}
+
+fn column_or_missing<'a>(dataframe: &'a DataFrame, column: &str) -> Option<&'a Series> {
+    dataframe.column(column).ok()
+}
+
+fn missing_column_result(
+    configuration: ExpectationConfiguration,
+    column: &str,
+) -> ValidationResult {
+    ValidationResult {
+        success: false,
+        expectation_config: configuration,
+        result: json!({}),
+        exception_info: exception(&format!("Column {column} not found in the dataframe.")),
+    }
+}
+
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        100.0 * count as f64 / total as f64
+    }
+}
+
+fn no_exception() -> Value {
+    json!({ "raised_exception": false, "exception_message": null, "exception_traceback": null })
+}
+
+fn exception(message: &str) -> Value {
+    json!({ "raised_exception": true, "exception_message": message, "exception_traceback": null })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_expectation_suite() {
+        let df = df!(
+            "id" => [Some(1i64), Some(2), Some(2), None],
+            "value" => [0.5f64, 1.5, 0.2, 0.9],
+            "country" => ["SE", "DE", "US", "se"],
+        )
+        .unwrap();
+        let suite = ExpectationSuite::new("test_suite")
+            .with_expectation(
+                ColumnValuesToBeBetween::new("value", Some(0.0), Some(1.0)).configuration(),
+            )
+            .with_expectation(
+                ColumnValuesToNotBeNull::new("id")
+                    .with_mostly(0.7)
+                    .configuration(),
+            )
+            .with_expectation(ColumnValuesToBeUnique::new("id").configuration())
+            .with_expectation(
+                ColumnValuesToBeInSet::new("country", &["SE", "DE", "US"]).configuration(),
+            )
+            .with_expectation(
+                ColumnValuesToMatchRegex::new("country", "^[A-Z]{2}$").configuration(),
+            )
+            .with_expectation(TableRowCountToBeBetween::new(Some(1), Some(10)).configuration())
+            .with_expectation(ColumnValuesToBeUnique::new("missing").configuration())
+            .with_expectation(ExpectationConfiguration::new(
+                "expect_column_kl_divergence_to_be_less_than",
+                json!({ "column": "value" }),
+            ));
+
+        let report = validate_expectation_suite(&df, &suite).unwrap();
+
+        let successes: Vec<bool> = report.results().iter().map(|r| r.success).collect();
+        assert_eq!(
+            successes,
+            vec![false, true, false, false, false, true, false]
+        );
+        assert!(!report.success());
+        assert_eq!(report.results()[0].result["unexpected_count"], 1);
+        assert_eq!(report.results()[2].result["unexpected_count"], 2);
+        assert_eq!(report.results()[3].result["unexpected_count"], 1);
+        assert_eq!(report.results()[6].exception_info["raised_exception"], true);
+    }
+
+    #[test]
+    fn test_register_expectation() {
+        let df = df!("value" => [0.5f64, 1.5]).unwrap();
+        let configuration = ExpectationConfiguration::new(
+            "expect_column_max_to_be_between",
+            json!({ "column": "value", "max_value": 1.0 }),
+        );
+        let suite = ExpectationSuite::new("test_suite").with_expectation(configuration.clone());
+        assert!(validate_expectation_suite(&df, &suite)
+            .unwrap()
+            .results()
+            .is_empty());
+
+        register_expectation(
+            "expect_column_max_to_be_between",
+            Arc::new(|configuration| {
+                Some(Box::new(ColumnValuesToBeBetween::new(
+                    configuration.column()?,
+                    None,
+                    configuration.kwargs.get("max_value")?.as_f64(),
+                )) as Box<dyn Expectation>)
+            }),
+        );
+        let report = validate_expectation_suite(&df, &suite).unwrap();
+
+        assert_eq!(report.results().len(), 1);
+        assert!(!report.success());
+    }
+}
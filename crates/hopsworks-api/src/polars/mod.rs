@@ -0,0 +1,328 @@
+use hopsworks_core::feature_store::{feature_group::feature::Feature, FeatureGroup};
+use hopsworks_core::{HopsworksError, Result};
+use polars::prelude::{DataFrame, DataType, Field, Schema, TimeUnit};
+use std::collections::HashMap;
+
+pub mod complex_features;
+pub mod expectations;
+pub mod statistics;
+pub mod validation;
+
+/// Names and Hopsworks types of the columns of `schema`, see [`convert_polars_data_type`].
+pub fn extract_features_from_polars_schema(schema: Schema) -> Result<(Vec<String>, Vec<String>)> {
+    extract_features_from_polars_schema_with_overrides(schema, &HashMap::new())
+}
+
+/// Names and Hopsworks types of the columns of `schema`, using the Hopsworks type in `type_overrides`
+/// for the columns it contains, e.g. `decimal(10,2)` or `string`, and [`convert_polars_data_type`]
+/// for the others. Fails if an override names a column missing from `schema`.
+pub fn extract_features_from_polars_schema_with_overrides(
+    schema: Schema,
+    type_overrides: &HashMap<String, String>,
+) -> Result<(Vec<String>, Vec<String>)> {
+    if let Some(unknown) = type_overrides
+        .keys()
+        .find(|name| schema.get(name.as_str()).is_none())
+    {
+        return Err(HopsworksError::Validation(format!(
+            "Type override for column {unknown} which is not in the dataframe."
+        )));
+    }
+    let feature_names: Vec<String> = schema.iter_names().map(|name| name.to_string()).collect();
+    let feature_types = schema
+        .iter()
+        .map(|(name, dtype)| match type_overrides.get(name.as_str()) {
+            Some(hopsworks_type) => Ok(hopsworks_type.clone()),
+            None => convert_polars_data_type(dtype),
+        })
+        .collect::<Result<Vec<String>>>()?;
+
+    Ok((feature_names, feature_types))
+}
+
+/// Register the feature group `fg` with the schema of `dataframe`, see
+/// [`extract_features_from_polars_schema_with_overrides`] for the `type_overrides`.
+///
+/// # Example
+/// ```no_run
+/// # use color_eyre::Result;
+/// use polars::prelude::*;
+/// use std::collections::HashMap;
+///
+/// # async fn run() -> Result<()> {
+/// let feature_store = hopsworks_api::login(None, hopsworks_api::RuntimeConfig::Current).await?
+///   .get_feature_store().await?;
+/// let mut feature_group = feature_store.create_feature_group("prices", 1, None, vec!["id"], None, false)?;
+///
+/// let df = df!("id" => [1i64], "price" => [9.99f64])?;
+/// let type_overrides = HashMap::from([("price".to_string(), "decimal(10,2)".to_string())]);
+/// hopsworks_api::polars::register_feature_group_from(&mut feature_group, &df, &type_overrides).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn register_feature_group_from(
+    fg: &mut FeatureGroup,
+    dataframe: &DataFrame,
+    type_overrides: &HashMap<String, String>,
+) -> Result<()> {
+    let (feature_names, feature_types) =
+        extract_features_from_polars_schema_with_overrides(dataframe.schema(), type_overrides)?;
+    fg.register_feature_group(&feature_names, &feature_types)
+        .await
+}
+
+/// Append the columns of `dataframe` missing from the schema of the registered feature group `fg`,
+/// see [`FeatureGroup::evolve_schema`]. Returns the appended features.
+///
+/// # Example
+/// ```no_run
+/// # use color_eyre::Result;
+/// use polars::prelude::*;
+///
+/// # async fn run() -> Result<()> {
+/// let mut feature_group = hopsworks_api::login(None, hopsworks_api::RuntimeConfig::Current).await?
+///   .get_feature_store().await?
+///   .get_feature_group("demo_feature_group", Some(1)).await?
+///   .expect("Feature group not found");
+///
+/// let df = df!("id" => [1i64], "new_feature" => [0.5f64])?;
+/// let appended = hopsworks_api::polars::evolve_schema_from(&mut feature_group, &df).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn evolve_schema_from(
+    fg: &mut FeatureGroup,
+    dataframe: &DataFrame,
+) -> Result<Vec<Feature>> {
+    let (feature_names, feature_types) = extract_features_from_polars_schema(dataframe.schema())?;
+    fg.evolve_schema(&feature_names, &feature_types).await
+}
+
+/// Hopsworks type of a column with the polars `data_type`, e.g. `bigint` for [`DataType::Int64`].
+/// Fails for types without Hopsworks equivalent, e.g. [`DataType::Null`] or [`DataType::Object`].
+///
+/// Types without Avro counterpart are cast to the Hopsworks type when inserted: small and unsigned
+/// integers to `int` or `bigint`, failing for `UInt64` values above [`i64::MAX`], [`DataType::Time`]
+/// to its nanoseconds since midnight and [`DataType::Duration`] to its number of time units.
+pub fn convert_polars_data_type(data_type: &DataType) -> Result<String> {
+    hopsworks_data_type(data_type).ok_or_else(|| {
+        HopsworksError::Validation(format!(
+            "Polars data type {data_type} has no Hopsworks equivalent, set the feature type with a type override."
+        ))
+    })
+}
+
+pub(crate) fn hopsworks_data_type(data_type: &DataType) -> Option<String> {
+    // polars to hopsworks data type
+    let hopsworks_type = match data_type {
+        DataType::Boolean => "boolean",
+        DataType::Int8 => "int",
+        DataType::UInt8 => "int",
+        DataType::Int16 => "int",
+        DataType::UInt16 => "int",
+        DataType::Int32 => "int",
+        DataType::UInt32 => "bigint",
+        DataType::Int64 => "bigint",
+        DataType::UInt64 => "bigint",
+        DataType::Float32 => "float",
+        DataType::Float64 => "double",
+        // Spark defaults to a precision of 38 when it is not set
+        DataType::Decimal(precision, scale) => {
+            return Some(format!(
+                "decimal({},{})",
+                precision.unwrap_or(38),
+                scale.unwrap_or(0)
+            ))
+        }
+        DataType::Datetime(_, _) => "timestamp",
+        DataType::Duration(_) => "bigint",
+        // Nanoseconds since midnight
+        DataType::Time => "bigint",
+        DataType::Date => "date",
+        DataType::String => "string",
+        DataType::Categorical(_, _) | DataType::Enum(_, _) => "string",
+        DataType::Array(inner, _) | DataType::List(inner) => {
+            return Some(format!("array<{}>", hopsworks_data_type(inner)?))
+        }
+        DataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| {
+                    Some(format!(
+                        "{}:{}",
+                        field.name(),
+                        hopsworks_data_type(field.data_type())?
+                    ))
+                })
+                .collect::<Option<Vec<String>>>()?;
+            return Some(format!("struct<{}>", fields.join(",")));
+        }
+        DataType::Binary | DataType::BinaryOffset => "binary",
+        // Null, Object and Unknown columns hold no typed values. polars only has 128-bit
+        // integers as the physical type of Decimal, which maps to decimal above.
+        _ => return None,
+    };
+    Some(hopsworks_type.to_string())
+}
+
+/// Polars type of the column holding a feature of the Hopsworks type `hopsworks_type`,
+/// including complex types, e.g. `array<struct<name:string,score:double>>`.
+pub fn polars_data_type(hopsworks_type: &str) -> Option<DataType> {
+    let hopsworks_type = hopsworks_type.trim();
+    let lowercase = hopsworks_type.to_lowercase();
+    if let Some(inner) = complex_type_inner(hopsworks_type, &lowercase, "array<") {
+        return Some(DataType::List(Box::new(polars_data_type(inner)?)));
+    }
+    if let Some(inner) = complex_type_inner(hopsworks_type, &lowercase, "struct<") {
+        let fields = split_top_level(inner)
+            .into_iter()
+            .map(|field| {
+                let (name, data_type) = field.split_once(':')?;
+                Some(Field::new(name.trim(), polars_data_type(data_type)?))
+            })
+            .collect::<Option<Vec<Field>>>()?;
+        return Some(DataType::Struct(fields));
+    }
+    if let Some(precision_and_scale) = lowercase
+        .strip_prefix("decimal(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let (precision, scale) = precision_and_scale.split_once(',')?;
+        return Some(DataType::Decimal(
+            Some(precision.trim().parse().ok()?),
+            Some(scale.trim().parse().ok()?),
+        ));
+    }
+    match lowercase.as_str() {
+        "boolean" => Some(DataType::Boolean),
+        "tinyint" => Some(DataType::Int8),
+        "smallint" => Some(DataType::Int16),
+        "int" => Some(DataType::Int32),
+        "bigint" => Some(DataType::Int64),
+        "float" => Some(DataType::Float32),
+        "double" => Some(DataType::Float64),
+        "string" => Some(DataType::String),
+        "date" => Some(DataType::Date),
+        "timestamp" => Some(DataType::Datetime(TimeUnit::Microseconds, None)),
+        "binary" => Some(DataType::Binary),
+        _ => None,
+    }
+}
+
+/// Whether `hopsworks_type` is an `array<...>` or `struct<...>` type.
+pub fn is_complex_type(hopsworks_type: &str) -> bool {
+    let lowercase = hopsworks_type.trim().to_lowercase();
+    lowercase.starts_with("array<") || lowercase.starts_with("struct<")
+}
+
+// Content of the angle brackets of `hopsworks_type` if it starts with `prefix`, sliced from the
+// original string to keep the case of struct field names
+fn complex_type_inner<'a>(
+    hopsworks_type: &'a str,
+    lowercase: &str,
+    prefix: &str,
+) -> Option<&'a str> {
+    if lowercase.starts_with(prefix) && lowercase.ends_with('>') {
+        Some(&hopsworks_type[prefix.len()..hopsworks_type.len() - 1])
+    } else {
+        None
+    }
+}
+
+// Split the fields of a struct type on the commas which are not nested in another type
+fn split_top_level(fields: &str) -> Vec<&str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut parts = vec![];
+    for (idx, c) in fields.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&fields[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&fields[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_convert_polars_data_type() {
+        assert_eq!(convert_polars_data_type(&DataType::UInt8).unwrap(), "int");
+        assert_eq!(
+            convert_polars_data_type(&DataType::Decimal(Some(10), Some(2))).unwrap(),
+            "decimal(10,2)"
+        );
+        assert_eq!(
+            convert_polars_data_type(&DataType::Datetime(TimeUnit::Milliseconds, None)).unwrap(),
+            "timestamp"
+        );
+        assert_eq!(convert_polars_data_type(&DataType::Time).unwrap(), "bigint");
+        assert!(matches!(
+            convert_polars_data_type(&DataType::Null),
+            Err(HopsworksError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_complex_types_round_trip() {
+        let data_type = DataType::List(Box::new(DataType::Struct(vec![
+            Field::new("name", DataType::String),
+            Field::new("scores", DataType::List(Box::new(DataType::Float64))),
+        ])));
+        let hopsworks_type = convert_polars_data_type(&data_type).unwrap();
+        assert_eq!(
+            hopsworks_type,
+            "array<struct<name:string,scores:array<double>>>"
+        );
+        assert!(is_complex_type(&hopsworks_type));
+        assert_eq!(polars_data_type(&hopsworks_type), Some(data_type));
+        assert_eq!(
+            convert_polars_data_type(&DataType::Array(Box::new(DataType::Float32), 3)).unwrap(),
+            "array<float>"
+        );
+        assert_eq!(
+            polars_data_type("STRUCT<Id: bigint, price: decimal(10, 2)>"),
+            Some(DataType::Struct(vec![
+                Field::new("Id", DataType::Int64),
+                Field::new("price", DataType::Decimal(Some(10), Some(2))),
+            ]))
+        );
+        assert!(convert_polars_data_type(&DataType::List(Box::new(DataType::Null))).is_err());
+    }
+
+    #[test]
+    fn test_extract_features_with_overrides() {
+        let df = df!(
+            "id" => [1i64],
+            "price" => [9.99f64],
+            "empty" => Series::full_null("empty", 1, &DataType::Null),
+        )
+        .unwrap();
+        assert!(extract_features_from_polars_schema(df.schema()).is_err());
+
+        let type_overrides = HashMap::from([
+            ("price".to_string(), "decimal(10,2)".to_string()),
+            ("empty".to_string(), "string".to_string()),
+        ]);
+        let (feature_names, feature_types) =
+            extract_features_from_polars_schema_with_overrides(df.schema(), &type_overrides)
+                .unwrap();
+        assert_eq!(feature_names, vec!["id", "price", "empty"]);
+        assert_eq!(feature_types, vec!["bigint", "decimal(10,2)", "string"]);
+
+        let unknown_override = HashMap::from([("missing".to_string(), "string".to_string())]);
+        assert!(matches!(
+            extract_features_from_polars_schema_with_overrides(df.schema(), &unknown_override),
+            Err(HopsworksError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,520 @@
+//! Compute descriptive statistics of polars [`DataFrame`]s in the Hopsworks statistics format.
+//!
+//! The metrics follow the [`StatisticsConfig`] of the [`FeatureGroup`]: descriptive metrics are always
+//! computed, histograms, correlations and exact uniqueness metrics only if enabled in the config.
+use hopsworks_core::feature_store::feature_group::statistics::{
+    Correlation, FeatureDescriptiveStatistics, HistogramBin, Statistics,
+};
+use hopsworks_core::feature_store::feature_group::statistics_config::StatisticsConfig;
+use hopsworks_core::feature_store::FeatureGroup;
+use hopsworks_core::{HopsworksError, Result};
+use polars::prelude::*;
+
+/// Number of bins of the histogram of numerical features, categorical features get one bin
+/// for each of their most frequent values.
+const HISTOGRAM_BINS: usize = 20;
+
+/// Compute the statistics of the `columns` of the config, all the columns of `dataframe` if empty.
+/// Like in the Python client, complex and binary columns have no statistics and are skipped.
+///
+/// # Example
+/// ```no_run
+/// # use color_eyre::Result;
+/// use hopsworks_api::polars::statistics::compute_statistics;
+/// use hopsworks_api::StatisticsConfig;
+/// use polars::prelude::*;
+///
+/// # fn run() -> Result<()> {
+/// let df = df!("id" => [1i64, 2i64, 3i64], "value" => [0.5f64, 0.7f64, 0.9f64])?;
+/// let config = StatisticsConfig {
+///     enabled: true,
+///     histograms: true,
+///     correlations: true,
+///     exact_uniqueness: true,
+///     columns: vec![],
+/// };
+/// let statistics = compute_statistics(&df, &config)?;
+/// assert_eq!(statistics.feature_statistics("value").unwrap().max, Some(0.9));
+/// # Ok(())
+/// # }
+/// ```
+pub fn compute_statistics(dataframe: &DataFrame, config: &StatisticsConfig) -> Result<Statistics> {
+    let columns: Vec<String> = if config.columns.is_empty() {
+        dataframe
+            .get_column_names()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    } else {
+        config.columns.clone()
+    };
+
+    let mut numerical_values = vec![];
+    let mut feature_descriptive_statistics = vec![];
+    for column in &columns {
+        let series = dataframe.column(column).map_err(|_| {
+            HopsworksError::Validation(format!(
+                "Column {column} of the statistics config not found in the dataframe."
+            ))
+        })?;
+        if !has_statistics(series.dtype()) {
+            tracing::debug!(
+                "Skipping statistics of column {column} with type {}",
+                series.dtype()
+            );
+            continue;
+        }
+        let (statistics, values) = compute_feature_statistics(dataframe, series, config)?;
+        if let Some(values) = values {
+            numerical_values.push((column.as_str(), values));
+        }
+        feature_descriptive_statistics.push(statistics);
+    }
+
+    if config.correlations {
+        for statistics in feature_descriptive_statistics.iter_mut() {
+            let Some((_, values)) = numerical_values
+                .iter()
+                .find(|(column, _)| *column == statistics.feature_name)
+            else {
+                continue;
+            };
+            statistics.correlations = Some(
+                numerical_values
+                    .iter()
+                    .filter(|(column, _)| *column != statistics.feature_name)
+                    .filter_map(|(column, other_values)| {
+                        pearson_correlation(values, other_values).map(|correlation| Correlation {
+                            column: column.to_string(),
+                            correlation,
+                        })
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    Ok(Statistics::new(feature_descriptive_statistics))
+}
+
+/// Compute the statistics of `dataframe` with the [`StatisticsConfig`] of `fg` and upload them.
+/// Descriptive metrics of all the columns are computed if the feature group has no config.
+pub async fn compute_and_save_statistics(
+    dataframe: &DataFrame,
+    fg: &FeatureGroup,
+) -> Result<Statistics> {
+    let default_config;
+    let config = match fg.statistics_config() {
+        Some(config) => config,
+        None => {
+            default_config = StatisticsConfig {
+                enabled: true,
+                histograms: false,
+                correlations: false,
+                exact_uniqueness: false,
+                columns: vec![],
+            };
+            &default_config
+        }
+    };
+    fg.save_statistics(&compute_statistics(dataframe, config)?)
+        .await
+}
+
+/// Compute the statistics of data about to be inserted in `fg`, `None` if statistics are disabled.
+/// They are uploaded with [`save_inserted_statistics`] once the rows are inserted.
+pub fn statistics_for_insert(
+    dataframe: &DataFrame,
+    fg: &FeatureGroup,
+) -> Result<Option<Statistics>> {
+    match fg.statistics_config() {
+        Some(config) if config.enabled => Ok(Some(compute_statistics(dataframe, config)?)),
+        _ => Ok(None),
+    }
+}
+
+/// Upload the statistics returned by [`statistics_for_insert`] once the rows are inserted.
+/// The rows are already in the feature group, so a failing upload is logged rather than returned.
+pub async fn save_inserted_statistics(
+    fg: &FeatureGroup,
+    statistics: &Statistics,
+) -> Option<Statistics> {
+    match fg.save_statistics(statistics).await {
+        Ok(statistics) => Some(statistics),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to upload the statistics of Feature Group {}: {}",
+                fg.name(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Most recent statistics of `fg` as a [`DataFrame`] with one row per feature, see [`statistics_to_polars_df`].
+///
+/// # Example
+/// ```no_run
+/// # use color_eyre::Result;
+/// # async fn run() -> Result<()> {
+/// let feature_group = hopsworks_api::login(None, hopsworks_api::RuntimeConfig::Current).await?
+///   .get_feature_store().await?
+///   .get_feature_group("demo_feature_group", Some(1)).await?
+///   .expect("Feature group not found");
+///
+/// if let Some(statistics_df) = hopsworks_api::polars::statistics::get_statistics_df(&feature_group).await? {
+///     println!("{}", statistics_df);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_statistics_df(fg: &FeatureGroup) -> Result<Option<DataFrame>> {
+    fg.get_statistics()
+        .await?
+        .as_ref()
+        .map(statistics_to_polars_df)
+        .transpose()
+}
+
+/// Flatten the statistics into a [`DataFrame`] with one row per feature and one column per metric,
+/// histograms and correlations are left out.
+pub fn statistics_to_polars_df(statistics: &Statistics) -> Result<DataFrame> {
+    let features = statistics.feature_descriptive_statistics();
+    let f64_column = |name: &str, metric: fn(&FeatureDescriptiveStatistics) -> Option<f64>| {
+        Series::new(name, features.iter().map(metric).collect::<Vec<_>>())
+    };
+    let i64_column = |name: &str, metric: fn(&FeatureDescriptiveStatistics) -> Option<i64>| {
+        Series::new(name, features.iter().map(metric).collect::<Vec<_>>())
+    };
+    let mut percentiles: ListChunked = features
+        .iter()
+        .map(|statistics| {
+            statistics
+                .percentiles
+                .as_ref()
+                .map(|percentiles| Series::new("", percentiles.as_slice()))
+        })
+        .collect();
+    percentiles.rename("percentiles");
+
+    DataFrame::new(vec![
+        Series::new(
+            "feature_name",
+            features
+                .iter()
+                .map(|statistics| statistics.feature_name.as_str())
+                .collect::<Vec<_>>(),
+        ),
+        Series::new(
+            "feature_type",
+            features
+                .iter()
+                .map(|statistics| statistics.feature_type.as_deref())
+                .collect::<Vec<_>>(),
+        ),
+        i64_column("count", |s| s.count),
+        f64_column("completeness", |s| s.completeness),
+        i64_column("num_non_null_values", |s| s.num_non_null_values),
+        i64_column("num_null_values", |s| s.num_null_values),
+        i64_column("approx_num_distinct_values", |s| {
+            s.approx_num_distinct_values
+        }),
+        f64_column("min", |s| s.min),
+        f64_column("max", |s| s.max),
+        f64_column("sum", |s| s.sum),
+        f64_column("mean", |s| s.mean),
+        f64_column("stddev", |s| s.stddev),
+        percentiles.into_series(),
+        f64_column("distinctness", |s| s.distinctness),
+        f64_column("entropy", |s| s.entropy),
+        f64_column("uniqueness", |s| s.uniqueness),
+        i64_column("exact_num_distinct_values", |s| s.exact_num_distinct_values),
+    ])
+    .map_err(HopsworksError::other)
+}
+
+/// Statistics of a single column, with its values if it is numerical to compute correlations.
+fn compute_feature_statistics(
+    dataframe: &DataFrame,
+    series: &Series,
+    config: &StatisticsConfig,
+) -> Result<(FeatureDescriptiveStatistics, Option<Vec<Option<f64>>>)> {
+    let dtype = series.dtype();
+    let count = series.len();
+    let num_null_values = series.null_count();
+    let num_non_null_values = count - num_null_values;
+
+    let mut statistics = FeatureDescriptiveStatistics::new(series.name());
+    statistics.feature_type = Some(
+        if dtype.is_integer() {
+            "Integral"
+        } else if dtype.is_float() {
+            "Fractional"
+        } else if dtype == &DataType::Boolean {
+            "Boolean"
+        } else {
+            "String"
+        }
+        .to_string(),
+    );
+    statistics.count = Some(count as i64);
+    statistics.num_null_values = Some(num_null_values as i64);
+    statistics.num_non_null_values = Some(num_non_null_values as i64);
+    statistics.completeness = Some(ratio(num_non_null_values, count));
+
+    // Number of occurrences of each non-null value, most frequent first
+    let value_counts = dataframe
+        .select([series.name()])
+        .map_err(HopsworksError::other)?
+        .lazy()
+        .filter(col(series.name()).is_not_null())
+        .group_by([col(series.name()).cast(DataType::String).alias("value")])
+        .agg([len().cast(DataType::UInt64).alias("count")])
+        .sort(
+            ["count"],
+            SortMultipleOptions::default().with_order_descending(true),
+        )
+        .collect()
+        .map_err(HopsworksError::other)?;
+    let counts: Vec<u64> = value_counts
+        .column("count")
+        .and_then(|counts| counts.u64().cloned())
+        .map_err(HopsworksError::other)?
+        .into_no_null_iter()
+        .collect();
+    statistics.approx_num_distinct_values = Some(counts.len() as i64);
+
+    if config.exact_uniqueness {
+        statistics.exact_num_distinct_values = Some(counts.len() as i64);
+        statistics.distinctness = Some(ratio(counts.len(), num_non_null_values));
+        statistics.uniqueness = Some(ratio(
+            counts.iter().filter(|count| **count == 1).count(),
+            num_non_null_values,
+        ));
+        statistics.entropy = Some(
+            counts
+                .iter()
+                .map(|count| {
+                    let p = *count as f64 / num_non_null_values as f64;
+                    -p * p.ln()
+                })
+                .sum(),
+        );
+    }
+
+    if !dtype.is_numeric() {
+        if config.histograms {
+            let values = value_counts
+                .column("value")
+                .and_then(|values| values.str().cloned())
+                .map_err(HopsworksError::other)?;
+            statistics.histogram = Some(
+                values
+                    .into_iter()
+                    .zip(counts.iter())
+                    .take(HISTOGRAM_BINS)
+                    .map(|(value, count)| HistogramBin {
+                        value: value.unwrap_or_default().to_string(),
+                        count: *count,
+                        ratio: ratio(*count as usize, num_non_null_values),
+                    })
+                    .collect(),
+            );
+        }
+        return Ok((statistics, None));
+    }
+
+    let row_values: Vec<Option<f64>> = series
+        .cast(&DataType::Float64)
+        .map_err(HopsworksError::other)?
+        .f64()
+        .map_err(HopsworksError::other)?
+        .into_iter()
+        .collect();
+    let mut values: Vec<f64> = row_values.iter().flatten().copied().collect();
+    values.sort_by(f64::total_cmp);
+    if !values.is_empty() {
+        let sum: f64 = values.iter().sum();
+        let mean = sum / values.len() as f64;
+        statistics.min = values.first().copied();
+        statistics.max = values.last().copied();
+        statistics.sum = Some(sum);
+        statistics.mean = Some(mean);
+        statistics.stddev = (values.len() > 1).then(|| {
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64)
+                .sqrt()
+        });
+        statistics.percentiles = Some(
+            (1..=100)
+                .map(|percentile| {
+                    let rank = (percentile * values.len()).div_ceil(100);
+                    values[rank.max(1) - 1]
+                })
+                .collect(),
+        );
+        if config.histograms {
+            statistics.histogram = Some(numerical_histogram(&values));
+        }
+    }
+    Ok((statistics, Some(row_values)))
+}
+
+fn has_statistics(dtype: &DataType) -> bool {
+    !matches!(
+        dtype,
+        DataType::List(_)
+            | DataType::Array(_, _)
+            | DataType::Struct(_)
+            | DataType::Binary
+            | DataType::BinaryOffset
+            | DataType::Null
+    )
+}
+
+/// Equal width histogram of sorted values, the value of a bin is its lower bound.
+fn numerical_histogram(values: &[f64]) -> Vec<HistogramBin> {
+    let (min, max) = (values[0], values[values.len() - 1]);
+    let bins = if min == max { 1 } else { HISTOGRAM_BINS };
+    let width = (max - min) / bins as f64;
+    let mut counts = vec![0u64; bins];
+    for value in values {
+        let bin = if width == 0.0 {
+            0
+        } else {
+            (((value - min) / width) as usize).min(bins - 1)
+        };
+        counts[bin] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(bin, count)| HistogramBin {
+            value: (min + bin as f64 * width).to_string(),
+            count,
+            ratio: ratio(count as usize, values.len()),
+        })
+        .collect()
+}
+
+/// Pearson correlation of the rows where both values are set, `None` if one of the columns is constant.
+fn pearson_correlation(x: &[Option<f64>], y: &[Option<f64>]) -> Option<f64> {
+    let pairs: Vec<(f64, f64)> = x
+        .iter()
+        .zip(y)
+        .filter_map(|(a, b)| Some(((*a)?, (*b)?)))
+        .collect();
+    if pairs.len() < 2 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(a, _)| a).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, b)| b).sum::<f64>() / n;
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+    for (a, b) in &pairs {
+        covariance += (a - mean_x) * (b - mean_y);
+        variance_x += (a - mean_x).powi(2);
+        variance_y += (b - mean_y).powi(2);
+    }
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+    Some(covariance / (variance_x * variance_y).sqrt())
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_statistics() {
+        let df = df!(
+            "id" => [1i64, 2, 3, 4],
+            "value" => [Some(2.0f64), Some(4.0), Some(6.0), None],
+            "country" => ["SE", "SE", "DE", "US"],
+        )
+        .unwrap();
+        let config = StatisticsConfig {
+            enabled: true,
+            histograms: true,
+            correlations: true,
+            exact_uniqueness: true,
+            columns: vec![],
+        };
+
+        let statistics = compute_statistics(&df, &config).unwrap();
+
+        let value = statistics.feature_statistics("value").unwrap();
+        assert_eq!(value.feature_type.as_deref(), Some("Fractional"));
+        assert_eq!(value.num_null_values, Some(1));
+        assert_eq!(value.completeness, Some(0.75));
+        assert_eq!(
+            (value.min, value.max, value.mean),
+            (Some(2.0), Some(6.0), Some(4.0))
+        );
+        assert_eq!(value.stddev, Some(2.0));
+        assert_eq!(value.percentiles.as_ref().unwrap()[49], 4.0);
+        let correlations = value.correlations.as_ref().unwrap();
+        assert_eq!(correlations[0].column, "id");
+        assert!((correlations[0].correlation - 1.0).abs() < 1e-9);
+
+        let country = statistics.feature_statistics("country").unwrap();
+        assert_eq!(country.feature_type.as_deref(), Some("String"));
+        assert_eq!(country.exact_num_distinct_values, Some(3));
+        assert_eq!(country.uniqueness, Some(0.5));
+        assert_eq!(country.histogram.as_ref().unwrap()[0].value, "SE");
+        assert_eq!(country.correlations, None);
+
+        let statistics_df = statistics_to_polars_df(&statistics).unwrap();
+        assert_eq!(statistics_df.shape(), (3, 17));
+    }
+
+    #[test]
+    fn test_compute_statistics_missing_column() {
+        let df = df!("id" => [1i64]).unwrap();
+        let config = StatisticsConfig {
+            enabled: true,
+            histograms: false,
+            correlations: false,
+            exact_uniqueness: false,
+            columns: vec!["missing".to_string()],
+        };
+
+        assert!(matches!(
+            compute_statistics(&df, &config),
+            Err(HopsworksError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_compute_statistics_skips_complex_columns() {
+        let df = DataFrame::new(vec![
+            Series::new("id", [1i64, 2]),
+            Series::new(
+                "values",
+                [Series::new("", [1i64, 2]), Series::new("", [3i64])],
+            ),
+        ])
+        .unwrap();
+        let config = StatisticsConfig {
+            enabled: true,
+            histograms: true,
+            correlations: true,
+            exact_uniqueness: true,
+            columns: vec![],
+        };
+
+        let statistics = compute_statistics(&df, &config).unwrap();
+
+        assert!(statistics.feature_statistics("id").is_some());
+        assert!(statistics.feature_statistics("values").is_none());
+    }
+}
@@ -0,0 +1,348 @@
+//! Validate a polars [`DataFrame`] against the schema of a [`FeatureGroup`] before inserting it.
+//!
+//! Inserted rows are only deserialized by the materialization job, so a missing column, a wrong type
+//! or a null primary key would otherwise fail long after the insert returned.
+use std::fmt;
+
+use hopsworks_core::feature_store::{feature_group::feature::Feature, FeatureGroup};
+use hopsworks_core::{HopsworksError, Result};
+use polars::prelude::{DataFrame, DataType};
+
+use super::{hopsworks_data_type, is_complex_type, polars_data_type};
+
+/// Column whose polars type does not match the type of the feature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatch {
+    pub column: String,
+    /// Hopsworks type of the feature, e.g. `bigint`.
+    pub expected: String,
+    pub actual: DataType,
+    /// Whether [`cast_polars_df_to_feature_group_schema`] can cast the column to the feature type.
+    pub castable: bool,
+}
+
+/// Result of [`validate_polars_df`], empty if the dataframe can be inserted in the feature group.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataFrameValidationReport {
+    /// Features of the feature group without a column in the dataframe.
+    pub missing_columns: Vec<String>,
+    /// Columns of the dataframe which are not features of the feature group.
+    pub unknown_columns: Vec<String>,
+    pub type_mismatches: Vec<TypeMismatch>,
+    /// Primary key columns with null values and their number of nulls.
+    pub null_primary_keys: Vec<(String, usize)>,
+    /// Number of rows sharing their primary key with another row, only one of them is kept
+    /// once inserted. Reported as a warning unless checked with [`Self::ensure_valid_strict`].
+    pub duplicate_primary_key_rows: usize,
+    /// Set if the event time column is neither a timestamp, a date nor an integer.
+    pub event_time_error: Option<String>,
+}
+
+impl DataFrameValidationReport {
+    /// Whether the dataframe can be inserted in the feature group, duplicate primary keys included.
+    pub fn is_valid(&self) -> bool {
+        let without_duplicates = DataFrameValidationReport {
+            duplicate_primary_key_rows: 0,
+            ..self.clone()
+        };
+        without_duplicates == DataFrameValidationReport::default()
+    }
+
+    /// Turn the report into a [`HopsworksError::Validation`] if the dataframe is not valid,
+    /// logging a warning for rows with a duplicate primary key.
+    pub fn ensure_valid(&self) -> Result<()> {
+        if !self.is_valid() {
+            return Err(HopsworksError::Validation(self.to_string()));
+        }
+        if self.duplicate_primary_key_rows > 0 {
+            tracing::warn!("{}", self);
+        }
+        Ok(())
+    }
+
+    /// Same as [`Self::ensure_valid`], also failing for rows with a duplicate primary key.
+    pub fn ensure_valid_strict(&self) -> Result<()> {
+        if self.is_valid() && self.duplicate_primary_key_rows == 0 {
+            Ok(())
+        } else {
+            Err(HopsworksError::Validation(self.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for DataFrameValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            write!(f, "DataFrame matches the Feature Group schema")?;
+            if self.duplicate_primary_key_rows > 0 {
+                write!(
+                    f,
+                    " but {} rows have a duplicate primary key",
+                    self.duplicate_primary_key_rows
+                )?;
+            }
+            return Ok(());
+        }
+        let mut issues = vec![];
+        if !self.missing_columns.is_empty() {
+            issues.push(format!("missing columns {:?}", self.missing_columns));
+        }
+        if !self.unknown_columns.is_empty() {
+            issues.push(format!("unknown columns {:?}", self.unknown_columns));
+        }
+        for mismatch in &self.type_mismatches {
+            issues.push(format!(
+                "column {} has type {} instead of {}{}",
+                mismatch.column,
+                mismatch.actual,
+                mismatch.expected,
+                if mismatch.castable { " (castable)" } else { "" }
+            ));
+        }
+        for (column, null_count) in &self.null_primary_keys {
+            issues.push(format!("primary key {column} has {null_count} null values"));
+        }
+        if self.duplicate_primary_key_rows > 0 {
+            issues.push(format!(
+                "{} rows have a duplicate primary key",
+                self.duplicate_primary_key_rows
+            ));
+        }
+        if let Some(event_time_error) = &self.event_time_error {
+            issues.push(event_time_error.clone());
+        }
+        write!(
+            f,
+            "DataFrame does not match the Feature Group schema: {}",
+            issues.join(", ")
+        )
+    }
+}
+
+/// Check the columns, types and primary keys of `dataframe` against the schema of `fg`.
+///
+/// # Example
+/// ```no_run
+/// # use color_eyre::Result;
+/// use polars::prelude::*;
+///
+/// # async fn run() -> Result<()> {
+/// let feature_group = hopsworks_api::login(None, hopsworks_api::RuntimeConfig::Current).await?
+///   .get_feature_store().await?
+///   .get_feature_group("demo_feature_group", Some(1)).await?
+///   .expect("Feature group not found");
+///
+/// let df = df!("id" => [1i64, 2i64], "value" => [0.5f64, 0.7f64])?;
+/// let report = hopsworks_api::polars::validation::validate_polars_df(&df, &feature_group)?;
+/// report.ensure_valid()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn validate_polars_df(
+    dataframe: &DataFrame,
+    fg: &FeatureGroup,
+) -> Result<DataFrameValidationReport> {
+    validate_against_features(
+        dataframe,
+        fg.features(),
+        fg.primary_keys().as_slice(),
+        fg.event_time(),
+    )
+}
+
+/// Cast the columns of `dataframe` whose type is compatible with the type of the feature, e.g. `Int32` to `bigint`,
+/// then validate it. Returns the report of the remaining issues, an error if a value does not fit the feature type.
+pub fn cast_polars_df_to_feature_group_schema(
+    dataframe: &mut DataFrame,
+    fg: &FeatureGroup,
+) -> Result<DataFrameValidationReport> {
+    let report = validate_polars_df(dataframe, fg)?;
+    if report
+        .type_mismatches
+        .iter()
+        .all(|mismatch| !mismatch.castable)
+    {
+        return Ok(report);
+    }
+    for mismatch in report.type_mismatches.iter().filter(|m| m.castable) {
+        let target =
+            polars_data_type(&mismatch.expected).expect("castable types have a polars counterpart");
+        let casted = dataframe
+            .column(&mismatch.column)
+            .and_then(|series| series.strict_cast(&target))
+            .map_err(|e| {
+                HopsworksError::Validation(format!(
+                    "Cannot cast column {} to {}: {}",
+                    mismatch.column, mismatch.expected, e
+                ))
+            })?;
+        dataframe
+            .with_column(casted)
+            .map_err(HopsworksError::other)?;
+    }
+    validate_polars_df(dataframe, fg)
+}
+
+pub(crate) fn validate_against_features(
+    dataframe: &DataFrame,
+    features: &[Feature],
+    primary_keys: &[&str],
+    event_time: Option<&str>,
+) -> Result<DataFrameValidationReport> {
+    let mut report = DataFrameValidationReport::default();
+    let schema = dataframe.schema();
+
+    for feature in features {
+        match schema.get(feature.name()) {
+            None => report.missing_columns.push(feature.name().to_string()),
+            Some(dtype) if !is_compatible(feature.data_type(), dtype) => {
+                report.type_mismatches.push(TypeMismatch {
+                    column: feature.name().to_string(),
+                    expected: feature.data_type().to_string(),
+                    actual: dtype.clone(),
+                    castable: is_castable(feature.data_type(), dtype),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    report.unknown_columns = schema
+        .iter_names()
+        .filter(|name| !features.iter().any(|f| f.name() == name.as_str()))
+        .map(|name| name.to_string())
+        .collect();
+
+    let present_primary_keys: Vec<&str> = primary_keys
+        .iter()
+        .copied()
+        .filter(|pk| schema.contains(pk))
+        .collect();
+    for pk in &present_primary_keys {
+        let null_count = dataframe
+            .column(pk)
+            .map_err(HopsworksError::other)?
+            .null_count();
+        if null_count > 0 {
+            report.null_primary_keys.push((pk.to_string(), null_count));
+        }
+    }
+    if !present_primary_keys.is_empty() && present_primary_keys.len() == primary_keys.len() {
+        report.duplicate_primary_key_rows = dataframe
+            .select(present_primary_keys)
+            .and_then(|pk_df| pk_df.is_duplicated())
+            .map_err(HopsworksError::other)?
+            .sum()
+            .unwrap_or(0) as usize;
+    }
+
+    if let Some(dtype) = event_time.and_then(|event_time| schema.get(event_time)) {
+        if !(dtype.is_integer() || matches!(dtype, DataType::Datetime(_, _) | DataType::Date)) {
+            report.event_time_error = Some(format!(
+                "event time {} has type {}, expected a timestamp, a date or an integer",
+                event_time.unwrap_or_default(),
+                dtype
+            ));
+        }
+    }
+
+    Ok(report)
+}
+
+// Types without Avro counterpart, e.g. `UInt8` or `Time`, are cast to the feature type when inserted
+fn is_compatible(expected: &str, dtype: &DataType) -> bool {
+    let expected = expected.to_lowercase().replace(' ', "");
+    // Complex features can also be inserted already encoded
+    if is_complex_type(&expected) && *dtype == DataType::Binary {
+        return true;
+    }
+    match hopsworks_data_type(dtype) {
+        Some(actual) => {
+            actual.to_lowercase() == expected
+                || (actual == "int" && matches!(expected.as_str(), "tinyint" | "smallint"))
+        }
+        None => false,
+    }
+}
+
+fn is_castable(expected: &str, dtype: &DataType) -> bool {
+    match polars_data_type(expected) {
+        Some(target) => {
+            (dtype.is_numeric() && target.is_numeric())
+                || (dtype.is_temporal() && target.is_temporal())
+                || (matches!(dtype, DataType::Categorical(_, _)) && target == DataType::String)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn features() -> Vec<Feature> {
+        vec![
+            Feature::new("id", "bigint"),
+            Feature::new("value", "double"),
+            Feature::new("ts", "timestamp"),
+        ]
+    }
+
+    #[test]
+    fn test_validate_against_features() {
+        let df = df!(
+            "id" => [Some(1i32), Some(1i32), None],
+            "value" => ["a", "b", "c"],
+            "extra" => [true, false, true],
+        )
+        .unwrap();
+
+        let report = validate_against_features(&df, &features(), &["id"], Some("value")).unwrap();
+
+        assert_eq!(report.missing_columns, vec!["ts"]);
+        assert_eq!(report.unknown_columns, vec!["extra"]);
+        assert_eq!(report.type_mismatches.len(), 2);
+        assert!(report.type_mismatches[0].castable);
+        assert!(!report.type_mismatches[1].castable);
+        assert_eq!(report.null_primary_keys, vec![("id".to_string(), 1)]);
+        assert_eq!(report.duplicate_primary_key_rows, 2);
+        assert!(report.event_time_error.is_some());
+        assert!(matches!(
+            report.ensure_valid(),
+            Err(HopsworksError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_primary_keys_are_a_warning() {
+        let df = df!("id" => [1i64, 1i64], "value" => [0.5f64, 0.7f64]).unwrap();
+
+        let report = validate_against_features(&df, &features()[..2], &["id"], None).unwrap();
+
+        assert_eq!(report.duplicate_primary_key_rows, 2);
+        assert!(report.is_valid());
+        assert!(report.ensure_valid().is_ok());
+        assert!(matches!(
+            report.ensure_valid_strict(),
+            Err(HopsworksError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_against_features_valid() {
+        let ts = Series::new("ts", [1i64, 2i64])
+            .cast(&DataType::Datetime(TimeUnit::Microseconds, None))
+            .unwrap();
+        let df = df!(
+            "id" => [1i64, 2i64],
+            "value" => [0.5f64, 0.7f64],
+        )
+        .unwrap()
+        .hstack(&[ts])
+        .unwrap();
+
+        let report = validate_against_features(&df, &features(), &["id"], Some("ts")).unwrap();
+
+        assert!(report.is_valid(), "{report}");
+    }
+}
@@ -21,11 +21,44 @@ pub fn mock_get_feature_group_info(name: String) {
     println!("Getting metadata information about Feature Group: {}", name);
 }
 
-pub fn mock_list_feature_groups(latest_only: bool) {
+pub async fn show_list_feature_groups(
+    project: hopsworks_core::platform::project::Project,
+    latest_only: bool,
+) {
     if latest_only {
-        println!("Listing latest version of each Feature Group in the current project");
+        println!(
+            "Fetching latest version of each Feature Group within project {}:",
+            project.name()
+        );
     } else {
-        println!("Listing all Feature Groups in the current project");
+        println!(
+            "Fetching all Feature Groups within project {}:",
+            project.name()
+        );
     }
+    let feature_store = project.get_feature_store().await.unwrap_or_else(|_| {
+        panic!(
+            "Failed to fetch the Feature Store of project {}.\n",
+            project.name()
+        )
+    });
+    let feature_groups = feature_store
+        .get_feature_groups(latest_only)
+        .await
+        .unwrap_or_else(|_| {
+            panic!(
+                "Failed to fetch Feature Groups for project {}.\n",
+                project.name()
+            )
+        });
+    feature_groups.iter().for_each(|feature_group| {
+        println!(
+            "id: {}, name: {}, version: {}, online enabled: {}, created at: {}",
+            feature_group.id().unwrap_or_default(),
+            feature_group.name(),
+            feature_group.version(),
+            feature_group.is_online_enabled(),
+            feature_group.created()
+        );
+    });
 }
-
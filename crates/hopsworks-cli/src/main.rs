@@ -1,16 +1,15 @@
 use clap::Parser;
 use color_eyre::Result;
 
-use hopsworks_core::HopsworksClientBuilder;
-use hopsworks_core::profiles::read::get_hopsworks_profile;
+use hopsworks_core::{HopsworksClientBuilder, RuntimeConfig};
 
-mod platform;
 mod feature_store;
+mod platform;
 mod subcommands;
 
-use subcommands::HopsworksCliSubCommands;
-use platform::project::{self, ProjectSubCommand};
 use platform::job::{self, JobSubCommand};
+use platform::project::{self, ProjectSubCommand};
+use subcommands::HopsworksCliSubCommands;
 
 /// A CLI to interact with the Hopsworks Platform, Feature Store without leaving the terminal.
 /// Requires a valid API key to be set in the environment variable `HOPSWORKS_API_KEY`.
@@ -33,27 +32,21 @@ struct HopsworksCli {
     project: Option<String>,
 }
 
-
 #[tokio::main]
 async fn main() -> Result<()> {
     env_logger::init();
 
     let args = HopsworksCli::parse();
 
-    let profile = get_hopsworks_profile(args.profile.as_deref())?;
-
-    let mut hopsworks_client_builder = HopsworksClientBuilder::new_from_env_or_provided(
-        &profile.user.api_key,
-        &profile.cluster.get_api_url(),
-        Some(profile.project.name).as_deref(),
-    );
+    let mut hopsworks_client_builder =
+        HopsworksClientBuilder::from_profile(args.profile.as_deref())?;
     if args.project.is_some() {
         hopsworks_client_builder =
             hopsworks_client_builder.with_project_name(args.project.unwrap().as_str());
     }
 
-    let multithreaded = false;
-    let current_project = hopsworks_core::login(Some(hopsworks_client_builder), multithreaded).await?;
+    let current_project =
+        hopsworks_core::login(Some(hopsworks_client_builder), RuntimeConfig::Current).await?;
 
     match args.command {
         HopsworksCliSubCommands::Project { command } => match command {
@@ -87,7 +80,8 @@ async fn main() -> Result<()> {
                 feature_store::feature_group::mock_get_feature_group_info(name)
             }
             feature_store::FeatureGroupSubCommand::List { latest_only } => {
-                feature_store::feature_group::mock_list_feature_groups(latest_only)
+                feature_store::feature_group::show_list_feature_groups(current_project, latest_only)
+                    .await
             }
         },
         HopsworksCliSubCommands::FeatureView { command } => match command {
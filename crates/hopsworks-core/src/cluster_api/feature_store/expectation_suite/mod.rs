@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+pub mod service;
+
+// Hopsworks stores the Great Expectations kwargs, meta and results as JSON encoded strings.
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectationDTO {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    pub expectation_type: String,
+    pub kwargs: String,
+    #[serde(default)]
+    pub meta: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectationSuiteDTO {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    pub expectation_suite_name: String,
+    pub expectations: Vec<ExpectationDTO>,
+    #[serde(default)]
+    pub meta: String,
+    pub run_validation: bool,
+    pub validation_ingestion_policy: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationResultDTO {
+    pub success: bool,
+    pub expectation_config: String,
+    #[serde(default)]
+    pub result: String,
+    #[serde(default)]
+    pub exception_info: String,
+    #[serde(default)]
+    pub meta: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReportDTO {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    pub success: bool,
+    pub results: Vec<ValidationResultDTO>,
+    #[serde(default)]
+    pub statistics: String,
+    #[serde(default)]
+    pub meta: String,
+    #[serde(default)]
+    pub evaluation_parameters: String,
+    pub ingestion_result: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation_time: Option<String>,
+}
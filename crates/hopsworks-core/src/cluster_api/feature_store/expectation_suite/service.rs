@@ -0,0 +1,117 @@
+use crate::error::{HopsworksError, Result};
+use reqwest::{Method, StatusCode};
+
+use super::{ExpectationSuiteDTO, ValidationReportDTO};
+use crate::get_hopsworks_client;
+
+/// Expectation suite attached to the feature group, `None` if there is none.
+pub async fn get_expectation_suite(
+    feature_store_id: i32,
+    feature_group_id: i32,
+) -> Result<Option<ExpectationSuiteDTO>> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::GET,
+            format!(
+                "featurestores/{feature_store_id}/featuregroups/{feature_group_id}/expectationsuite"
+            )
+            .as_str(),
+            true,
+            true,
+        )
+        .await?
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND | StatusCode::NO_CONTENT => Ok(None),
+        // An empty body is returned if no suite is attached
+        StatusCode::OK => match response.text().await?.as_str() {
+            "" | "{}" => Ok(None),
+            body => Ok(Some(serde_json::from_str(body)?)),
+        },
+        _ => Err(HopsworksError::from_response("get_expectation_suite", response).await),
+    }
+}
+
+/// Attach the suite to the feature group, replacing the suite already attached if any.
+pub async fn save_expectation_suite(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    expectation_suite_dto: &ExpectationSuiteDTO,
+) -> Result<ExpectationSuiteDTO> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::PUT,
+            format!(
+                "featurestores/{feature_store_id}/featuregroups/{feature_group_id}/expectationsuite"
+            )
+            .as_str(),
+            true,
+            true,
+        )
+        .await?
+        .json(expectation_suite_dto)
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::OK | StatusCode::CREATED => Ok(response.json::<ExpectationSuiteDTO>().await?),
+        _ => Err(HopsworksError::from_response("save_expectation_suite", response).await),
+    }
+}
+
+pub async fn delete_expectation_suite(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    expectation_suite_id: i32,
+) -> Result<()> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::DELETE,
+            format!(
+                "featurestores/{feature_store_id}/featuregroups/{feature_group_id}/expectationsuite/{expectation_suite_id}"
+            )
+            .as_str(),
+            true,
+            true,
+        )
+        .await?
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+        _ => Err(HopsworksError::from_response("delete_expectation_suite", response).await),
+    }
+}
+
+pub async fn save_validation_report(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    validation_report_dto: &ValidationReportDTO,
+) -> Result<ValidationReportDTO> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::PUT,
+            format!(
+                "featurestores/{feature_store_id}/featuregroups/{feature_group_id}/validationreport"
+            )
+            .as_str(),
+            true,
+            true,
+        )
+        .await?
+        .json(validation_report_dto)
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::OK | StatusCode::CREATED => Ok(response.json::<ValidationReportDTO>().await?),
+        _ => Err(HopsworksError::from_response("save_validation_report", response).await),
+    }
+}
@@ -7,8 +7,8 @@ pub struct NewFeaturePayload {
     #[serde(rename = "type")]
     data_type: String,
     pub primary: bool,
-    hudi_precombine_key: bool,
-    partition: bool,
+    pub hudi_precombine_key: bool,
+    pub partition: bool,
 }
 
 impl NewFeaturePayload {
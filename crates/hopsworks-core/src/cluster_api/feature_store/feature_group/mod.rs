@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
 
 use crate::cluster_api::feature_store::{
-    embedding::EmbeddingIndexDTO, feature::FeatureDTO, statistics_config::StatisticsConfigDTO,
+    embedding::EmbeddingIndexDTO,
+    feature::FeatureDTO,
+    statistics_config::StatisticsConfigDTO,
+    storage_connector::{FeatureStoreStorageConnectorDTO, StorageConnectorDTO},
 };
 use crate::cluster_api::platform::users::UserDTO;
-use crate::feature_store::feature_group::FeatureGroup;
+use crate::feature_store::feature_group::{FeatureGroup, EXTERNAL_FEATURE_GROUP_TYPE};
 
 pub mod payloads;
 pub mod service;
@@ -26,10 +29,21 @@ pub struct FeatureGroupDTO {
     pub statistics_config: Option<StatisticsConfigDTO>,
     pub features: Vec<FeatureDTO>,
     pub online_enabled: bool,
+    // Not set for external feature groups, which can be listed with the others
+    #[serde(default)]
     pub time_travel_format: String,
     pub online_topic_name: Option<String>,
     pub event_time: Option<String>,
     pub embedding_index: Option<EmbeddingIndexDTO>,
+    // Source of the data of external feature groups
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_connector: Option<StorageConnectorDTO>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_format: Option<String>,
 }
 
 impl From<&FeatureGroup> for FeatureGroupDTO {
@@ -58,6 +72,7 @@ impl From<&FeatureGroup> for FeatureGroupDTO {
             feature_group_type: match feature_group.feature_group_type() {
                 "STREAM_FEATURE_GROUP" => "streamFeatureGroupDTO".to_owned(),
                 "streamFeatureGroupDTO" => "streamFeatureGroupDTO".to_owned(),
+                EXTERNAL_FEATURE_GROUP_TYPE => EXTERNAL_FEATURE_GROUP_TYPE.to_owned(),
                 _ => "streamFeatureGroupDTO".to_owned(),
             },
             featurestore_id: feature_group.feature_store_id(),
@@ -74,6 +89,14 @@ impl From<&FeatureGroup> for FeatureGroupDTO {
             online_enabled: feature_group.is_online_enabled(),
             time_travel_format: feature_group.time_travel_format().to_string(),
             embedding_index: feature_group.embedding_index().map(EmbeddingIndexDTO::from),
+            storage_connector: feature_group.storage_connector().map(|storage_connector| {
+                StorageConnectorDTO::OtherConnectorDTO(FeatureStoreStorageConnectorDTO::from(
+                    storage_connector,
+                ))
+            }),
+            query: feature_group.query().map(String::from),
+            path: feature_group.path().map(String::from),
+            data_format: feature_group.data_format().map(String::from),
         }
     }
 }
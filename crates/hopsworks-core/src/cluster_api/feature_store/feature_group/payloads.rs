@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::cluster_api::feature_store::feature::payloads::NewFeaturePayload;
+use crate::cluster_api::feature_store::{
+    feature::payloads::NewFeaturePayload, storage_connector::FeatureStoreStorageConnectorDTO,
+};
+use crate::feature_store::feature_group::EXTERNAL_FEATURE_GROUP_TYPE;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -13,6 +16,16 @@ pub struct NewFeatureGroupPayload {
     features: Vec<NewFeaturePayload>,
     event_time: Option<String>,
     online_enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_travel_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage_connector: Option<FeatureStoreStorageConnectorDTO>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data_format: Option<String>,
 }
 
 impl NewFeatureGroupPayload {
@@ -32,6 +45,33 @@ impl NewFeatureGroupPayload {
             features,
             event_time: event_time.map(String::from),
             online_enabled,
+            time_travel_format: None,
+            storage_connector: None,
+            query: None,
+            path: None,
+            data_format: None,
         }
     }
+
+    pub fn with_time_travel_format(mut self, time_travel_format: &str) -> Self {
+        self.time_travel_format = Some(String::from(time_travel_format));
+        self
+    }
+
+    /// Create an external feature group reading `query` or the files at `path` from `storage_connector`.
+    pub fn with_external_source(
+        mut self,
+        storage_connector: FeatureStoreStorageConnectorDTO,
+        query: Option<&str>,
+        path: Option<&str>,
+        data_format: Option<&str>,
+    ) -> Self {
+        self.fg_type = String::from(EXTERNAL_FEATURE_GROUP_TYPE);
+        self.online_enabled = false;
+        self.storage_connector = Some(storage_connector);
+        self.query = query.map(String::from);
+        self.path = path.map(String::from);
+        self.data_format = data_format.map(String::from);
+        self
+    }
 }
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::{Method, StatusCode};
 
 use super::{payloads::NewFeatureGroupPayload, FeatureGroupDTO};
@@ -12,7 +12,7 @@ pub async fn get_feature_group_by_name_and_version(
     let query_params = [("version", version.to_string())];
 
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{feature_store_id}/featuregroups/{name}").as_str(),
@@ -27,11 +27,9 @@ pub async fn get_feature_group_by_name_and_version(
     match response.status() {
         StatusCode::NOT_FOUND => Ok(None),
         StatusCode::OK => Ok(response.json::<Vec<FeatureGroupDTO>>().await?.pop()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_feature_group_by_name_and_version failed with status : {:?}, here is the response :\n{:?}",
-            response.status(),
-            response.text_with_charset("utf-8").await?
-        )),
+        _ => Err(
+            HopsworksError::from_response("get_feature_group_by_name_and_version", response).await,
+        ),
     }
 }
 
@@ -40,7 +38,7 @@ pub async fn get_latest_feature_group_by_name(
     name: &str,
 ) -> Result<Option<FeatureGroupDTO>> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{feature_store_id}/featuregroups/{name}").as_str(),
@@ -54,20 +52,17 @@ pub async fn get_latest_feature_group_by_name(
     match response.status() {
         StatusCode::NOT_FOUND => Ok(None),
         StatusCode::OK => Ok(response.json::<Vec<FeatureGroupDTO>>().await?.pop()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_latest_feature_group_by_name failed with status : {:?}, here is the response :\n{:?}",
-            response.status(),
-            response.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("get_latest_feature_group_by_name", response).await),
     }
 }
 
+/// Feature groups are fetched by id as a single object, unlike by name.
 pub async fn get_feature_group_by_id(
     feature_store_id: i32,
     feature_group_id: i32,
 ) -> Result<Option<FeatureGroupDTO>> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{feature_store_id}/featuregroups/{feature_group_id}").as_str(),
@@ -80,12 +75,54 @@ pub async fn get_feature_group_by_id(
 
     match resp.status() {
         StatusCode::NOT_FOUND => Ok(None),
-        StatusCode::OK => Ok(resp.json::<Vec<FeatureGroupDTO>>().await?.pop()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_feature_group_by_id failed with status : {:?}, here is the response :\n{:?}",
-            resp.status(),
-            resp.text_with_charset("utf-8").await?
-        )),
+        StatusCode::OK => Ok(Some(resp.json::<FeatureGroupDTO>().await?)),
+        _ => Err(HopsworksError::from_response("get_feature_group_by_id", resp).await),
+    }
+}
+
+/// Every version of the feature group `name`, empty if it does not exist.
+pub async fn get_feature_group_versions_by_name(
+    feature_store_id: i32,
+    name: &str,
+) -> Result<Vec<FeatureGroupDTO>> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::GET,
+            format!("featurestores/{feature_store_id}/featuregroups/{name}").as_str(),
+            true,
+            true,
+        )
+        .await?
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND => Ok(vec![]),
+        StatusCode::OK => Ok(response.json::<Vec<FeatureGroupDTO>>().await?),
+        _ => {
+            Err(HopsworksError::from_response("get_feature_group_versions_by_name", response).await)
+        }
+    }
+}
+
+/// Every version of every feature group of the feature store.
+pub async fn get_feature_group_list(feature_store_id: i32) -> Result<Vec<FeatureGroupDTO>> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::GET,
+            format!("featurestores/{feature_store_id}/featuregroups").as_str(),
+            true,
+            true,
+        )
+        .await?
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(response.json::<Vec<FeatureGroupDTO>>().await?),
+        _ => Err(HopsworksError::from_response("get_feature_group_list", response).await),
     }
 }
 
@@ -94,7 +131,7 @@ pub async fn create_feature_group(
     new_feature_group_payload: &NewFeatureGroupPayload,
 ) -> Result<FeatureGroupDTO> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!("featurestores/{feature_store_id}/featuregroups/").as_str(),
@@ -108,17 +145,41 @@ pub async fn create_feature_group(
 
     match response.status() {
         StatusCode::CREATED => Ok(response.json::<FeatureGroupDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "create_feature_group failed with status : {:?}, here is the response :\n{:?}",
-            response.status(),
-            response.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("create_feature_group", response).await),
+    }
+}
+
+/// Update a registered feature group, the query parameters select which part of the
+/// metadata is updated, e.g. `appendFeatures` or `updateMetadata`.
+pub async fn update_feature_group(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    feature_group_dto: &FeatureGroupDTO,
+    query_params: &[(&str, &str)],
+) -> Result<FeatureGroupDTO> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::PUT,
+            format!("featurestores/{feature_store_id}/featuregroups/{feature_group_id}").as_str(),
+            true,
+            true,
+        )
+        .await?
+        .query(query_params)
+        .json(feature_group_dto)
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(response.json::<FeatureGroupDTO>().await?),
+        _ => Err(HopsworksError::from_response("update_feature_group", response).await),
     }
 }
 
 pub async fn delete_feature_group(feature_store_id: i32, feature_group_id: i32) -> Result<()> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::DELETE,
             format!("featurestores/{feature_store_id}/featuregroups/{feature_group_id}").as_str(),
@@ -131,10 +192,6 @@ pub async fn delete_feature_group(feature_store_id: i32, feature_group_id: i32)
 
     match response.status() {
         StatusCode::OK => Ok(()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "delete_feature_group failed with status : {:?}, here is the response :\n{:?}",
-            response.status(),
-            response.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("delete_feature_group", response).await),
     }
 }
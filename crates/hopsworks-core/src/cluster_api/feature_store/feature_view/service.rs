@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::{Method, StatusCode};
 
 use super::{
@@ -30,7 +30,7 @@ pub async fn get_feature_view_by_name_and_version(
     };
 
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, relative_url.as_str(), true, true)
         .await?
         .query(&query_params)
@@ -39,11 +39,7 @@ pub async fn get_feature_view_by_name_and_version(
 
     match res.status() {
         StatusCode::OK => Ok(Some(res.json::<FeatureViewDTO>().await?)),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_feature_view failed with status : {:?}, here is the response :\n{:?}",
-            res.status(),
-            res.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("get_feature_view", res).await),
     }
 }
 
@@ -52,7 +48,7 @@ pub async fn create_feature_view(
     new_feature_view_payload: NewFeatureViewPayload,
 ) -> Result<FeatureViewDTO> {
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!("featurestores/{}/featureview", feature_store_id).as_str(),
@@ -66,11 +62,7 @@ pub async fn create_feature_view(
 
     match res.status() {
         StatusCode::CREATED => Ok(res.json::<FeatureViewDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "create_feature_view failed with status : {:?}, here is the response :\n{:?}",
-            res.status(),
-            res.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("create_feature_view", res).await),
     }
 }
 
@@ -81,7 +73,7 @@ pub async fn create_training_dataset_attached_to_feature_view(
     new_training_dataset_payload: NewTrainingDatasetPayload,
 ) -> Result<TrainingDatasetDTO> {
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!(
@@ -99,11 +91,7 @@ pub async fn create_training_dataset_attached_to_feature_view(
 
     match res.status() {
         StatusCode::CREATED => Ok(res.json::<TrainingDatasetDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "create_training_dataset failed with status : {:?}, here is the response :\n{:?}",
-            res.status(),
-            res.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("create_training_dataset", res).await),
     }
 }
 
@@ -115,7 +103,7 @@ pub async fn compute_training_dataset_attached_to_feature_view(
     job_config: TrainingDatasetComputeJobConfigPayload,
 ) -> Result<JobDTO> {
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!(
@@ -132,11 +120,7 @@ pub async fn compute_training_dataset_attached_to_feature_view(
 
     match res.status() {
         StatusCode::CREATED => Ok(res.json::<JobDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "create_training_dataset failed with status : {:?}, here is the response :\n{:?}",
-            res.status(),
-            res.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("create_training_dataset", res).await),
     }
 }
 
@@ -147,7 +131,7 @@ pub async fn get_feature_view_batch_query(
     batch_query_payload: FeatureViewBatchQueryPayload,
 ) -> Result<QueryDTO> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!(
@@ -164,11 +148,7 @@ pub async fn get_feature_view_batch_query(
 
     match resp.status() {
         StatusCode::OK => Ok(resp.json::<QueryDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_feature_view_batch_query failed with status : {:?}, here is the response :\n{:?}",
-            resp.status(),
-            resp.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("get_feature_view_batch_query", resp).await),
     }
 }
 
@@ -178,7 +158,7 @@ pub async fn delete_feature_view_by_name_and_version(
     feature_view_version: i32,
 ) -> Result<()> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::DELETE,
             format!("featurestores/{feature_store_id}/featureview/{feature_view_name}/version/{feature_view_version}").as_str(),
@@ -191,11 +171,11 @@ pub async fn delete_feature_view_by_name_and_version(
 
     match response.status() {
         StatusCode::OK => Ok(()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "delete_feature_view_by_name_and_version failed with status : {:?}, here is the response :\n{:?}",
-            response.status(),
-            response.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response(
+            "delete_feature_view_by_name_and_version",
+            response,
+        )
+        .await),
     }
 }
 
@@ -204,7 +184,7 @@ pub async fn delete_feature_view_by_name(
     feature_view_name: &str,
 ) -> Result<()> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::DELETE,
             format!("featurestores/{feature_store_id}/featureview/{feature_view_name}").as_str(),
@@ -217,10 +197,6 @@ pub async fn delete_feature_view_by_name(
 
     match response.status() {
         StatusCode::OK => Ok(()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "delete_feature_view_by_name failed with status : {:?}, here is the response :\n{:?}",
-            response.status(),
-            response.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("delete_feature_view_by_name", response).await),
     }
 }
@@ -3,10 +3,12 @@ use serde::{Deserialize, Serialize};
 pub(crate) mod service;
 
 pub(crate) mod embedding;
+pub(crate) mod expectation_suite;
 pub(crate) mod feature;
 pub(crate) mod feature_group;
 pub(crate) mod feature_view;
 pub(crate) mod query;
+pub(crate) mod statistics;
 pub(crate) mod statistics_config;
 pub(crate) mod storage_connector;
 pub(crate) mod training_dataset;
@@ -30,8 +30,27 @@ pub struct FeatureStoreQueryDTO {
     pub query_online: String,
     pub pit_query: Option<String>,
     pub pit_query_asof: Option<String>,
-    pub hudi_cached_feature_groups: Vec<FeatureGroupDTO>,
-    pub on_demand_feature_groups: Vec<FeatureGroupDTO>,
+    pub hudi_cached_feature_groups: Vec<HudiFeatureGroupAliasDTO>,
+    pub on_demand_feature_groups: Vec<OnDemandFeatureGroupAliasDTO>,
+}
+
+/// Alias of a cached feature group in the SQL of a [`FeatureStoreQueryDTO`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HudiFeatureGroupAliasDTO {
+    pub feature_group: FeatureGroupDTO,
+    pub alias: String,
+    pub left_feature_group_start_timestamp: Option<i64>,
+    pub left_feature_group_end_timestamp: Option<i64>,
+}
+
+/// Alias of an external feature group in the SQL of a [`FeatureStoreQueryDTO`],
+/// under which the data read from its storage connector is registered.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OnDemandFeatureGroupAliasDTO {
+    pub on_demand_feature_group: FeatureGroupDTO,
+    pub alias: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::{Method, StatusCode};
 
 use crate::get_hopsworks_client;
@@ -7,7 +7,7 @@ use super::{payloads::NewQueryPayload, FeatureStoreQueryDTO};
 
 pub async fn construct_query(query_payload: NewQueryPayload) -> Result<FeatureStoreQueryDTO> {
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::PUT, "featurestores/query", true, true)
         .await?
         .json(&query_payload)
@@ -16,11 +16,7 @@ pub async fn construct_query(query_payload: NewQueryPayload) -> Result<FeatureSt
 
     match res.status() {
         StatusCode::OK => Ok(res.json::<FeatureStoreQueryDTO>().await?),
-        _ => panic!(
-            "Failed with status {:?}, here is the response : \n{:?}.",
-            res.status(),
-            res.text_with_charset("utf-8").await
-        ),
+        _ => Err(HopsworksError::from_response("construct_query", res).await),
     }
 }
 
@@ -30,7 +26,7 @@ pub async fn get_batch_query_by_feature_view_name_and_version(
     version: i32,
 ) -> Result<FeatureStoreQueryDTO> {
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!(
@@ -46,10 +42,10 @@ pub async fn get_batch_query_by_feature_view_name_and_version(
 
     match res.status() {
         StatusCode::OK => Ok(res.json::<FeatureStoreQueryDTO>().await?),
-        _ => panic!(
-            "Failed with status {:?}, here is the response : \n{:?}.",
-            res.status(),
-            res.text_with_charset("utf-8").await
-        ),
+        _ => Err(HopsworksError::from_response(
+            "get_batch_query_by_feature_view_name_and_version",
+            res,
+        )
+        .await),
     }
 }
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::{Method, StatusCode};
 
 use crate::get_hopsworks_client;
@@ -7,7 +7,7 @@ use super::FeatureStoreDTO;
 
 pub async fn get_feature_store_by_name(feature_store_name: &str) -> Result<FeatureStoreDTO> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{feature_store_name}").as_str(),
@@ -20,10 +20,6 @@ pub async fn get_feature_store_by_name(feature_store_name: &str) -> Result<Featu
 
     match resp.status() {
         StatusCode::OK => Ok(resp.json::<FeatureStoreDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_feature_store_by_name failed with status : {:?}, here is the response :\n{:?}",
-            resp.status(),
-            resp.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("get_feature_store_by_name", resp).await),
     }
 }
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+pub mod service;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureDescriptiveStatisticsDTO {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    pub feature_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completeness: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_non_null_values: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_null_values: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approx_num_distinct_values: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stddev: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentiles: Option<Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distinctness: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uniqueness: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exact_num_distinct_values: Option<i64>,
+    /// JSON encoded histogram and correlations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_statistics: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsDTO {
+    /// Epoch milliseconds.
+    pub computation_time: i64,
+    pub row_percentage: f64,
+    #[serde(default)]
+    pub feature_descriptive_statistics: Vec<FeatureDescriptiveStatisticsDTO>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_start_commit_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_end_commit_time: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsListDTO {
+    #[serde(default)]
+    pub count: i32,
+    #[serde(default)]
+    pub items: Vec<StatisticsDTO>,
+}
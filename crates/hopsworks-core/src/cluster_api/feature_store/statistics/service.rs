@@ -0,0 +1,66 @@
+use crate::error::{HopsworksError, Result};
+use reqwest::{Method, StatusCode};
+
+use super::{StatisticsDTO, StatisticsListDTO};
+use crate::get_hopsworks_client;
+
+/// Most recent statistics of the feature group, `None` if none were computed.
+pub async fn get_last_statistics(
+    feature_store_id: i32,
+    feature_group_id: i32,
+) -> Result<Option<StatisticsDTO>> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::GET,
+            format!("featurestores/{feature_store_id}/featuregroups/{feature_group_id}/statistics")
+                .as_str(),
+            true,
+            true,
+        )
+        .await?
+        .query(&[
+            ("sort_by", "computation_time:desc"),
+            ("offset", "0"),
+            ("limit", "1"),
+            ("fields", "content"),
+        ])
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(response
+            .json::<StatisticsListDTO>()
+            .await?
+            .items
+            .into_iter()
+            .next()),
+        StatusCode::NOT_FOUND | StatusCode::NO_CONTENT => Ok(None),
+        _ => Err(HopsworksError::from_response("get_last_statistics", response).await),
+    }
+}
+
+pub async fn save_statistics(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    statistics_dto: &StatisticsDTO,
+) -> Result<StatisticsDTO> {
+    let response = get_hopsworks_client()
+        .await?
+        .request(
+            Method::POST,
+            format!("featurestores/{feature_store_id}/featuregroups/{feature_group_id}/statistics")
+                .as_str(),
+            true,
+            true,
+        )
+        .await?
+        .json(statistics_dto)
+        .send()
+        .await?;
+
+    match response.status() {
+        StatusCode::OK | StatusCode::CREATED => Ok(response.json::<StatisticsDTO>().await?),
+        _ => Err(HopsworksError::from_response("save_statistics", response).await),
+    }
+}
@@ -26,35 +26,106 @@ pub struct FeatureStoreKafkaConnectorDTO {
 #[serde(rename_all = "camelCase")]
 pub struct FeatureStoreHopsfsConnectorDTO {
     #[serde(rename = "type")]
-    _type: String,
-    id: i32,
-    description: String,
-    name: String,
+    pub(crate) _type: String,
+    pub(crate) id: i32,
+    pub(crate) description: String,
+    pub(crate) name: String,
     #[serde(rename = "featurestoreId")]
-    feature_store_id: i32,
-    storage_connector_type: String,
-    hopsfs_path: String,
-    dataset_name: String,
+    pub(crate) feature_store_id: i32,
+    pub(crate) storage_connector_type: String,
+    pub(crate) hopsfs_path: String,
+    pub(crate) dataset_name: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FeatureStoreJdbcConnectorDTO {
     #[serde(rename = "type")]
-    _type: String,
-    id: i32,
-    description: String,
-    name: String,
+    pub(crate) _type: String,
+    pub(crate) id: i32,
+    pub(crate) description: String,
+    pub(crate) name: String,
     #[serde(rename = "featurestoreId")]
-    feature_store_id: i32,
-    storage_connector_type: String,
+    pub(crate) feature_store_id: i32,
+    pub(crate) storage_connector_type: String,
     pub(crate) connection_string: String,
     pub(crate) arguments: Vec<HashMap<String, String>>,
 }
 
+/// S3 connector, the credentials are only deserialized and are left out of the debug output,
+/// which shows the names of the arguments without their values.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureStoreS3ConnectorDTO {
+    #[serde(rename = "type")]
+    pub(crate) _type: String,
+    pub(crate) id: i32,
+    pub(crate) description: Option<String>,
+    pub(crate) name: String,
+    #[serde(rename = "featurestoreId")]
+    pub(crate) feature_store_id: i32,
+    pub(crate) storage_connector_type: String,
+    pub(crate) bucket: String,
+    #[serde(default)]
+    pub(crate) region: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub(crate) access_key: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub(crate) secret_key: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub(crate) session_token: Option<String>,
+    #[serde(default)]
+    pub(crate) arguments: Vec<HashMap<String, String>>,
+}
+
+impl std::fmt::Debug for FeatureStoreS3ConnectorDTO {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let redacted = |secret: &Option<String>| secret.as_ref().map(|_| "<redacted>");
+        f.debug_struct("FeatureStoreS3ConnectorDTO")
+            .field("_type", &self._type)
+            .field("id", &self.id)
+            .field("description", &self.description)
+            .field("name", &self.name)
+            .field("feature_store_id", &self.feature_store_id)
+            .field("storage_connector_type", &self.storage_connector_type)
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("access_key", &redacted(&self.access_key))
+            .field("secret_key", &redacted(&self.secret_key))
+            .field("session_token", &redacted(&self.session_token))
+            .field(
+                "arguments",
+                &self
+                    .arguments
+                    .iter()
+                    .filter_map(|argument| argument.get("name"))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Fields shared by the storage connectors of every type, as returned by the list endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureStoreStorageConnectorDTO {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub id: i32,
+    pub description: Option<String>,
+    pub name: String,
+    #[serde(rename = "featurestoreId")]
+    pub feature_store_id: i32,
+    pub storage_connector_type: String,
+}
+
+/// Storage connector with the fields specific to its type. Connectors of the types without
+/// a dedicated DTO, e.g. `SNOWFLAKE`, only keep the fields shared by every type.
 pub enum StorageConnectorDTO {
     JdbcConnectorDTO(FeatureStoreJdbcConnectorDTO),
     HopsfsConnectorDTO(FeatureStoreHopsfsConnectorDTO),
+    S3ConnectorDTO(FeatureStoreS3ConnectorDTO),
+    OtherConnectorDTO(FeatureStoreStorageConnectorDTO),
 }
 
 impl std::fmt::Debug for StorageConnectorDTO {
@@ -64,6 +135,8 @@ impl std::fmt::Debug for StorageConnectorDTO {
             StorageConnectorDTO::HopsfsConnectorDTO(hopsfs_connector_dto) => {
                 hopsfs_connector_dto.fmt(f)
             }
+            StorageConnectorDTO::S3ConnectorDTO(s3_connector_dto) => s3_connector_dto.fmt(f),
+            StorageConnectorDTO::OtherConnectorDTO(connector_dto) => connector_dto.fmt(f),
         }
     }
 }
@@ -80,6 +153,12 @@ impl Serialize for StorageConnectorDTO {
             StorageConnectorDTO::HopsfsConnectorDTO(ref hopsfs_connector_dto) => {
                 hopsfs_connector_dto.serialize(serializer)
             }
+            StorageConnectorDTO::S3ConnectorDTO(ref s3_connector_dto) => {
+                s3_connector_dto.serialize(serializer)
+            }
+            StorageConnectorDTO::OtherConnectorDTO(ref connector_dto) => {
+                connector_dto.serialize(serializer)
+            }
         }
     }
 }
@@ -90,24 +169,24 @@ impl<'de> Deserialize<'de> for StorageConnectorDTO {
         D: serde::Deserializer<'de>,
     {
         let value: serde_json::Value = serde_json::Value::deserialize(deserializer)?;
-        let _type = value["type"].as_str().unwrap();
-        match _type {
-            "JDBC" => {
-                let jdbc_connector_dto: FeatureStoreJdbcConnectorDTO =
-                    serde_json::from_value(value).unwrap();
-                Ok(StorageConnectorDTO::JdbcConnectorDTO(jdbc_connector_dto))
-            }
-            "HOPSFS" => {
-                let hopsfs_connector_dto: FeatureStoreHopsfsConnectorDTO =
-                    serde_json::from_value(value).unwrap();
-                Ok(StorageConnectorDTO::HopsfsConnectorDTO(
-                    hopsfs_connector_dto,
-                ))
-            }
-            _ => Err(serde::de::Error::custom(format!(
-                "Unknown storage connector type: {}",
-                _type,
-            ))),
+        let storage_connector_type = value["storageConnectorType"]
+            .as_str()
+            .or(value["type"].as_str())
+            .unwrap_or_default()
+            .to_string();
+        match storage_connector_type.as_str() {
+            "JDBC" => Ok(StorageConnectorDTO::JdbcConnectorDTO(
+                serde_json::from_value(value).map_err(serde::de::Error::custom)?,
+            )),
+            "HOPSFS" => Ok(StorageConnectorDTO::HopsfsConnectorDTO(
+                serde_json::from_value(value).map_err(serde::de::Error::custom)?,
+            )),
+            "S3" => Ok(StorageConnectorDTO::S3ConnectorDTO(
+                serde_json::from_value(value).map_err(serde::de::Error::custom)?,
+            )),
+            _ => Ok(StorageConnectorDTO::OtherConnectorDTO(
+                serde_json::from_value(value).map_err(serde::de::Error::custom)?,
+            )),
         }
     }
 }
@@ -121,6 +200,12 @@ impl Clone for StorageConnectorDTO {
             StorageConnectorDTO::HopsfsConnectorDTO(hopsfs_connector_dto) => {
                 StorageConnectorDTO::HopsfsConnectorDTO(hopsfs_connector_dto.clone())
             }
+            StorageConnectorDTO::S3ConnectorDTO(s3_connector_dto) => {
+                StorageConnectorDTO::S3ConnectorDTO(s3_connector_dto.clone())
+            }
+            StorageConnectorDTO::OtherConnectorDTO(connector_dto) => {
+                StorageConnectorDTO::OtherConnectorDTO(connector_dto.clone())
+            }
         }
     }
 }
@@ -1,10 +1,14 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::Method;
 use tracing::debug;
 
 use crate::get_hopsworks_client;
+use crate::pagination::{ListOptions, PageDTO};
 
-use super::{FeatureStoreJdbcConnectorDTO, FeatureStoreKafkaConnectorDTO, StorageConnectorDTO};
+use super::{
+    FeatureStoreJdbcConnectorDTO, FeatureStoreKafkaConnectorDTO, FeatureStoreStorageConnectorDTO,
+    StorageConnectorDTO,
+};
 
 pub async fn get_feature_store_kafka_connector(
     feature_store_id: i32,
@@ -15,7 +19,7 @@ pub async fn get_feature_store_kafka_connector(
         feature_store_id
     );
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{feature_store_id}/storageconnectors/kafka_connector/byok")
@@ -30,11 +34,7 @@ pub async fn get_feature_store_kafka_connector(
 
     match resp.status() {
         reqwest::StatusCode::OK => Ok(resp.json::<FeatureStoreKafkaConnectorDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_feature_store_kafka_connector failed with status : {:?}, here is the response :\n{:?}",
-            resp.status(),
-            resp.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("get_feature_store_kafka_connector", resp).await),
     }
 }
 
@@ -46,7 +46,7 @@ pub async fn get_feature_store_online_connector(
         feature_store_id
     );
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{feature_store_id}/storageconnectors/onlinefeaturestore")
@@ -60,23 +60,20 @@ pub async fn get_feature_store_online_connector(
 
     match resp.status() {
         reqwest::StatusCode::OK => Ok(resp.json::<FeatureStoreJdbcConnectorDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_feature_store_online_connector failed with status : {:?}, here is the response :\n{:?}",
-            resp.status(),
-            resp.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("get_feature_store_online_connector", resp).await),
     }
 }
 
 pub async fn get_list_feature_store_storage_connectors(
     feature_store_id: i32,
-) -> Result<StorageConnectorDTO> {
+    list_options: &ListOptions,
+) -> Result<PageDTO<FeatureStoreStorageConnectorDTO>> {
     debug!(
         "Fetching list storage connectors for feature store {}",
         feature_store_id
     );
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{feature_store_id}/storageconnectors").as_str(),
@@ -84,15 +81,47 @@ pub async fn get_list_feature_store_storage_connectors(
             true,
         )
         .await?
+        .query(&list_options.to_query_params())
+        .send()
+        .await?;
+
+    match resp.status() {
+        reqwest::StatusCode::OK => Ok(resp
+            .json::<PageDTO<FeatureStoreStorageConnectorDTO>>()
+            .await?),
+        _ => Err(
+            HopsworksError::from_response("get_list_feature_store_storage_connectors", resp).await,
+        ),
+    }
+}
+
+pub async fn get_feature_store_storage_connector_by_name(
+    feature_store_id: i32,
+    name: &str,
+) -> Result<Option<StorageConnectorDTO>> {
+    debug!(
+        "Fetching storage connector {} for feature store {}",
+        name, feature_store_id
+    );
+    let resp = get_hopsworks_client()
+        .await?
+        .request(
+            Method::GET,
+            format!("featurestores/{feature_store_id}/storageconnectors/{name}").as_str(),
+            true,
+            true,
+        )
+        .await?
         .send()
         .await?;
 
     match resp.status() {
-        reqwest::StatusCode::OK => Ok(resp.json::<StorageConnectorDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_list_feature_store_storage_connectors failed with status : {:?}, here is the response :\n{:?}",
-            resp.status(),
-            resp.text_with_charset("utf-8").await?
-        )),
+        reqwest::StatusCode::NOT_FOUND => Ok(None),
+        reqwest::StatusCode::OK => Ok(Some(resp.json::<StorageConnectorDTO>().await?)),
+        _ => Err(HopsworksError::from_response(
+            "get_feature_store_storage_connector_by_name",
+            resp,
+        )
+        .await),
     }
 }
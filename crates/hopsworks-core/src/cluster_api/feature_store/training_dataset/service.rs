@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::{Method, StatusCode};
 
 use crate::get_hopsworks_client;
@@ -19,7 +19,7 @@ pub async fn get_training_dataset_by_name_and_version(
     }
 
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{feature_store_id}/trainingdatasets/{name}").as_str(),
@@ -32,12 +32,14 @@ pub async fn get_training_dataset_by_name_and_version(
         .await?;
 
     match res.status() {
-        StatusCode::OK => Ok(res.json::<Vec<TrainingDatasetDTO>>().await?.first().cloned()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_training_dataset_by_name_and_version failed with status : {:?}, here is the response :\n{:?}",
-            res.status(),
-            res.text_with_charset("utf-8").await?
-        )),
+        StatusCode::OK => Ok(res
+            .json::<Vec<TrainingDatasetDTO>>()
+            .await?
+            .first()
+            .cloned()),
+        _ => Err(
+            HopsworksError::from_response("get_training_dataset_by_name_and_version", res).await,
+        ),
     }
 }
 
@@ -45,7 +47,7 @@ pub async fn create_training_dataset(
     new_training_dataset_payload: NewTrainingDatasetPayload,
 ) -> Result<TrainingDatasetDTO> {
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!(
@@ -63,11 +65,7 @@ pub async fn create_training_dataset(
 
     match res.status() {
         StatusCode::CREATED => Ok(res.json::<TrainingDatasetDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "create_training_dataset failed with status : {:?}, here is the response :\n{:?}",
-            res.status(),
-            res.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("create_training_dataset", res).await),
     }
 }
 
@@ -78,7 +76,7 @@ pub async fn create_feature_view_training_dataset(
     new_training_dataset_payload: NewTrainingDatasetPayloadV2,
 ) -> Result<TrainingDatasetDTO> {
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!(
@@ -95,10 +93,6 @@ pub async fn create_feature_view_training_dataset(
 
     match res.status() {
         StatusCode::CREATED => Ok(res.json::<TrainingDatasetDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "create_training_dataset failed with status : {:?}, here is the response :\n{:?}",
-            res.status(),
-            res.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("create_training_dataset", res).await),
     }
 }
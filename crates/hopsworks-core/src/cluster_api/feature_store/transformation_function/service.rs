@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::{Method, StatusCode};
 
 use crate::get_hopsworks_client;
@@ -19,7 +19,7 @@ pub async fn get_transformation_function_by_name_and_version(
     }
 
     let res = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("featurestores/{}/transformationfunctions", feature_store_id).as_str(),
@@ -31,11 +31,15 @@ pub async fn get_transformation_function_by_name_and_version(
         .send()
         .await?;
 
-    let mut transformation_function_list= match res.status() {
+    let mut transformation_function_list = match res.status() {
         StatusCode::OK => res.json::<TransformationFunctionResponse>().await?,
-        _ => Err(color_eyre::eyre::eyre!("Transformation function get request failed with status {:?}, here is the response : \n{:?}.",
-            res.status(),
-            res.text_with_charset("utf-8").await))?,
+        _ => {
+            return Err(HopsworksError::from_response(
+                "get_transformation_function_by_name_and_version",
+                res,
+            )
+            .await)
+        }
     };
 
     match transformation_function_list.items.pop() {
@@ -1,2 +1,2 @@
+pub mod feature_store;
 pub mod platform;
-pub mod feature_store;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 use reqwest::Method;
 
 use crate::get_hopsworks_client;
@@ -6,7 +6,7 @@ use super::CredentialsDTO;
 
 pub async fn get_hopsworks_credentials_for_project() -> Result<CredentialsDTO> {
     Ok(get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, "credentials", true, true)
         .await?
         .send()
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::Method;
 
 use crate::cluster_api::platform::file_system::FlowBaseParams;
@@ -6,7 +6,7 @@ use crate::get_hopsworks_client;
 
 pub async fn remove(path: &str) -> Result<()> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::DELETE,
             format!("dataset/{}", path).as_str(),
@@ -19,16 +19,13 @@ pub async fn remove(path: &str) -> Result<()> {
 
     match resp.status() {
         reqwest::StatusCode::OK => Ok(()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "Failed to remove dataset: {}",
-            resp.text().await?
-        )),
+        _ => Err(HopsworksError::from_response("remove", resp).await),
     }
 }
 
 pub async fn get_path_metadata(path: &str) -> Result<serde_json::Value> {
-    Ok(get_hopsworks_client()
-        .await
+    let resp = get_hopsworks_client()
+        .await?
         .request(
             Method::GET,
             format!("dataset/{}", path).as_str(),
@@ -37,14 +34,17 @@ pub async fn get_path_metadata(path: &str) -> Result<serde_json::Value> {
         )
         .await?
         .send()
-        .await?
-        .json::<serde_json::Value>()
-        .await?)
+        .await?;
+
+    match resp.status() {
+        reqwest::StatusCode::OK => Ok(resp.json::<serde_json::Value>().await?),
+        _ => Err(HopsworksError::from_response("get_path_metadata", resp).await),
+    }
 }
 
 pub async fn mkdir(path: &str) -> Result<()> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!("dataset/{}", path).as_str(),
@@ -63,16 +63,13 @@ pub async fn mkdir(path: &str) -> Result<()> {
 
     match resp.status() {
         reqwest::StatusCode::OK => Ok(()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "Failed to create directory: {}",
-            resp.text().await?
-        )),
+        _ => Err(HopsworksError::from_response("mkdir", resp).await),
     }
 }
 
 pub async fn move_file_or_dir(src_path: &str, dst_path: &str) -> Result<()> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!("dataset/{}", src_path).as_str(),
@@ -86,16 +83,13 @@ pub async fn move_file_or_dir(src_path: &str, dst_path: &str) -> Result<()> {
 
     match resp.status() {
         reqwest::StatusCode::OK => Ok(()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "Failed to move dataset: {}",
-            resp.text().await?
-        )),
+        _ => Err(HopsworksError::from_response("move_file_or_dir", resp).await),
     }
 }
 
 pub async fn copy(src_path: &str, dst_path: &str) -> Result<()> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!("dataset/{}", src_path).as_str(),
@@ -109,10 +103,7 @@ pub async fn copy(src_path: &str, dst_path: &str) -> Result<()> {
 
     match resp.status() {
         reqwest::StatusCode::OK => Ok(()),
-        _ => Err(color_eyre::eyre::eyre!(
-            "Failed to copy dataset: {}",
-            resp.text().await?
-        )),
+        _ => Err(HopsworksError::from_response("copy", resp).await),
     }
 }
 
@@ -123,7 +114,7 @@ pub async fn upload_request_single_chunk(
     flow_params: FlowBaseParams,
 ) -> Result<reqwest::Response> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!("dataset/upload/{}", path).as_str(),
@@ -141,8 +132,8 @@ pub async fn upload_request_single_chunk(
 }
 
 pub async fn download(path: &str) -> Result<reqwest::Response> {
-    Ok(get_hopsworks_client()
-        .await
+    get_hopsworks_client()
+        .await?
         .request(
             Method::GET,
             format!("dataset/download/with_auth/{}", path).as_str(),
@@ -152,5 +143,5 @@ pub async fn download(path: &str) -> Result<reqwest::Response> {
         .await?
         .query(&[("type", "Dataset")])
         .send()
-        .await?)
+        .await
 }
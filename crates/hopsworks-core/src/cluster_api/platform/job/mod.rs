@@ -61,10 +61,3 @@ struct JobConfigDTO {
     #[serde(rename = "spark.tensorflow.num.ps")]
     spark_tensorflow_num_ps: i32,
 }
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct JobListDTO {
-    href: String,
-    items: Vec<JobDTO>,
-}
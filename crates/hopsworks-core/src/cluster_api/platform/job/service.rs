@@ -1,13 +1,14 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::Method;
 
 use crate::get_hopsworks_client;
+use crate::pagination::{ListOptions, PageDTO};
 
-use super::{JobDTO, JobListDTO};
+use super::JobDTO;
 
 pub async fn get_job_by_name(job_name: &str) -> Result<JobDTO> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, format!("jobs/{job_name}").as_str(), true, true)
         .await?
         .query(&[("expand", "creator")])
@@ -17,23 +18,29 @@ pub async fn get_job_by_name(job_name: &str) -> Result<JobDTO> {
     if response.status().is_success() {
         Ok(response.json::<JobDTO>().await?)
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("get_job_by_name", response).await)
     }
 }
 
-pub async fn get_job_list() -> Result<Vec<JobDTO>> {
+pub async fn get_job_list(list_options: &ListOptions) -> Result<PageDTO<JobDTO>> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, "jobs", true, true)
         .await?
-        .query(&[("expand", "creator"), ("sort_by", "submissiontime:desc")])
+        .query(&[("expand", "creator")])
+        .query(
+            &list_options
+                .clone()
+                .with_default_sort_by("submissiontime:desc")
+                .to_query_params(),
+        )
         .send()
         .await?;
 
     if response.status().is_success() {
-        Ok(response.json::<JobListDTO>().await?.items)
+        Ok(response.json::<PageDTO<JobDTO>>().await?)
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("get_job_list", response).await)
     }
 }
 
@@ -41,19 +48,14 @@ pub async fn exists(job_name: &str) -> Result<bool> {
     let response = get_job_by_name(job_name).await;
     match response {
         Ok(_) => Ok(true),
-        Err(e) => {
-            if e.to_string().contains("404") {
-                Ok(false)
-            } else {
-                Err(e)
-            }
-        }
+        Err(HopsworksError::NotFound(_)) => Ok(false),
+        Err(e) => Err(e),
     }
 }
 
 pub async fn delete_job(job_name: &str) -> Result<()> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::DELETE,
             format!("jobs/{job_name}").as_str(),
@@ -67,13 +69,13 @@ pub async fn delete_job(job_name: &str) -> Result<()> {
     if response.status().is_success() {
         Ok(())
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("delete_job", response).await)
     }
 }
 
 pub async fn get_job_configuration(job_type: &str) -> Result<serde_json::Value> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("jobs/{job_type}/configuration").as_str(),
@@ -87,13 +89,13 @@ pub async fn get_job_configuration(job_type: &str) -> Result<serde_json::Value>
     if response.status().is_success() {
         Ok(response.json::<serde_json::Value>().await?)
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("get_job_configuration", response).await)
     }
 }
 
 pub async fn create_job(job_name: &str, job_config: serde_json::Value) -> Result<JobDTO> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!("jobs/{job_name}").as_str(),
@@ -108,13 +110,13 @@ pub async fn create_job(job_name: &str, job_config: serde_json::Value) -> Result
     if response.status().is_success() {
         Ok(response.json::<JobDTO>().await?)
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("create_job", response).await)
     }
 }
 
 pub async fn update_job(job_name: &str, job_config: serde_json::Value) -> Result<JobDTO> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::PUT, format!("jobs/{job_name}").as_str(), true, true)
         .await?
         .json(&job_config)
@@ -124,6 +126,6 @@ pub async fn update_job(job_name: &str, job_config: serde_json::Value) -> Result
     if response.status().is_success() {
         Ok(response.json::<JobDTO>().await?)
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("update_job", response).await)
     }
 }
@@ -1,15 +1,19 @@
-use color_eyre::Result;
-use reqwest::Method;
+use crate::error::{HopsworksError, Result};
+use reqwest::{
+    header::{HeaderValue, CONTENT_TYPE},
+    Method,
+};
 
 use super::JobExecutionDTO;
 use crate::get_hopsworks_client;
+use crate::pagination::{ListOptions, PageDTO};
 
 pub async fn start_new_execution_for_named_job(
     job_name: &str,
     args: &str,
 ) -> Result<JobExecutionDTO> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::POST,
             format!("jobs/{job_name}/executions").as_str(),
@@ -17,7 +21,7 @@ pub async fn start_new_execution_for_named_job(
             true,
         )
         .await?
-        .header("Content-Type", "text/plain")
+        .header(CONTENT_TYPE, HeaderValue::from_static("text/plain"))
         .body(args.to_string())
         .send()
         .await?;
@@ -27,7 +31,7 @@ pub async fn start_new_execution_for_named_job(
         job_execution_dto.job_name = Some(job_name.to_string());
         Ok(job_execution_dto)
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("start_new_execution_for_named_job", response).await)
     }
 }
 
@@ -36,7 +40,7 @@ pub async fn get_job_execution_by_id(
     job_execution_id: i32,
 ) -> Result<JobExecutionDTO> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("jobs/{job_name}/executions/{job_execution_id}").as_str(),
@@ -53,13 +57,16 @@ pub async fn get_job_execution_by_id(
         job_execution_dto.job_name = Some(job_name.to_string());
         Ok(job_execution_dto)
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("get_job_execution_by_id", response).await)
     }
 }
 
-pub async fn get_job_executions(job_name: &str) -> Result<Vec<JobExecutionDTO>> {
-    let mut job_execution_dtos = get_hopsworks_client()
-        .await
+pub async fn get_job_executions(
+    job_name: &str,
+    list_options: &ListOptions,
+) -> Result<PageDTO<JobExecutionDTO>> {
+    let response = get_hopsworks_client()
+        .await?
         .request(
             Method::GET,
             format!("jobs/{job_name}/executions").as_str(),
@@ -67,20 +74,29 @@ pub async fn get_job_executions(job_name: &str) -> Result<Vec<JobExecutionDTO>>
             true,
         )
         .await?
+        .query(
+            &list_options
+                .clone()
+                .with_default_sort_by("submissiontime:desc")
+                .to_query_params(),
+        )
         .send()
-        .await?
-        .json::<Vec<JobExecutionDTO>>()
         .await?;
 
-    for job_execution_dto in job_execution_dtos.iter_mut() {
-        job_execution_dto.job_name = Some(job_name.to_string());
+    if response.status().is_success() {
+        let mut page = response.json::<PageDTO<JobExecutionDTO>>().await?;
+        for job_execution_dto in page.items_mut() {
+            job_execution_dto.job_name = Some(job_name.to_string());
+        }
+        Ok(page)
+    } else {
+        Err(HopsworksError::from_response("get_job_executions", response).await)
     }
-    Ok(job_execution_dtos)
 }
 
 pub async fn stop_job_execution(job_name: &str, job_execution_id: i32) -> Result<JobExecutionDTO> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::PUT,
             format!("jobs/{job_name}/executions/{job_execution_id}").as_str(),
@@ -97,13 +113,13 @@ pub async fn stop_job_execution(job_name: &str, job_execution_id: i32) -> Result
         job_execution_dto.job_name = Some(job_name.to_string());
         Ok(job_execution_dto)
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("stop_job_execution", response).await)
     }
 }
 
 pub async fn delete_job_execution(job_name: &str, job_execution_id: i32) -> Result<()> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::DELETE,
             format!("jobs/{job_name}/executions/{job_execution_id}").as_str(),
@@ -117,6 +133,6 @@ pub async fn delete_job_execution(job_name: &str, job_execution_id: i32) -> Resu
     if response.status().is_success() {
         Ok(())
     } else {
-        Err(response.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("delete_job_execution", response).await)
     }
 }
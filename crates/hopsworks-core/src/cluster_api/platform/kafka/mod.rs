@@ -21,6 +21,12 @@ pub struct KafkaSubjectDTO {
     pub schema: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KafkaSchemaDTO {
+    pub schema: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct KafkaTopicListDTO {
@@ -1,15 +1,15 @@
-use color_eyre::Result;
+use crate::error::Result;
 use reqwest::Method;
 
 use crate::get_hopsworks_client;
 
-use super::{KafkaBrokersDTO, KafkaSubjectDTO};
+use super::{KafkaBrokersDTO, KafkaSchemaDTO, KafkaSubjectDTO};
 
 pub async fn get_project_broker_endpoints(external: bool) -> Result<KafkaBrokersDTO> {
     let query_params = [("external", external)];
 
     Ok(get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, "kafka/clusterinfo", true, true)
         .await?
         .query(query_params.as_ref())
@@ -25,7 +25,7 @@ pub async fn get_kafka_topic_subject(
 ) -> Result<KafkaSubjectDTO> {
     let version = opt_version.unwrap_or("latest");
     Ok(get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             format!("kafka/subjects/{subject_name}/versions/{version}").as_str(),
@@ -38,3 +38,19 @@ pub async fn get_kafka_topic_subject(
         .json::<KafkaSubjectDTO>()
         .await?)
 }
+
+pub async fn get_kafka_schema_by_id(schema_id: i32) -> Result<KafkaSchemaDTO> {
+    Ok(get_hopsworks_client()
+        .await?
+        .request(
+            Method::GET,
+            format!("kafka/schemas/ids/{schema_id}").as_str(),
+            true,
+            true,
+        )
+        .await?
+        .send()
+        .await?
+        .json::<KafkaSchemaDTO>()
+        .await?)
+}
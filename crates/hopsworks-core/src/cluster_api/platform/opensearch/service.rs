@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::Method;
 
 use crate::get_hopsworks_client;
@@ -7,7 +7,7 @@ use super::OpenSearchTokenDTO;
 
 pub async fn get_authorization_token(project_id: i32) -> Result<String> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, format!("elastic/jwt/{}", project_id).as_str(), true, false)
         .await?
         .send()
@@ -16,6 +16,6 @@ pub async fn get_authorization_token(project_id: i32) -> Result<String> {
     if resp.status().is_success() {
         Ok(resp.json::<OpenSearchTokenDTO>().await.map(|dto| dto.token)?)
     } else {
-        Err(resp.error_for_status().unwrap_err().into())
+        Err(HopsworksError::from_response("get_authorization_token", resp).await)
     }
 }
\ No newline at end of file
@@ -1,15 +1,15 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use reqwest::{Method, StatusCode};
 
 use super::{
-    {ProjectAndUserDTO, SingleProjectDTO},
     payloads::NewProjectPayload,
+    {ProjectAndUserDTO, SingleProjectDTO},
 };
 use crate::get_hopsworks_client;
 
 pub async fn get_project_and_user_list() -> Result<Vec<ProjectAndUserDTO>> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, "project", true, false)
         .await?
         .send()
@@ -18,11 +18,7 @@ pub async fn get_project_and_user_list() -> Result<Vec<ProjectAndUserDTO>> {
     if response.status().is_success() {
         Ok(response.json::<Vec<ProjectAndUserDTO>>().await?)
     } else {
-        Err(color_eyre::eyre::eyre!(
-            "get_project_and_user_list failed with status : {:?}, here is the response :\n{:?}",
-            response.status(),
-            response.text_with_charset("utf-8").await?
-        ))
+        Err(HopsworksError::from_response("get_project_and_user_list", response).await)
     }
 }
 
@@ -30,7 +26,7 @@ pub async fn create_project(
     new_project_payload: &NewProjectPayload,
 ) -> Result<Vec<ProjectAndUserDTO>> {
     let response = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::POST, "project", true, false)
         .await?
         .json(new_project_payload)
@@ -40,17 +36,13 @@ pub async fn create_project(
     if response.status().is_success() {
         Ok(response.json::<Vec<ProjectAndUserDTO>>().await?)
     } else {
-        Err(color_eyre::eyre::eyre!(
-            "create_project failed with status : {:?}, here is the response :\n{:?}",
-            response.status(),
-            response.text_with_charset("utf-8").await?
-        ))
+        Err(HopsworksError::from_response("create_project", response).await)
     }
 }
 
 pub async fn get_client_project() -> Result<SingleProjectDTO> {
     let resp = get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, "", true, true)
         .await?
         .send()
@@ -58,10 +50,6 @@ pub async fn get_client_project() -> Result<SingleProjectDTO> {
 
     match resp.status() {
         StatusCode::OK => Ok(resp.json::<SingleProjectDTO>().await?),
-        _ => Err(color_eyre::eyre::eyre!(
-            "get_client_project failed with status : {:?}, here is the response :\n{:?}",
-            resp.status(),
-            resp.text_with_charset("utf-8").await?
-        )),
+        _ => Err(HopsworksError::from_response("get_client_project", resp).await),
     }
 }
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 use reqwest::Method;
 
 use crate::get_hopsworks_client;
@@ -6,7 +6,7 @@ use super::StringMessageResponse;
 
 pub async fn get_flyingduck_enabled() -> Result<bool> {
     Ok(get_hopsworks_client()
-        .await
+        .await?
         .request(Method::GET, "variables/enable_flyingduck", true, false)
         .await?
         .send()
@@ -20,7 +20,7 @@ pub async fn get_flyingduck_enabled() -> Result<bool> {
 pub async fn get_loadbalancer_external_domain(service: &str) -> Result<String> {
     let path = format!("variables/loadbalancer_external_domain_{}", service);
     Ok(get_hopsworks_client()
-        .await
+        .await?
         .request(
             Method::GET,
             &path,
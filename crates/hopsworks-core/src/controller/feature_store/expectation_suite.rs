@@ -0,0 +1,55 @@
+use crate::error::Result;
+
+use crate::cluster_api::feature_store::expectation_suite::{
+    service, ExpectationSuiteDTO, ValidationReportDTO,
+};
+use crate::feature_store::feature_group::expectation_suite::{ExpectationSuite, ValidationReport};
+
+pub async fn get_expectation_suite(
+    feature_store_id: i32,
+    feature_group_id: i32,
+) -> Result<Option<ExpectationSuite>> {
+    service::get_expectation_suite(feature_store_id, feature_group_id)
+        .await?
+        .map(ExpectationSuite::try_from)
+        .transpose()
+}
+
+pub async fn save_expectation_suite(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    expectation_suite: &ExpectationSuite,
+) -> Result<ExpectationSuite> {
+    ExpectationSuite::try_from(
+        service::save_expectation_suite(
+            feature_store_id,
+            feature_group_id,
+            &ExpectationSuiteDTO::from(expectation_suite),
+        )
+        .await?,
+    )
+}
+
+pub async fn delete_expectation_suite(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    expectation_suite_id: i32,
+) -> Result<()> {
+    service::delete_expectation_suite(feature_store_id, feature_group_id, expectation_suite_id)
+        .await
+}
+
+pub async fn save_validation_report(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    validation_report: &ValidationReport,
+) -> Result<ValidationReport> {
+    ValidationReport::try_from(
+        service::save_validation_report(
+            feature_store_id,
+            feature_group_id,
+            &ValidationReportDTO::from(validation_report),
+        )
+        .await?,
+    )
+}
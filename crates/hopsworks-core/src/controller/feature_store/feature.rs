@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 
 use crate::cluster_api::feature_store::feature::payloads::NewFeaturePayload;
 
@@ -6,7 +6,17 @@ pub fn build_feature_payloads_from_schema_and_feature_group_options(
     feature_names: &[String],
     feature_types: &[String],
     primary_key: Vec<&str>,
+    partition_key: &[&str],
+    hudi_precombine_key: Option<&str>,
 ) -> Result<Vec<NewFeaturePayload>> {
+    for key in partition_key.iter().chain(hudi_precombine_key.iter()) {
+        if !feature_names.iter().any(|name| name == key) {
+            return Err(HopsworksError::Validation(format!(
+                "Feature {key} of the partition or precombine key not found in the schema {feature_names:?}."
+            )));
+        }
+    }
+
     let mut feature_payloads = feature_names
         .iter()
         .zip(feature_types.iter())
@@ -17,6 +27,12 @@ pub fn build_feature_payloads_from_schema_and_feature_group_options(
         if primary_key.contains(&payload.name.as_str()) {
             payload.primary = true;
         }
+        if partition_key.contains(&payload.name.as_str()) {
+            payload.partition = true;
+        }
+        if hudi_precombine_key == Some(payload.name.as_str()) {
+            payload.hudi_precombine_key = true;
+        }
     });
 
     Ok(feature_payloads)
@@ -1,10 +1,13 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 
 use crate::controller::feature_store::feature;
 
+use crate::cluster_api::feature_store::feature::FeatureDTO;
 use crate::cluster_api::feature_store::feature_group::{
     self, payloads::NewFeatureGroupPayload, FeatureGroupDTO,
 };
+use crate::cluster_api::feature_store::storage_connector::FeatureStoreStorageConnectorDTO;
+use crate::feature_store::feature_group::FeatureGroup;
 
 pub async fn get_feature_group_by_name_and_version(
     feature_store_id: i32,
@@ -23,6 +26,42 @@ pub async fn get_feature_group_by_name_and_version(
     .await
 }
 
+pub async fn get_feature_group_by_id(
+    feature_store_id: i32,
+    feature_group_id: i32,
+) -> Result<Option<FeatureGroupDTO>> {
+    feature_group::service::get_feature_group_by_id(feature_store_id, feature_group_id).await
+}
+
+/// Versions of the feature group `name` sorted by ascending version.
+pub async fn get_feature_group_versions(
+    feature_store_id: i32,
+    name: &str,
+) -> Result<Vec<FeatureGroupDTO>> {
+    let mut feature_groups =
+        feature_group::service::get_feature_group_versions_by_name(feature_store_id, name).await?;
+    feature_groups.sort_by_key(|feature_group| feature_group.version);
+    Ok(feature_groups)
+}
+
+/// Feature groups of the feature store sorted by name and version,
+/// keeping only the latest version of each feature group if `latest_only`.
+pub async fn get_feature_groups(
+    feature_store_id: i32,
+    latest_only: bool,
+) -> Result<Vec<FeatureGroupDTO>> {
+    let mut feature_groups =
+        feature_group::service::get_feature_group_list(feature_store_id).await?;
+    feature_groups.sort_by(|a, b| a.name.cmp(&b.name).then(a.version.cmp(&b.version)));
+    if latest_only {
+        // Sorted by version, so the last one of each name is the latest
+        feature_groups.reverse();
+        feature_groups.dedup_by(|a, b| a.name == b.name);
+        feature_groups.reverse();
+    }
+    Ok(feature_groups)
+}
+
 pub async fn create_feature_group(
     feature_store_id: i32,
     new_feature_group_payload: NewFeatureGroupPayload,
@@ -30,62 +69,120 @@ pub async fn create_feature_group(
     feature_group::service::create_feature_group(feature_store_id, &new_feature_group_payload).await
 }
 
+/// Payload registering `feature_group` with the features `feature_names` of types `feature_types`.
 fn build_new_feature_group_payload(
-    name: &str,
-    version: i32,
-    description: Option<&str>,
-    primary_key: Vec<&str>,
-    event_time: Option<&str>,
-    online_enabled: bool,
+    feature_group: &FeatureGroup,
     feature_names: &[String],
     feature_types: &[String],
 ) -> Result<NewFeatureGroupPayload> {
     let features = feature::build_feature_payloads_from_schema_and_feature_group_options(
         feature_names,
         feature_types,
-        primary_key,
+        feature_group.primary_keys(),
+        feature_group.partition_keys().as_slice(),
+        feature_group.hudi_precombine_key(),
     )?;
 
     Ok(NewFeatureGroupPayload::new(
-        name,
-        version,
-        description,
+        feature_group.name(),
+        feature_group.version(),
+        feature_group.description(),
         features,
-        event_time,
-        online_enabled,
+        feature_group.event_time(),
+        feature_group.is_online_enabled(),
     ))
 }
 
-#[tracing::instrument]
+/// Register the feature group `feature_group` created with
+/// [`FeatureStore::feature_group_builder`][crate::feature_store::FeatureStore::feature_group_builder],
+/// its partition and precombine keys must be features of the schema.
+#[tracing::instrument(skip(feature_group), fields(name = feature_group.name(), version = feature_group.version()))]
 pub async fn save_feature_group_metadata(
-    feature_store_id: i32,
-    name: &str,
-    version: i32,
-    description: Option<&str>,
-    primary_key: Vec<&str>,
-    event_time: Option<&str>,
-    online_enabled: bool,
+    feature_group: &FeatureGroup,
     feature_names: &[String],
     feature_types: &[String],
 ) -> Result<FeatureGroupDTO> {
-    let new_feature_group_payload = build_new_feature_group_payload(
-        name,
-        version,
-        description,
-        primary_key,
-        event_time,
-        online_enabled,
-        feature_names,
-        feature_types,
-    )?;
+    if feature_group.hudi_precombine_key().is_some() && feature_group.time_travel_format() != "HUDI"
+    {
+        return Err(HopsworksError::Validation(format!(
+            "Feature Group {} sets a Hudi precombine key but its time travel format is {}.",
+            feature_group.name(),
+            feature_group.time_travel_format()
+        )));
+    }
+    let new_feature_group_payload =
+        build_new_feature_group_payload(feature_group, feature_names, feature_types)?
+            .with_time_travel_format(feature_group.time_travel_format());
 
-    let feature_group_dto =
-        feature_group::service::create_feature_group(feature_store_id, &new_feature_group_payload)
-            .await?;
+    let feature_group_dto = feature_group::service::create_feature_group(
+        feature_group.feature_store_id(),
+        &new_feature_group_payload,
+    )
+    .await?;
 
     Ok(feature_group_dto)
 }
 
+/// Register the external feature group `feature_group` created with
+/// [`FeatureStore::external_feature_group_builder`][crate::feature_store::FeatureStore::external_feature_group_builder].
+pub async fn save_external_feature_group_metadata(
+    feature_group: &FeatureGroup,
+    feature_names: &[String],
+    feature_types: &[String],
+) -> Result<FeatureGroupDTO> {
+    let storage_connector = feature_group.storage_connector().ok_or_else(|| {
+        HopsworksError::Validation(format!(
+            "External Feature Group {} has no storage connector.",
+            feature_group.name()
+        ))
+    })?;
+    let new_feature_group_payload =
+        build_new_feature_group_payload(feature_group, feature_names, feature_types)?
+            .with_external_source(
+                FeatureStoreStorageConnectorDTO::from(storage_connector),
+                feature_group.query(),
+                feature_group.path(),
+                feature_group.data_format(),
+            );
+
+    feature_group::service::create_feature_group(
+        feature_group.feature_store_id(),
+        &new_feature_group_payload,
+    )
+    .await
+}
+
 pub async fn delete_feature_group(feature_store_id: i32, feature_group_id: i32) -> Result<()> {
     feature_group::service::delete_feature_group(feature_store_id, feature_group_id).await
 }
+
+/// Append `new_features` to the schema of the registered feature group `feature_group_dto`,
+/// returns the feature group with its updated features.
+pub async fn append_features(
+    mut feature_group_dto: FeatureGroupDTO,
+    new_features: Vec<FeatureDTO>,
+) -> Result<FeatureGroupDTO> {
+    feature_group_dto.features.extend(new_features);
+    feature_group::service::update_feature_group(
+        feature_group_dto.featurestore_id,
+        feature_group_dto.id,
+        &feature_group_dto,
+        &[("appendFeatures", "true")],
+    )
+    .await
+}
+
+/// Update the metadata of the registered feature group `feature_group_dto`, `query_param`
+/// selects the updated part, e.g. `updateMetadata`, `updateStatsConfig` or `enableOnline`.
+pub async fn update_feature_group_metadata(
+    feature_group_dto: FeatureGroupDTO,
+    query_param: &str,
+) -> Result<FeatureGroupDTO> {
+    feature_group::service::update_feature_group(
+        feature_group_dto.featurestore_id,
+        feature_group_dto.id,
+        &feature_group_dto,
+        &[(query_param, "true")],
+    )
+    .await
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use color_eyre::Result;
+use crate::error::Result;
 
 use crate::cluster_api::feature_store::{
     feature::{FeatureDTO, TrainingDatasetFeatureDTO},
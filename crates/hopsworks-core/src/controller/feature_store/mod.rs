@@ -1,12 +1,14 @@
+pub mod expectation_suite;
 pub mod feature;
 pub mod feature_group;
 pub mod feature_view;
 pub mod query;
+pub mod statistics;
 pub mod storage_connector;
 pub mod training_dataset;
 pub mod transformation_function;
 
-use color_eyre::Result;
+use crate::error::Result;
 
 use crate::cluster_api::feature_store;
 use crate::feature_store::FeatureStore;
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 use tracing::debug;
 
 use crate::controller::feature_store::storage_connector;
@@ -0,0 +1,29 @@
+use crate::error::Result;
+
+use crate::cluster_api::feature_store::statistics::{service, StatisticsDTO};
+use crate::feature_store::feature_group::statistics::Statistics;
+
+pub async fn get_last_statistics(
+    feature_store_id: i32,
+    feature_group_id: i32,
+) -> Result<Option<Statistics>> {
+    service::get_last_statistics(feature_store_id, feature_group_id)
+        .await?
+        .map(Statistics::try_from)
+        .transpose()
+}
+
+pub async fn save_statistics(
+    feature_store_id: i32,
+    feature_group_id: i32,
+    statistics: &Statistics,
+) -> Result<Statistics> {
+    Statistics::try_from(
+        service::save_statistics(
+            feature_store_id,
+            feature_group_id,
+            &StatisticsDTO::from(statistics),
+        )
+        .await?,
+    )
+}
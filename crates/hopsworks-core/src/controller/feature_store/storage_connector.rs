@@ -1,9 +1,10 @@
-use color_eyre::Result;
+use crate::error::Result;
 
 use crate::cluster_api::feature_store::storage_connector::service;
 use crate::feature_store::storage_connector::{
-    FeatureStoreKafkaConnector, FeatureStoreOnlineConnector,
+    FeatureStoreKafkaConnector, FeatureStoreOnlineConnector, StorageConnector,
 };
+use crate::pagination::{ListOptions, PageDTO};
 
 pub async fn get_feature_store_kafka_connector(
     feature_store_id: i32,
@@ -21,3 +22,36 @@ pub async fn get_feature_store_online_connector(
         service::get_feature_store_online_connector(feature_store_id).await?,
     ))
 }
+
+pub async fn get_feature_store_storage_connectors(
+    feature_store_id: i32,
+    list_options: &ListOptions,
+) -> Result<Vec<StorageConnector>> {
+    Ok(
+        get_feature_store_storage_connector_page(feature_store_id, list_options)
+            .await?
+            .into_items(),
+    )
+}
+
+pub(crate) async fn get_feature_store_storage_connector_page(
+    feature_store_id: i32,
+    list_options: &ListOptions,
+) -> Result<PageDTO<StorageConnector>> {
+    Ok(
+        service::get_list_feature_store_storage_connectors(feature_store_id, list_options)
+            .await?
+            .map(StorageConnector::from),
+    )
+}
+
+pub async fn get_feature_store_storage_connector_by_name(
+    feature_store_id: i32,
+    name: &str,
+) -> Result<Option<StorageConnector>> {
+    Ok(
+        service::get_feature_store_storage_connector_by_name(feature_store_id, name)
+            .await?
+            .map(StorageConnector::from),
+    )
+}
@@ -1,6 +1,6 @@
 use crate::cluster_api::feature_store::transformation_function;
+use crate::error::Result;
 use crate::feature_store::feature_view::transformation_function::TransformationFunction;
-use color_eyre::Result;
 
 pub async fn get_transformation_function_by_name_and_version(
     feature_store_id: i32,
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 use tracing::debug;
 use std::path::Path;
 use tokio::fs::File;
@@ -6,6 +6,15 @@ use tokio::io::AsyncWriteExt;
 
 use crate::cluster_api::platform::credentials::service::get_hopsworks_credentials_for_project;
 
+const CREDENTIALS_FILE_NAMES: [&str; 6] = [
+    "ca_chain.pem",
+    "client_key.pem",
+    "client_cert.pem",
+    "key_store.jks",
+    "trust_store.jks",
+    "material_passwd",
+];
+
 pub async fn write_locally_project_credentials_on_login(cert_dir: &str) -> Result<String> {
     let credentials_dto = get_hopsworks_credentials_for_project().await?;
 
@@ -29,6 +38,29 @@ pub async fn write_locally_project_credentials_on_login(cert_dir: &str) -> Resul
     Ok(credentials_dto.password.clone())
 }
 
+/// Delete the files written by [`write_locally_project_credentials_on_login`],
+/// and the `cert_dir` itself if nothing else is left in it.
+pub async fn delete_locally_project_credentials(cert_dir: &str) -> Result<()> {
+    for cert_file_name in CREDENTIALS_FILE_NAMES {
+        let cert_file_path = Path::new(cert_dir).join(cert_file_name);
+        if cert_file_path.exists() {
+            debug!("Deleting {:?}", cert_file_path.as_path());
+            tokio::fs::remove_file(cert_file_path).await?;
+        }
+    }
+
+    let is_empty = match tokio::fs::read_dir(cert_dir).await {
+        Ok(mut entries) => entries.next_entry().await?.is_none(),
+        Err(_) => false,
+    };
+    if is_empty {
+        debug!("Deleting cert dir: {:?}", cert_dir);
+        tokio::fs::remove_dir(cert_dir).await?;
+    }
+
+    Ok(())
+}
+
 async fn write_cert_to_file(cert_file_name: &str, cert_dir: &str, cert_text: &str) -> Result<()> {
     let cert_file_path = Path::new(cert_dir).join(cert_file_name);
     if !cert_file_path.exists() {
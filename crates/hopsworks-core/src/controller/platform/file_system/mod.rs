@@ -1,9 +1,10 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 use std::sync::Arc;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::cluster_api::platform::file_system::{service, FlowBaseParams};
+use crate::get_hopsworks_client;
 pub mod util;
 
 const FLOW_PERMANENT_ERRORS_STATUS: [reqwest::StatusCode; 5] = [
@@ -37,10 +38,10 @@ pub async fn move_file_or_dir(src_path: &str, dst_path: &str, overwrite: bool) -
     if overwrite && dst_exists {
         remove_file_or_dir(dst_path).await?;
     } else if !overwrite && dst_exists {
-        return Err(color_eyre::eyre::eyre!(
+        return Err(HopsworksError::Validation(format!(
             "Destination path {} already exists, set overwrite=Some(true) to overwrite it",
             dst_path,
-        ));
+        )));
     }
 
     service::move_file_or_dir(src_path, dst_path).await?;
@@ -54,10 +55,10 @@ pub async fn copy(src_path: &str, dst_path: &str, overwrite: bool) -> Result<()>
     if overwrite && dst_exists {
         remove_file_or_dir(dst_path).await?;
     } else if !overwrite && dst_exists {
-        return Err(color_eyre::eyre::eyre!(
+        return Err(HopsworksError::Validation(format!(
             "Destination path {} already exists, set overwrite=Some(true) to overwrite it",
             dst_path,
-        ));
+        )));
     }
 
     service::copy(src_path, dst_path).await?;
@@ -89,10 +90,13 @@ pub async fn upload(
     
     pbar.set_style(
             ProgressStyle::default_bar()
-                .template("{desc}: {percentage:.3}%|{bar}| {bytes}/{total_bytes} elapsed<{elapsed} remaining<{eta}")?
+                .template("{desc}: {percentage:.3}%|{bar}| {bytes}/{total_bytes} elapsed<{elapsed} remaining<{eta}")
+                .map_err(HopsworksError::other)?
                 .progress_chars("#>-"),
         );
 
+    // Spawned tasks do not inherit the scoped client, pass it explicitly
+    let client = get_hopsworks_client().await?;
     let mut handles = vec![];
     loop {
         chunk_number += 1;
@@ -104,17 +108,19 @@ pub async fn upload(
         let base_params = base_params.clone();
         let upload_path = upload_path.clone();
         let pbar = pbar.clone();
+        let client = client.clone();
 
         let handle = tokio::spawn(async move {
             let chunk_len = chunk.len() as u64;
-            let status = upload_chunk(
-                &upload_path,
-                base_params,
-                chunk,
-                max_chunk_retries,
-                chunk_retry_interval,
-            )
-            .await;
+            let status = client
+                .scope(upload_chunk(
+                    &upload_path,
+                    base_params,
+                    chunk,
+                    max_chunk_retries,
+                    chunk_retry_interval,
+                ))
+                .await;
             match status {
                 Ok(_) => {
                     pbar.inc(chunk_len);
@@ -156,19 +162,14 @@ async fn upload_chunk(
         {
             Ok(resp) => {
                 if FLOW_PERMANENT_ERRORS_STATUS.contains(&resp.status()) {
-                    return Err(color_eyre::eyre::eyre!(
-                        "Permanent error {}, failed to upload chunk: {}",
-                        &resp.status(),
-                        resp.text().await?
-                    ));
+                    return Err(HopsworksError::from_response("upload_chunk", resp).await);
                 }
                 return Ok(resp.status());
             }
             Err(_) => {
                 if retries == max_chunk_retries {
-                    return Err(color_eyre::eyre::eyre!(
-                        "Failed to upload chunk after {} retries",
-                        retries
+                    return Err(HopsworksError::Transport(
+                        format!("Failed to upload chunk after {} retries", retries).into(),
                     ));
                 } else {
                     tokio::time::sleep(tokio::time::Duration::from_millis(chunk_retry_interval))
@@ -197,7 +198,8 @@ pub async fn download(path: &str, local_path: Option<&str>, overwrite: bool) ->
     let pbar = ProgressBar::new(file_size as u64);
     pbar.set_style(
             ProgressStyle::default_bar()
-                .template("{desc}: {percentage:.3}%|{bar}| {bytes}/{total_bytes} elapsed<{elapsed} remaining<{eta}")?
+                .template("{desc}: {percentage:.3}%|{bar}| {bytes}/{total_bytes} elapsed<{elapsed} remaining<{eta}")
+                .map_err(HopsworksError::other)?
                 .progress_chars("#>-"),
         );
 
@@ -205,6 +207,8 @@ pub async fn download(path: &str, local_path: Option<&str>, overwrite: bool) ->
         file.write_all(&chunk).await?;
         pbar.inc(chunk.len() as u64);
     }
+    // tokio files write in the background, flush so the content is on disk once we return
+    file.flush().await?;
 
     pbar.finish();
     Ok(local_path
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -71,7 +71,7 @@ pub(super) async fn download_local_path_or_default(
                 tokio::fs::remove_dir_all(&local_path).await?;
             }
         } else {
-            return Err(color_eyre::eyre::eyre!(format!(
+            return Err(HopsworksError::Validation(format!(
                 "{} already exists, set overwrite=True to overwrite it",
                 local_path.display()
             )));
@@ -105,7 +105,7 @@ pub async fn prepare_upload(
         if overwrite {
             super::remove_file_or_dir(&destination_path).await?;
         } else {
-            return Err(color_eyre::eyre::eyre!(format!(
+            return Err(HopsworksError::Validation(format!(
                 "{} already exists, set overwrite=True to overwrite it",
                 destination_path
             )));
@@ -1,6 +1,7 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 
 use crate::cluster_api::platform::job::service;
+use crate::pagination::{ListOptions, PageDTO};
 use crate::platform::job::Job;
 
 pub async fn get_job_by_name(job_name: &str) -> Result<Job> {
@@ -10,16 +11,16 @@ pub async fn get_job_by_name(job_name: &str) -> Result<Job> {
 async fn validate_job_name(job_name: &str, should_exist: bool) -> Result<()> {
     // Most validation is done by Hopsworks, but we can do some basic checks here
     if job_name.is_empty() {
-        return Err(color_eyre::Report::msg("job_name is empty"));
+        return Err(HopsworksError::Validation("job_name is empty".to_string()));
     }
     let exists = service::exists(job_name).await?;
     if should_exist && !exists {
-        return Err(color_eyre::Report::msg(format!(
+        return Err(HopsworksError::NotFound(format!(
             "job with name {} does not exist",
             job_name
         )));
     } else if !should_exist && exists {
-        return Err(color_eyre::Report::msg(format!(
+        return Err(HopsworksError::Validation(format!(
             "job with name {} already exists",
             job_name
         )));
@@ -29,9 +30,9 @@ async fn validate_job_name(job_name: &str, should_exist: bool) -> Result<()> {
 }
 
 fn validate_job_configuration(job_config: &serde_json::Value) -> Result<()> {
-    job_config
-        .get("job_type")
-        .ok_or_else(|| color_eyre::Report::msg("job_type is missing from job configuration"))?;
+    job_config.get("job_type").ok_or_else(|| {
+        HopsworksError::Validation("job_type is missing from job configuration".to_string())
+    })?;
 
     Ok(())
 }
@@ -57,10 +58,10 @@ pub async fn get_job_configuration(job_type: &str) -> Result<serde_json::Value>
     service::get_job_configuration(job_type).await
 }
 
-pub async fn get_job_list() -> Result<Vec<Job>> {
-    Ok(service::get_job_list()
-        .await?
-        .iter()
-        .map(|job_dto| Job::from(job_dto.to_owned()))
-        .collect())
+pub async fn get_job_list(list_options: &ListOptions) -> Result<Vec<Job>> {
+    Ok(get_job_page(list_options).await?.into_items())
+}
+
+pub(crate) async fn get_job_page(list_options: &ListOptions) -> Result<PageDTO<Job>> {
+    Ok(service::get_job_list(list_options).await?.map(Job::from))
 }
@@ -1,7 +1,8 @@
-use color_eyre::Result;
+use crate::error::Result;
 use tracing::debug;
 
 use crate::cluster_api::platform::job_execution::{self, JobExecutionDTO};
+use crate::pagination::{ListOptions, PageDTO};
 
 use super::file_system::download;
 
@@ -55,8 +56,20 @@ pub async fn get_job_execution_by_id(
     job_execution::service::get_job_execution_by_id(job_name, job_execution_id).await
 }
 
-pub async fn get_job_executions(job_name: &str) -> Result<Vec<JobExecutionDTO>> {
-    job_execution::service::get_job_executions(job_name).await
+pub async fn get_job_executions(
+    job_name: &str,
+    list_options: &ListOptions,
+) -> Result<Vec<JobExecutionDTO>> {
+    Ok(get_job_execution_page(job_name, list_options)
+        .await?
+        .into_items())
+}
+
+pub(crate) async fn get_job_execution_page(
+    job_name: &str,
+    list_options: &ListOptions,
+) -> Result<PageDTO<JobExecutionDTO>> {
+    job_execution::service::get_job_executions(job_name, list_options).await
 }
 
 pub async fn delete_job_execution(job_name: &str, job_execution_id: i32) -> Result<()> {
@@ -64,13 +77,15 @@ pub async fn delete_job_execution(job_name: &str, job_execution_id: i32) -> Resu
 }
 
 pub async fn await_termination(job_name: &str, job_execution_id: i32) -> Result<()> {
-
     while {
-        let state = get_job_execution_by_id(job_name, job_execution_id).await?.state.to_lowercase();
+        let state = get_job_execution_by_id(job_name, job_execution_id)
+            .await?
+            .state
+            .to_lowercase();
         debug!("Job execution state: {}", state);
         state != "finished" && state != "failed" && state != "killed"
     } {
         tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
     }
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 
 use crate::cluster_api::platform::kafka;
 use crate::platform::kafka::KafkaSubject;
@@ -23,5 +23,14 @@ pub async fn get_kafka_topic_subject(
     subject_name: &str,
     opt_version: Option<&str>,
 ) -> Result<KafkaSubject> {
-    Ok(KafkaSubject::from(kafka::service::get_kafka_topic_subject(subject_name, opt_version).await?))
+    Ok(KafkaSubject::from(
+        kafka::service::get_kafka_topic_subject(subject_name, opt_version).await?,
+    ))
+}
+
+/// Avro schema registered under the given id, e.g. read from the `subjectId` header of a message.
+pub async fn get_kafka_schema_by_id(schema_id: i32) -> Result<String> {
+    Ok(kafka::service::get_kafka_schema_by_id(schema_id)
+        .await?
+        .schema)
 }
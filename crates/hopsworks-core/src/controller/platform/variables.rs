@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 
 use crate::cluster_api::platform::variables::service;
 
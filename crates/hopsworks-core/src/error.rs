@@ -0,0 +1,185 @@
+//! Error type shared by every layer of the SDK.
+//!
+//! Errors returned by the Hopsworks REST API are parsed into [`HopsworksError::Http`],
+//! [`HopsworksError::Auth`] or [`HopsworksError::NotFound`] depending on the status code,
+//! so callers can match on the failure instead of inspecting the message.
+//! Crates built on top of hopsworks-core (kafka, offline-store, online-store, opensearch)
+//! convert their own errors into this type.
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+pub type Result<T, E = HopsworksError> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HopsworksError {
+    /// The Hopsworks REST API answered with a non-success status code.
+    #[error("{message} (status: {status}, error code: {error_code:?})")]
+    Http {
+        status: StatusCode,
+        error_code: Option<i64>,
+        message: String,
+    },
+    /// Missing or rejected credentials, e.g. no API key or a 401/403 response.
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+    /// The requested entity does not exist, e.g. a 404 response.
+    #[error("Not found: {0}")]
+    NotFound(String),
+    /// Invalid input detected before or after talking to the cluster.
+    #[error("Validation failed: {0}")]
+    Validation(String),
+    /// Network, TLS or broker level failure.
+    #[error("Transport error: {0}")]
+    Transport(#[source] BoxError),
+    /// Failure to encode or decode a payload.
+    #[error("Serialization error: {0}")]
+    Serialization(#[source] BoxError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Failure raised by a third-party engine, e.g. Polars or Arrow.
+    #[error(transparent)]
+    Other(BoxError),
+}
+
+/// Error body returned by the Hopsworks REST API.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RestApiErrorDTO {
+    error_code: Option<i64>,
+    error_msg: Option<String>,
+    usr_msg: Option<String>,
+}
+
+impl HopsworksError {
+    /// Consume a non-success response and build the matching error.
+    /// `context` is the name of the failed operation and prefixes the message.
+    pub async fn from_response(context: &str, response: reqwest::Response) -> Self {
+        let status = response.status();
+        let body = match response.text_with_charset("utf-8").await {
+            Ok(body) => body,
+            Err(e) => return e.into(),
+        };
+        let (error_code, server_message) = match serde_json::from_str::<RestApiErrorDTO>(&body) {
+            Ok(dto) => (
+                dto.error_code,
+                dto.usr_msg
+                    .filter(|msg| !msg.is_empty())
+                    .or(dto.error_msg)
+                    .unwrap_or(body),
+            ),
+            Err(_) => (None, body),
+        };
+        let message = format!("{context} failed: {server_message}");
+
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => HopsworksError::Auth(message),
+            StatusCode::NOT_FOUND => HopsworksError::NotFound(message),
+            _ => HopsworksError::Http {
+                status,
+                error_code,
+                message,
+            },
+        }
+    }
+
+    pub fn transport(err: impl Into<BoxError>) -> Self {
+        HopsworksError::Transport(err.into())
+    }
+
+    pub fn serialization(err: impl Into<BoxError>) -> Self {
+        HopsworksError::Serialization(err.into())
+    }
+
+    pub fn other(err: impl Into<BoxError>) -> Self {
+        HopsworksError::Other(err.into())
+    }
+
+    /// HTTP status code of the response that caused the error, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            HopsworksError::Http { status, .. } => Some(*status),
+            HopsworksError::Transport(e) => e
+                .downcast_ref::<reqwest::Error>()
+                .and_then(reqwest::Error::status),
+            _ => None,
+        }
+    }
+
+    /// Hopsworks REST API error code, see the Hopsworks documentation for the full list.
+    pub fn error_code(&self) -> Option<i64> {
+        match self {
+            HopsworksError::Http { error_code, .. } => *error_code,
+            _ => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, HopsworksError::NotFound(_))
+    }
+}
+
+impl From<reqwest::Error> for HopsworksError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_decode() {
+            HopsworksError::Serialization(err.into())
+        } else {
+            HopsworksError::Transport(err.into())
+        }
+    }
+}
+
+impl From<serde_json::Error> for HopsworksError {
+    fn from(err: serde_json::Error) -> Self {
+        HopsworksError::Serialization(err.into())
+    }
+}
+
+impl From<toml::de::Error> for HopsworksError {
+    fn from(err: toml::de::Error) -> Self {
+        HopsworksError::Serialization(err.into())
+    }
+}
+
+impl From<reqwest::header::InvalidHeaderValue> for HopsworksError {
+    fn from(err: reqwest::header::InvalidHeaderValue) -> Self {
+        HopsworksError::Validation(err.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for HopsworksError {
+    fn from(err: tokio::task::JoinError) -> Self {
+        HopsworksError::Other(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rest_api_error_body() {
+        let dto: RestApiErrorDTO = serde_json::from_str(
+            r#"{"errorCode": 270009, "errorMsg": "Featuregroup wasn't found.", "usrMsg": ""}"#,
+        )
+        .unwrap();
+
+        assert_eq!(dto.error_code, Some(270009));
+        assert_eq!(dto.error_msg.as_deref(), Some("Featuregroup wasn't found."));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let err = HopsworksError::Http {
+            status: StatusCode::BAD_REQUEST,
+            error_code: Some(120001),
+            message: "create_feature_group failed: bad request".to_string(),
+        };
+
+        assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(err.error_code(), Some(120001));
+        assert!(!err.is_not_found());
+        assert!(HopsworksError::NotFound("feature group".to_string()).is_not_found());
+    }
+}
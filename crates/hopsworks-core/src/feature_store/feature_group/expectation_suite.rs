@@ -0,0 +1,341 @@
+//! Expectation suites and validation reports of a [`FeatureGroup`][super::FeatureGroup].
+//!
+//! The suite follows the Great Expectations format used by Hopsworks, so suites created by
+//! the Python client can be read here and the other way around.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cluster_api::feature_store::expectation_suite::{
+    ExpectationDTO, ExpectationSuiteDTO, ValidationReportDTO, ValidationResultDTO,
+};
+use crate::error::{HopsworksError, Result};
+
+/// Whether data failing the validation is still inserted in the Feature Group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ValidationIngestionPolicy {
+    /// Insert the data and upload the validation report whatever the result.
+    #[default]
+    Always,
+    /// Reject the data if an expectation fails, the validation report is still uploaded.
+    Strict,
+}
+
+/// Outcome of an insert recorded in a [`ValidationReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IngestionResult {
+    Ingested,
+    Rejected,
+    Unknown,
+}
+
+/// A single expectation, e.g. `expect_column_values_to_be_between` with its kwargs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpectationConfiguration {
+    pub expectation_type: String,
+    pub kwargs: Value,
+    #[serde(default)]
+    pub meta: Value,
+}
+
+impl ExpectationConfiguration {
+    pub fn new(expectation_type: &str, kwargs: Value) -> Self {
+        Self {
+            expectation_type: expectation_type.to_string(),
+            kwargs,
+            meta: Value::Object(Default::default()),
+        }
+    }
+
+    /// Column the expectation applies to, `None` for table expectations.
+    pub fn column(&self) -> Option<&str> {
+        self.kwargs.get("column").and_then(Value::as_str)
+    }
+}
+
+/// Expectations validated against the data inserted in a Feature Group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpectationSuite {
+    id: Option<i32>,
+    name: String,
+    expectations: Vec<ExpectationConfiguration>,
+    meta: Value,
+    run_validation: bool,
+    validation_ingestion_policy: ValidationIngestionPolicy,
+}
+
+impl ExpectationSuite {
+    pub fn new(name: &str) -> Self {
+        Self {
+            id: None,
+            name: name.to_string(),
+            expectations: vec![],
+            meta: Value::Object(Default::default()),
+            run_validation: true,
+            validation_ingestion_policy: ValidationIngestionPolicy::default(),
+        }
+    }
+
+    pub fn with_expectation(mut self, expectation: ExpectationConfiguration) -> Self {
+        self.expectations.push(expectation);
+        self
+    }
+
+    pub fn with_validation_ingestion_policy(mut self, policy: ValidationIngestionPolicy) -> Self {
+        self.validation_ingestion_policy = policy;
+        self
+    }
+
+    /// Disable to keep the suite attached without validating inserted data.
+    pub fn with_run_validation(mut self, run_validation: bool) -> Self {
+        self.run_validation = run_validation;
+        self
+    }
+
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn expectations(&self) -> &[ExpectationConfiguration] {
+        &self.expectations
+    }
+
+    pub fn run_validation(&self) -> bool {
+        self.run_validation
+    }
+
+    pub fn validation_ingestion_policy(&self) -> ValidationIngestionPolicy {
+        self.validation_ingestion_policy
+    }
+}
+
+/// Result of a single expectation of a [`ValidationReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationResult {
+    pub success: bool,
+    pub expectation_config: ExpectationConfiguration,
+    /// Observed values, e.g. `unexpected_count` or `observed_value`.
+    pub result: Value,
+    /// Set if the expectation could not be evaluated.
+    pub exception_info: Value,
+}
+
+/// Result of the validation of a dataframe against an [`ExpectationSuite`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    id: Option<i32>,
+    success: bool,
+    results: Vec<ValidationResult>,
+    statistics: Value,
+    ingestion_result: IngestionResult,
+    validation_time: Option<String>,
+}
+
+impl ValidationReport {
+    /// Report of the `results`, successful if every result is.
+    pub fn new(results: Vec<ValidationResult>) -> Self {
+        let evaluated = results.len();
+        let successful = results.iter().filter(|result| result.success).count();
+        Self {
+            id: None,
+            success: successful == evaluated,
+            results,
+            statistics: serde_json::json!({
+                "evaluated_expectations": evaluated,
+                "successful_expectations": successful,
+                "unsuccessful_expectations": evaluated - successful,
+                "success_percent": if evaluated == 0 {
+                    100.0
+                } else {
+                    100.0 * successful as f64 / evaluated as f64
+                },
+            }),
+            ingestion_result: IngestionResult::Unknown,
+            validation_time: None,
+        }
+    }
+
+    pub fn with_ingestion_result(mut self, ingestion_result: IngestionResult) -> Self {
+        self.ingestion_result = ingestion_result;
+        self
+    }
+
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    pub fn success(&self) -> bool {
+        self.success
+    }
+
+    pub fn results(&self) -> &[ValidationResult] {
+        &self.results
+    }
+
+    /// Results of the failed expectations.
+    pub fn failed_results(&self) -> impl Iterator<Item = &ValidationResult> {
+        self.results.iter().filter(|result| !result.success)
+    }
+
+    pub fn statistics(&self) -> &Value {
+        &self.statistics
+    }
+
+    pub fn ingestion_result(&self) -> IngestionResult {
+        self.ingestion_result
+    }
+
+    pub fn validation_time(&self) -> Option<&str> {
+        self.validation_time.as_deref()
+    }
+}
+
+fn to_json_string(value: &Value) -> String {
+    value.to_string()
+}
+
+fn from_json_string(field: &str, value: &str) -> Result<Value> {
+    if value.is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(value)
+        .map_err(|e| HopsworksError::serialization(format!("Invalid JSON in field {field}: {e}")))
+}
+
+impl From<&ExpectationConfiguration> for ExpectationDTO {
+    fn from(expectation: &ExpectationConfiguration) -> Self {
+        ExpectationDTO {
+            id: None,
+            expectation_type: expectation.expectation_type.clone(),
+            kwargs: to_json_string(&expectation.kwargs),
+            meta: to_json_string(&expectation.meta),
+        }
+    }
+}
+
+impl TryFrom<ExpectationDTO> for ExpectationConfiguration {
+    type Error = HopsworksError;
+
+    fn try_from(expectation_dto: ExpectationDTO) -> Result<Self> {
+        Ok(ExpectationConfiguration {
+            expectation_type: expectation_dto.expectation_type,
+            kwargs: from_json_string("kwargs", &expectation_dto.kwargs)?,
+            meta: from_json_string("meta", &expectation_dto.meta)?,
+        })
+    }
+}
+
+impl From<&ExpectationSuite> for ExpectationSuiteDTO {
+    fn from(expectation_suite: &ExpectationSuite) -> Self {
+        ExpectationSuiteDTO {
+            id: expectation_suite.id,
+            expectation_suite_name: expectation_suite.name.clone(),
+            expectations: expectation_suite
+                .expectations
+                .iter()
+                .map(ExpectationDTO::from)
+                .collect(),
+            meta: to_json_string(&expectation_suite.meta),
+            run_validation: expectation_suite.run_validation,
+            validation_ingestion_policy: match expectation_suite.validation_ingestion_policy {
+                ValidationIngestionPolicy::Always => "ALWAYS".to_string(),
+                ValidationIngestionPolicy::Strict => "STRICT".to_string(),
+            },
+        }
+    }
+}
+
+impl TryFrom<ExpectationSuiteDTO> for ExpectationSuite {
+    type Error = HopsworksError;
+
+    fn try_from(expectation_suite_dto: ExpectationSuiteDTO) -> Result<Self> {
+        Ok(ExpectationSuite {
+            id: expectation_suite_dto.id,
+            name: expectation_suite_dto.expectation_suite_name,
+            expectations: expectation_suite_dto
+                .expectations
+                .into_iter()
+                .map(ExpectationConfiguration::try_from)
+                .collect::<Result<_>>()?,
+            meta: from_json_string("meta", &expectation_suite_dto.meta)?,
+            run_validation: expectation_suite_dto.run_validation,
+            validation_ingestion_policy: match expectation_suite_dto
+                .validation_ingestion_policy
+                .to_uppercase()
+                .as_str()
+            {
+                "STRICT" => ValidationIngestionPolicy::Strict,
+                _ => ValidationIngestionPolicy::Always,
+            },
+        })
+    }
+}
+
+impl From<&ValidationReport> for ValidationReportDTO {
+    fn from(validation_report: &ValidationReport) -> Self {
+        ValidationReportDTO {
+            id: validation_report.id,
+            success: validation_report.success,
+            results: validation_report
+                .results
+                .iter()
+                .map(|result| ValidationResultDTO {
+                    success: result.success,
+                    expectation_config: serde_json::to_string(&result.expectation_config)
+                        .unwrap_or_default(),
+                    result: to_json_string(&result.result),
+                    exception_info: to_json_string(&result.exception_info),
+                    meta: "{}".to_string(),
+                })
+                .collect(),
+            statistics: to_json_string(&validation_report.statistics),
+            meta: "{}".to_string(),
+            evaluation_parameters: "{}".to_string(),
+            ingestion_result: match validation_report.ingestion_result {
+                IngestionResult::Ingested => "INGESTED".to_string(),
+                IngestionResult::Rejected => "REJECTED".to_string(),
+                IngestionResult::Unknown => "UNKNOWN".to_string(),
+            },
+            validation_time: validation_report.validation_time.clone(),
+        }
+    }
+}
+
+impl TryFrom<ValidationReportDTO> for ValidationReport {
+    type Error = HopsworksError;
+
+    fn try_from(validation_report_dto: ValidationReportDTO) -> Result<Self> {
+        Ok(ValidationReport {
+            id: validation_report_dto.id,
+            success: validation_report_dto.success,
+            results: validation_report_dto
+                .results
+                .into_iter()
+                .map(|result| {
+                    Ok(ValidationResult {
+                        success: result.success,
+                        expectation_config: serde_json::from_str(&result.expectation_config)?,
+                        result: from_json_string("result", &result.result)?,
+                        exception_info: from_json_string("exceptionInfo", &result.exception_info)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            statistics: from_json_string("statistics", &validation_report_dto.statistics)?,
+            ingestion_result: match validation_report_dto
+                .ingestion_result
+                .to_uppercase()
+                .as_str()
+            {
+                "INGESTED" => IngestionResult::Ingested,
+                "REJECTED" => IngestionResult::Rejected,
+                _ => IngestionResult::Unknown,
+            },
+            validation_time: validation_report_dto.validation_time,
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::cluster_api::feature_store::feature::FeatureDTO;
@@ -36,6 +36,19 @@ impl From<FeatureDTO> for Feature {
     }
 }
 
+impl Feature {
+    /// Create a feature to append to the schema of a [`FeatureGroup`][super::FeatureGroup],
+    /// `data_type` is the Hopsworks (Hive) type of the feature, e.g. `bigint` or `string`.
+    pub fn new(name: &str, data_type: &str) -> Self {
+        Feature::new_from_dto(FeatureDTO::new(name.to_string(), data_type.to_string()))
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+}
+
 impl Feature {
     pub fn feature_group_id(&self) -> Option<i32> {
         self.feature_group_id
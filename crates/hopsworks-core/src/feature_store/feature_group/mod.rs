@@ -7,24 +7,61 @@
 //! Feature groups are central to Feature Engineering pipelines. A common use case is to schedule a job that
 //! pulls data from an external data source, performs some transformations on it,
 //! and then inserts the data via the Feature Group.
+pub mod expectation_suite;
 pub mod feature;
+pub mod statistics;
 pub mod statistics_config;
 
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 use typed_builder::TypedBuilder;
 
-use crate::cluster_api::feature_store::feature_group::FeatureGroupDTO;
+use crate::cluster_api::feature_store::{feature::FeatureDTO, feature_group::FeatureGroupDTO};
 use crate::feature_store::query::Query;
 use crate::util;
 
-use self::{feature::Feature, statistics_config::StatisticsConfig};
+use self::{
+    expectation_suite::{ExpectationSuite, ValidationReport},
+    feature::Feature,
+    statistics::Statistics,
+    statistics_config::StatisticsConfig,
+};
 
 use crate::platform::user::User;
 
 use super::embedding::embedding_index::EmbeddingIndex;
-use crate::controller::feature_store::feature_group;
+use super::storage_connector::StorageConnector;
+use crate::controller::feature_store::{
+    expectation_suite as expectation_suite_controller, feature_group,
+    statistics as statistics_controller,
+};
+use crate::{get_scoped_hopsworks_client, with_hopsworks_client, HopsworksClient};
+
+pub(crate) const EXTERNAL_FEATURE_GROUP_TYPE: &str = "onDemandFeaturegroupDTO";
+
+/// Table format of the offline storage of a [`FeatureGroup`], set when it is created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TimeTravelFormat {
+    /// Hudi table, supporting upserts on the primary key and time travel queries.
+    #[default]
+    Hudi,
+    /// Delta Lake table, supporting upserts on the primary key and time travel queries.
+    Delta,
+    /// Append-only table without time travel.
+    None,
+}
+
+impl std::fmt::Display for TimeTravelFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeTravelFormat::Hudi => write!(f, "HUDI"),
+            TimeTravelFormat::Delta => write!(f, "DELTA"),
+            TimeTravelFormat::None => write!(f, "NONE"),
+        }
+    }
+}
 
 /// Feature Group are metadata objects describing a table in the Feature Store.
 /// They are the primary interface through which one can ingest Feature data to the Feature Store.
@@ -87,15 +124,103 @@ pub struct FeatureGroup {
     #[builder(setter(skip), default = Vec::new())]
     features: Vec<Feature>,
     online_enabled: bool,
-    #[builder(setter(skip), default = String::from("NONE"))]
+    #[builder(
+        default = TimeTravelFormat::default().to_string(),
+        setter(transform = |time_travel_format: TimeTravelFormat| time_travel_format.to_string())
+    )]
     time_travel_format: String,
     #[builder(default = None, setter(skip))]
     online_topic_name: Option<String>,
     primary_key: Vec<String>,
+    /// Features partitioning the offline table.
+    #[builder(default = Vec::new())]
+    #[serde(default)]
+    partition_key: Vec<String>,
+    /// Feature deciding which row is kept when rows with the same primary key are upserted in a Hudi table.
+    #[builder(default = None)]
+    hudi_precombine_key: Option<String>,
     #[builder(default = None)]
     event_time: Option<String>,
     #[builder(default = None)]
     embedding_index: Option<EmbeddingIndex>,
+    #[builder(setter(skip), default = None)]
+    storage_connector: Option<StorageConnector>,
+    #[builder(setter(skip), default = None)]
+    query: Option<String>,
+    #[builder(setter(skip), default = None)]
+    path: Option<String>,
+    #[builder(setter(skip), default = None)]
+    data_format: Option<String>,
+    #[builder(default = None)]
+    #[serde(skip)]
+    client: Option<HopsworksClient>,
+}
+
+/// Definition of an external (on-demand) [`FeatureGroup`] reading its data from a storage connector,
+/// built with [`FeatureStore::external_feature_group_builder`](crate::feature_store::FeatureStore::external_feature_group_builder).
+/// Building it checks that the data source is complete and returns the [`FeatureGroup`].
+#[derive(Debug, Clone, TypedBuilder)]
+#[builder(
+    builder_method(vis = "pub(super)"),
+    builder_type(name = ExternalFeatureGroupBuilder),
+    build_method(into = Result<FeatureGroup>)
+)]
+pub struct ExternalFeatureGroupDefinition {
+    featurestore_id: i32,
+    featurestore_name: String,
+    client: Option<HopsworksClient>,
+    storage_connector: StorageConnector,
+    #[builder(setter(into))]
+    name: String,
+    version: i32,
+    #[builder(default, setter(strip_option, into))]
+    description: Option<String>,
+    #[builder(default = Vec::new())]
+    primary_key: Vec<String>,
+    #[builder(default, setter(strip_option, into))]
+    event_time: Option<String>,
+    /// SQL query run on the storage connector, e.g. a JDBC connector.
+    #[builder(default, setter(strip_option, into))]
+    query: Option<String>,
+    /// Path of the files relative to the storage connector, e.g. an S3 or HopsFS connector.
+    #[builder(default, setter(strip_option, into))]
+    path: Option<String>,
+    /// Format of the files at `path`, e.g. `PARQUET` or `CSV`.
+    #[builder(default, setter(strip_option, into))]
+    data_format: Option<String>,
+}
+
+impl From<ExternalFeatureGroupDefinition> for Result<FeatureGroup> {
+    fn from(definition: ExternalFeatureGroupDefinition) -> Self {
+        if definition.query.is_none() && definition.path.is_none() {
+            return Err(HopsworksError::Validation(
+                "External Feature Group requires a query or a path to read its data.".to_string(),
+            ));
+        }
+        if definition.path.is_some() && definition.data_format.is_none() {
+            return Err(HopsworksError::Validation(
+                "External Feature Group reading from a path requires a data format.".to_string(),
+            ));
+        }
+        let feature_group = FeatureGroup::builder()
+            .featurestore_id(definition.featurestore_id)
+            .featurestore_name(definition.featurestore_name)
+            .name(definition.name)
+            .version(definition.version)
+            .description(definition.description)
+            .primary_key(definition.primary_key)
+            .online_enabled(false)
+            .event_time(definition.event_time)
+            .client(definition.client)
+            .build();
+
+        Ok(feature_group.with_external_source(
+            definition.storage_connector,
+            definition.query.as_deref(),
+            definition.path.as_deref(),
+            definition.data_format.as_deref(),
+        ))
+    }
 }
 
 impl From<FeatureGroupDTO> for FeatureGroup {
@@ -134,8 +259,26 @@ impl From<FeatureGroupDTO> for FeatureGroup {
                     }
                 })
                 .collect(),
+            partition_key: feature_group_dto
+                .features
+                .iter()
+                .filter(|f| f.partition)
+                .map(|f| f.name.clone())
+                .collect(),
+            hudi_precombine_key: feature_group_dto
+                .features
+                .iter()
+                .find(|f| f.hudi_precombine_key)
+                .map(|f| f.name.clone()),
             event_time: feature_group_dto.event_time,
             embedding_index: feature_group_dto.embedding_index.map(EmbeddingIndex::from),
+            storage_connector: feature_group_dto
+                .storage_connector
+                .map(StorageConnector::from),
+            query: feature_group_dto.query,
+            path: feature_group_dto.path,
+            data_format: feature_group_dto.data_format,
+            client: get_scoped_hopsworks_client(),
         }
     }
 }
@@ -181,12 +324,57 @@ impl FeatureGroup {
         self.online_enabled
     }
 
-    pub(crate) fn time_travel_format(&self) -> &str {
+    pub fn time_travel_format(&self) -> &str {
         self.time_travel_format.as_str()
     }
 
     pub fn is_time_travel_enabled(&self) -> bool {
-        self.time_travel_format == "HUDI"
+        matches!(self.time_travel_format.as_str(), "HUDI" | "DELTA")
+    }
+
+    /// Whether the [`FeatureGroup`] is an external (on-demand) Feature Group, whose data is read
+    /// from its storage connector rather than stored in the Feature Store.
+    pub fn is_external(&self) -> bool {
+        self.feature_group_type == EXTERNAL_FEATURE_GROUP_TYPE
+    }
+
+    /// Storage connector of an external [`FeatureGroup`].
+    pub fn storage_connector(&self) -> Option<&StorageConnector> {
+        self.storage_connector.as_ref()
+    }
+
+    /// SQL query run on the storage connector of an external [`FeatureGroup`] to read its data.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Path of the data of an external [`FeatureGroup`], relative to its storage connector.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Format of the data of an external [`FeatureGroup`] read from a path, e.g. `PARQUET` or `CSV`.
+    pub fn data_format(&self) -> Option<&str> {
+        self.data_format.as_deref()
+    }
+
+    /// Make the [`FeatureGroup`] being created an external Feature Group reading `query`
+    /// or the files at `path` from `storage_connector`.
+    fn with_external_source(
+        mut self,
+        storage_connector: StorageConnector,
+        query: Option<&str>,
+        path: Option<&str>,
+        data_format: Option<&str>,
+    ) -> Self {
+        self.feature_group_type = EXTERNAL_FEATURE_GROUP_TYPE.to_string();
+        self.time_travel_format = TimeTravelFormat::None.to_string();
+        self.online_enabled = false;
+        self.storage_connector = Some(storage_connector);
+        self.query = query.map(String::from);
+        self.path = path.map(String::from);
+        self.data_format = data_format.map(String::from);
+        self
     }
 
     pub fn online_topic_name(&self) -> Option<&str> {
@@ -233,6 +421,15 @@ impl FeatureGroup {
         self.primary_key.clone()
     }
 
+    /// Features partitioning the offline table of the feature group.
+    pub fn partition_keys(&self) -> Vec<&str> {
+        self.partition_key.iter().map(|key| key.as_str()).collect()
+    }
+
+    pub fn hudi_precombine_key(&self) -> Option<&str> {
+        self.hudi_precombine_key.as_deref()
+    }
+
     pub fn embedding_index(&self) -> Option<&EmbeddingIndex> {
         self.embedding_index.as_ref()
     }
@@ -241,6 +438,12 @@ impl FeatureGroup {
         &mut self.embedding_index
     }
 
+    /// Get the client used to register, insert into or read from the feature group,
+    /// `None` if it uses the global client initialized by [`login`][crate::login].
+    pub fn hopsworks_client(&self) -> Option<&HopsworksClient> {
+        self.client.as_ref()
+    }
+
     /// Returns the feature with the given name if exists.
     ///
     /// # Arguments
@@ -328,21 +531,30 @@ impl FeatureGroup {
         feature_types: &[String],
     ) -> Result<()> {
         if self.id().is_none() {
-            let feature_group_dto = feature_group::save_feature_group_metadata(
-                self.featurestore_id,
-                self.name(),
-                self.version(),
-                self.description(),
-                self.primary_key.iter().map(|pk| pk.as_ref()).collect(),
-                self.event_time.as_deref(),
-                self.online_enabled,
-                feature_names,
-                feature_types,
-            )
-            .await?;
+            let feature_group_dto = if self.is_external() {
+                with_hopsworks_client(
+                    self.hopsworks_client(),
+                    feature_group::save_external_feature_group_metadata(
+                        self,
+                        feature_names,
+                        feature_types,
+                    ),
+                )
+                .await?
+            } else {
+                with_hopsworks_client(
+                    self.hopsworks_client(),
+                    feature_group::save_feature_group_metadata(self, feature_names, feature_types),
+                )
+                .await?
+            };
 
             self.id = Some(feature_group_dto.id);
             self.online_topic_name = feature_group_dto.online_topic_name;
+            // Not returned for external feature groups
+            if !feature_group_dto.time_travel_format.is_empty() {
+                self.time_travel_format = feature_group_dto.time_travel_format;
+            }
             self.creator = Some(User::from(feature_group_dto.creator));
             self.location = Some(feature_group_dto.location);
             self.statistics_config = feature_group_dto
@@ -354,15 +566,316 @@ impl FeatureGroup {
 
             Ok(())
         } else {
-            Err(color_eyre::eyre::eyre!("Feature Group already registered."))
+            Err(HopsworksError::Validation(
+                "Feature Group already registered.".to_string(),
+            ))
+        }
+    }
+
+    /// Append features to the schema of the registered [`FeatureGroup`], without creating a new version.
+    ///
+    /// Appended features cannot be part of the primary key, rows inserted before the schema evolution
+    /// have a null value for the new features. For [`FeatureGroup`]s ingested via Kafka,
+    /// Hopsworks registers a new version of the Avro subject of the online topic, used by the following inserts.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use color_eyre::Result;
+    /// use hopsworks_core::feature_store::feature_group::feature::Feature;
+    ///
+    /// # async fn run() -> Result<()> {
+    /// let mut feature_group = hopsworks_core::login(None, hopsworks_core::RuntimeConfig::Current).await?
+    ///   .get_feature_store().await?
+    ///   .get_feature_group("demo_feature_group", Some(1)).await?
+    ///   .expect("Feature group not found");
+    ///
+    /// feature_group.append_features(&[
+    ///   Feature::new("amount_eur", "double").with_description("Amount converted to EUR"),
+    /// ]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, features), fields(name = self.name, version = self.version))]
+    pub async fn append_features(&mut self, features: &[Feature]) -> Result<()> {
+        if self.id().is_none() {
+            return Err(HopsworksError::Validation(
+                "Feature Group not registered, features are appended to registered Feature Groups only."
+                    .to_string(),
+            ));
+        }
+        for (index, feature) in features.iter().enumerate() {
+            if self.feature_by_name(feature.name()).is_some()
+                || features[..index]
+                    .iter()
+                    .any(|other| other.name() == feature.name())
+            {
+                return Err(HopsworksError::Validation(format!(
+                    "Feature {} already exists in Feature Group {}.",
+                    feature.name(),
+                    self.name
+                )));
+            }
+            if feature.is_primary() {
+                return Err(HopsworksError::Validation(format!(
+                    "Appended feature {} cannot be part of the primary key.",
+                    feature.name()
+                )));
+            }
+        }
+        if features.is_empty() {
+            return Ok(());
+        }
+
+        let feature_group_dto = with_hopsworks_client(
+            self.hopsworks_client(),
+            feature_group::append_features(
+                FeatureGroupDTO::from(&*self),
+                features.iter().map(FeatureDTO::from).collect(),
+            ),
+        )
+        .await?;
+        self.refresh_from_dto(feature_group_dto);
+        Ok(())
+    }
+
+    /// Append the features missing from the [`FeatureGroup`] schema, given the names and types of
+    /// the columns of the data to insert, e.g. extracted from a DataFrame schema.
+    ///
+    /// Returns the appended features, empty if the schema did not change.
+    pub async fn evolve_schema(
+        &mut self,
+        feature_names: &[String],
+        feature_types: &[String],
+    ) -> Result<Vec<Feature>> {
+        let new_features: Vec<Feature> = feature_names
+            .iter()
+            .zip(feature_types.iter())
+            .filter(|(name, _)| self.feature_by_name(name).is_none())
+            .map(|(name, data_type)| Feature::new(name, data_type))
+            .collect();
+        self.append_features(&new_features).await?;
+
+        Ok(self
+            .features
+            .iter()
+            .filter(|feature| {
+                new_features
+                    .iter()
+                    .any(|new_feature| new_feature.name() == feature.name())
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Update the description of the registered [`FeatureGroup`].
+    pub async fn update_description(&mut self, description: &str) -> Result<()> {
+        let mut feature_group_dto = FeatureGroupDTO::from(&*self);
+        feature_group_dto.description = Some(description.to_string());
+        self.update_metadata(feature_group_dto, "updateMetadata")
+            .await
+    }
+
+    /// Update the configuration of the statistics computed on the registered [`FeatureGroup`].
+    pub async fn update_statistics_config(
+        &mut self,
+        statistics_config: &StatisticsConfig,
+    ) -> Result<()> {
+        let mut feature_group_dto = FeatureGroupDTO::from(&*self);
+        feature_group_dto.statistics_config = Some(statistics_config.into());
+        self.update_metadata(feature_group_dto, "updateStatsConfig")
+            .await
+    }
+
+    /// Update the description of the feature `feature_name` of the registered [`FeatureGroup`].
+    pub async fn update_feature_description(
+        &mut self,
+        feature_name: &str,
+        description: &str,
+    ) -> Result<()> {
+        if self.feature_by_name(feature_name).is_none() {
+            return Err(HopsworksError::Validation(format!(
+                "Feature {} not found in Feature Group {}.",
+                feature_name, self.name
+            )));
+        }
+        let mut feature_group_dto = FeatureGroupDTO::from(&*self);
+        feature_group_dto
+            .features
+            .iter_mut()
+            .filter(|feature| feature.name == feature_name)
+            .for_each(|feature| feature.description = Some(description.to_string()));
+        self.update_metadata(feature_group_dto, "updateMetadata")
+            .await
+    }
+
+    /// Enable the online storage of the registered [`FeatureGroup`],
+    /// rows inserted from now on are also written to the online Feature Store.
+    pub async fn enable_online(&mut self) -> Result<()> {
+        let mut feature_group_dto = FeatureGroupDTO::from(&*self);
+        feature_group_dto.online_enabled = true;
+        self.update_metadata(feature_group_dto, "enableOnline")
+            .await
+    }
+
+    /// Disable the online storage of the registered [`FeatureGroup`], its online table is dropped.
+    pub async fn disable_online(&mut self) -> Result<()> {
+        let mut feature_group_dto = FeatureGroupDTO::from(&*self);
+        feature_group_dto.online_enabled = false;
+        self.update_metadata(feature_group_dto, "disableOnline")
+            .await
+    }
+
+    /// Get the [`ExpectationSuite`] attached to the registered [`FeatureGroup`], if any.
+    pub async fn get_expectation_suite(&self) -> Result<Option<ExpectationSuite>> {
+        let feature_group_id = self.registered_id("get its expectation suite")?;
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            expectation_suite_controller::get_expectation_suite(
+                self.featurestore_id,
+                feature_group_id,
+            ),
+        )
+        .await
+    }
+
+    /// Attach the [`ExpectationSuite`] to the registered [`FeatureGroup`], replacing the current one.
+    /// Data inserted in the Feature Group is then validated against it, see [`ValidationIngestionPolicy`].
+    ///
+    /// [`ValidationIngestionPolicy`]: self::expectation_suite::ValidationIngestionPolicy
+    pub async fn save_expectation_suite(
+        &self,
+        expectation_suite: &ExpectationSuite,
+    ) -> Result<ExpectationSuite> {
+        let feature_group_id = self.registered_id("attach an expectation suite")?;
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            expectation_suite_controller::save_expectation_suite(
+                self.featurestore_id,
+                feature_group_id,
+                expectation_suite,
+            ),
+        )
+        .await
+    }
+
+    /// Detach the [`ExpectationSuite`] of the registered [`FeatureGroup`], does nothing if there is none.
+    pub async fn delete_expectation_suite(&self) -> Result<()> {
+        let feature_group_id = self.registered_id("delete its expectation suite")?;
+        with_hopsworks_client(self.hopsworks_client(), async {
+            let Some(expectation_suite_id) = expectation_suite_controller::get_expectation_suite(
+                self.featurestore_id,
+                feature_group_id,
+            )
+            .await?
+            .and_then(|expectation_suite| expectation_suite.id()) else {
+                return Ok(());
+            };
+            expectation_suite_controller::delete_expectation_suite(
+                self.featurestore_id,
+                feature_group_id,
+                expectation_suite_id,
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Upload the [`ValidationReport`] of data inserted in the registered [`FeatureGroup`].
+    pub async fn save_validation_report(
+        &self,
+        validation_report: &ValidationReport,
+    ) -> Result<ValidationReport> {
+        let feature_group_id = self.registered_id("upload a validation report")?;
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            expectation_suite_controller::save_validation_report(
+                self.featurestore_id,
+                feature_group_id,
+                validation_report,
+            ),
+        )
+        .await
+    }
+
+    /// Get the most recent [`Statistics`] computed on the registered [`FeatureGroup`], if any.
+    pub async fn get_statistics(&self) -> Result<Option<Statistics>> {
+        let feature_group_id = self.registered_id("get its statistics")?;
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            statistics_controller::get_last_statistics(self.featurestore_id, feature_group_id),
+        )
+        .await
+    }
+
+    /// Upload [`Statistics`] computed on the data of the registered [`FeatureGroup`].
+    pub async fn save_statistics(&self, statistics: &Statistics) -> Result<Statistics> {
+        let feature_group_id = self.registered_id("upload its statistics")?;
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            statistics_controller::save_statistics(
+                self.featurestore_id,
+                feature_group_id,
+                statistics,
+            ),
+        )
+        .await
+    }
+
+    fn registered_id(&self, action: &str) -> Result<i32> {
+        self.id.ok_or_else(|| {
+            HopsworksError::Validation(format!(
+                "Feature Group not registered, register it before you {action}."
+            ))
+        })
+    }
+
+    async fn update_metadata(
+        &mut self,
+        feature_group_dto: FeatureGroupDTO,
+        query_param: &str,
+    ) -> Result<()> {
+        if self.id().is_none() {
+            return Err(HopsworksError::Validation(
+                "Feature Group not registered, register it before updating its metadata."
+                    .to_string(),
+            ));
         }
+        let feature_group_dto = with_hopsworks_client(
+            self.hopsworks_client(),
+            feature_group::update_feature_group_metadata(feature_group_dto, query_param),
+        )
+        .await?;
+        self.refresh_from_dto(feature_group_dto);
+        Ok(())
+    }
+
+    /// Refresh the metadata which can be updated after registration, keeping the client of the Feature Group.
+    fn refresh_from_dto(&mut self, feature_group_dto: FeatureGroupDTO) {
+        self.description = feature_group_dto.description;
+        self.online_enabled = feature_group_dto.online_enabled;
+        self.online_topic_name = feature_group_dto.online_topic_name;
+        self.statistics_config = feature_group_dto
+            .statistics_config
+            .as_ref()
+            .map(StatisticsConfig::from);
+        self.features = feature_group_dto
+            .features
+            .into_iter()
+            .map(Feature::from)
+            .collect();
     }
 
     pub async fn delete(&self) -> Result<()> {
         if self.id().is_none() {
-            Err(color_eyre::eyre::eyre!("Feature Group not registered."))
+            Err(HopsworksError::Validation(
+                "Feature Group not registered.".to_string(),
+            ))
         } else {
-            feature_group::delete_feature_group(self.feature_store_id(), self.id().unwrap()).await
+            with_hopsworks_client(
+                self.hopsworks_client(),
+                feature_group::delete_feature_group(self.feature_store_id(), self.id().unwrap()),
+            )
+            .await
         }
     }
 }
@@ -0,0 +1,229 @@
+//! Descriptive statistics of the data of a [`FeatureGroup`][super::FeatureGroup].
+//!
+//! Statistics follow the format of the Hopsworks statistics service, so statistics computed by
+//! Hopsworks jobs or the Python client can be read here and the other way around.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cluster_api::feature_store::statistics::{
+    FeatureDescriptiveStatisticsDTO, StatisticsDTO,
+};
+use crate::error::{HopsworksError, Result};
+
+/// Number of values of a feature falling in a bin of its histogram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBin {
+    /// Value for categorical features, lower bound of the bin for numerical features.
+    pub value: String,
+    pub count: u64,
+    pub ratio: f64,
+}
+
+/// Pearson correlation between a feature and another numerical feature.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Correlation {
+    pub column: String,
+    pub correlation: f64,
+}
+
+/// Statistics of a single feature, metrics which do not apply to its type or were not computed are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeatureDescriptiveStatistics {
+    pub feature_name: String,
+    /// Type of the feature, `Integral`, `Fractional`, `Boolean` or `String`.
+    pub feature_type: Option<String>,
+    pub count: Option<i64>,
+    pub completeness: Option<f64>,
+    pub num_non_null_values: Option<i64>,
+    pub num_null_values: Option<i64>,
+    pub approx_num_distinct_values: Option<i64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub sum: Option<f64>,
+    pub mean: Option<f64>,
+    pub stddev: Option<f64>,
+    pub percentiles: Option<Vec<f64>>,
+    pub distinctness: Option<f64>,
+    pub entropy: Option<f64>,
+    pub uniqueness: Option<f64>,
+    pub exact_num_distinct_values: Option<i64>,
+    pub histogram: Option<Vec<HistogramBin>>,
+    pub correlations: Option<Vec<Correlation>>,
+}
+
+impl FeatureDescriptiveStatistics {
+    pub fn new(feature_name: &str) -> Self {
+        Self {
+            feature_name: feature_name.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Statistics of the features of a [`FeatureGroup`][super::FeatureGroup] computed at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    computation_time: DateTime<Utc>,
+    row_percentage: f64,
+    feature_descriptive_statistics: Vec<FeatureDescriptiveStatistics>,
+}
+
+impl Statistics {
+    /// Statistics computed now on all the rows.
+    pub fn new(feature_descriptive_statistics: Vec<FeatureDescriptiveStatistics>) -> Self {
+        Self {
+            computation_time: Utc::now(),
+            row_percentage: 1.0,
+            feature_descriptive_statistics,
+        }
+    }
+
+    pub fn with_computation_time(mut self, computation_time: DateTime<Utc>) -> Self {
+        self.computation_time = computation_time;
+        self
+    }
+
+    /// Ratio of the rows the statistics were computed on, between 0 and 1.
+    pub fn with_row_percentage(mut self, row_percentage: f64) -> Self {
+        self.row_percentage = row_percentage;
+        self
+    }
+
+    pub fn computation_time(&self) -> DateTime<Utc> {
+        self.computation_time
+    }
+
+    pub fn row_percentage(&self) -> f64 {
+        self.row_percentage
+    }
+
+    pub fn feature_descriptive_statistics(&self) -> &[FeatureDescriptiveStatistics] {
+        &self.feature_descriptive_statistics
+    }
+
+    pub fn feature_statistics(&self, feature_name: &str) -> Option<&FeatureDescriptiveStatistics> {
+        self.feature_descriptive_statistics
+            .iter()
+            .find(|statistics| statistics.feature_name == feature_name)
+    }
+}
+
+// Histograms and correlations are stored in the JSON encoded extended statistics.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ExtendedStatistics {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    histogram: Option<Vec<HistogramBin>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    correlations: Option<Vec<Correlation>>,
+}
+
+impl From<&FeatureDescriptiveStatistics> for FeatureDescriptiveStatisticsDTO {
+    fn from(statistics: &FeatureDescriptiveStatistics) -> Self {
+        let extended_statistics =
+            (statistics.histogram.is_some() || statistics.correlations.is_some()).then(|| {
+                serde_json::json!({
+                    "histogram": statistics.histogram,
+                    "correlations": statistics.correlations,
+                })
+                .to_string()
+            });
+        FeatureDescriptiveStatisticsDTO {
+            id: None,
+            feature_name: statistics.feature_name.clone(),
+            feature_type: statistics.feature_type.clone(),
+            count: statistics.count,
+            completeness: statistics.completeness,
+            num_non_null_values: statistics.num_non_null_values,
+            num_null_values: statistics.num_null_values,
+            approx_num_distinct_values: statistics.approx_num_distinct_values,
+            min: statistics.min,
+            max: statistics.max,
+            sum: statistics.sum,
+            mean: statistics.mean,
+            stddev: statistics.stddev,
+            percentiles: statistics.percentiles.clone(),
+            distinctness: statistics.distinctness,
+            entropy: statistics.entropy,
+            uniqueness: statistics.uniqueness,
+            exact_num_distinct_values: statistics.exact_num_distinct_values,
+            extended_statistics,
+        }
+    }
+}
+
+impl TryFrom<FeatureDescriptiveStatisticsDTO> for FeatureDescriptiveStatistics {
+    type Error = HopsworksError;
+
+    fn try_from(statistics_dto: FeatureDescriptiveStatisticsDTO) -> Result<Self> {
+        let extended_statistics = match statistics_dto.extended_statistics.as_deref() {
+            None | Some("") => ExtendedStatistics::default(),
+            Some(extended_statistics) => {
+                serde_json::from_str::<ExtendedStatistics>(extended_statistics).map_err(|e| {
+                    HopsworksError::serialization(format!(
+                        "Invalid extended statistics of feature {}: {e}",
+                        statistics_dto.feature_name
+                    ))
+                })?
+            }
+        };
+        Ok(FeatureDescriptiveStatistics {
+            feature_name: statistics_dto.feature_name,
+            feature_type: statistics_dto.feature_type,
+            count: statistics_dto.count,
+            completeness: statistics_dto.completeness,
+            num_non_null_values: statistics_dto.num_non_null_values,
+            num_null_values: statistics_dto.num_null_values,
+            approx_num_distinct_values: statistics_dto.approx_num_distinct_values,
+            min: statistics_dto.min,
+            max: statistics_dto.max,
+            sum: statistics_dto.sum,
+            mean: statistics_dto.mean,
+            stddev: statistics_dto.stddev,
+            percentiles: statistics_dto.percentiles,
+            distinctness: statistics_dto.distinctness,
+            entropy: statistics_dto.entropy,
+            uniqueness: statistics_dto.uniqueness,
+            exact_num_distinct_values: statistics_dto.exact_num_distinct_values,
+            histogram: extended_statistics.histogram,
+            correlations: extended_statistics.correlations,
+        })
+    }
+}
+
+impl From<&Statistics> for StatisticsDTO {
+    fn from(statistics: &Statistics) -> Self {
+        StatisticsDTO {
+            computation_time: statistics.computation_time.timestamp_millis(),
+            row_percentage: statistics.row_percentage,
+            feature_descriptive_statistics: statistics
+                .feature_descriptive_statistics
+                .iter()
+                .map(FeatureDescriptiveStatisticsDTO::from)
+                .collect(),
+            window_start_commit_time: None,
+            window_end_commit_time: None,
+        }
+    }
+}
+
+impl TryFrom<StatisticsDTO> for Statistics {
+    type Error = HopsworksError;
+
+    fn try_from(statistics_dto: StatisticsDTO) -> Result<Self> {
+        Ok(Statistics {
+            computation_time: DateTime::from_timestamp_millis(statistics_dto.computation_time)
+                .ok_or_else(|| {
+                    HopsworksError::serialization(format!(
+                        "Invalid statistics computation time {}",
+                        statistics_dto.computation_time
+                    ))
+                })?,
+            row_percentage: statistics_dto.row_percentage,
+            feature_descriptive_statistics: statistics_dto
+                .feature_descriptive_statistics
+                .into_iter()
+                .map(FeatureDescriptiveStatistics::try_from)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
@@ -9,7 +9,7 @@ pub mod training_dataset;
 pub mod training_dataset_builder;
 pub mod transformation_function;
 
-use color_eyre::Result;
+use crate::error::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +21,7 @@ use crate::{
 };
 
 use crate::cluster_api::feature_store::feature_view::FeatureViewDTO;
+use crate::{get_scoped_hopsworks_client, with_hopsworks_client, HopsworksClient};
 use std::collections::HashMap;
 
 use self::{training_dataset_builder::NoSplit, transformation_function::TransformationFunction};
@@ -124,6 +125,8 @@ pub struct FeatureView {
     transformation_functions: HashMap<String, TransformationFunction>,
     feature_store_id: i32,
     feature_store_name: String,
+    #[serde(skip)]
+    client: Option<HopsworksClient>,
 }
 
 impl From<FeatureViewDTO> for FeatureView {
@@ -136,6 +139,7 @@ impl From<FeatureViewDTO> for FeatureView {
             transformation_functions: HashMap::<String, TransformationFunction>::new(),
             feature_store_id: dto.featurestore_id,
             feature_store_name: dto.featurestore_name,
+            client: get_scoped_hopsworks_client(),
         }
     }
 }
@@ -177,8 +181,18 @@ impl FeatureView {
         &mut self.transformation_functions
     }
 
+    /// Get the client used to read from the feature view,
+    /// `None` if it uses the global client initialized by [`login`][crate::login].
+    pub fn hopsworks_client(&self) -> Option<&HopsworksClient> {
+        self.client.as_ref()
+    }
+
     pub async fn delete(&self) -> Result<()> {
-        crate::controller::feature_store::feature_view::delete(self).await?;
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::feature_store::feature_view::delete(self),
+        )
+        .await?;
         Ok(())
     }
 
@@ -186,26 +200,38 @@ impl FeatureView {
         &self,
         batch_query_options: &BatchQueryOptions,
     ) -> Result<String> {
-        crate::controller::feature_store::feature_view::get_batch_query_string(
-            self,
-            batch_query_options,
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::feature_store::feature_view::get_batch_query_string(
+                self,
+                batch_query_options,
+            ),
         )
         .await
     }
 
     pub async fn get_batch_query(&self, batch_query_options: &BatchQueryOptions) -> Result<Query> {
-        crate::controller::feature_store::feature_view::get_batch_query(self, batch_query_options)
-            .await
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::feature_store::feature_view::get_batch_query(
+                self,
+                batch_query_options,
+            ),
+        )
+        .await
     }
 
     pub fn training_dataset_builder(
         &self,
     ) -> self::training_dataset_builder::TrainingDatasetBuilder<NoSplit> {
-        self::training_dataset_builder::TrainingDatasetBuilder::new_default_from_feature_view(
-            self.feature_store_id(),
-            self.name(),
-            self.version(),
-        )
+        let mut builder =
+            self::training_dataset_builder::TrainingDatasetBuilder::new_default_from_feature_view(
+                self.feature_store_id(),
+                self.name(),
+                self.version(),
+            );
+        builder.client = self.client.clone();
+        builder
     }
 
     pub async fn create_train_test_split(
@@ -217,7 +243,7 @@ impl FeatureView {
         // data_format: &str,
         // coalesce: bool,
     ) -> Result<()> {
-        create_train_test_split().await?;
+        with_hopsworks_client(self.hopsworks_client(), create_train_test_split()).await?;
         Ok(())
     }
 
@@ -228,7 +254,11 @@ impl FeatureView {
         // data_format: &str,
         // coalesce: bool,
     ) -> Result<()> {
-        create_training_dataset_attached_to_feature_view(self).await?;
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            create_training_dataset_attached_to_feature_view(self),
+        )
+        .await?;
         Ok(())
     }
 }
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use color_eyre::Result;
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::cluster_api::feature_store::{
@@ -14,6 +14,7 @@ use crate::feature_store::{
 };
 
 use super::training_dataset::TrainingDataset;
+use crate::{get_scoped_hopsworks_client, with_hopsworks_client, HopsworksClient};
 
 mod seal {
     pub trait Sealed {}
@@ -75,6 +76,8 @@ where
     pub(crate) test_split_options: Option<SplitOptions>,
     pub(crate) validation_split_options: Option<SplitOptions>,
     pub(crate) storage_connector: Option<StorageConnectorDTO>,
+    #[serde(skip)]
+    pub(crate) client: Option<HopsworksClient>,
     state: std::marker::PhantomData<State>,
 }
 
@@ -100,6 +103,7 @@ impl TrainingDatasetBuilder<NoSplit> {
             description: None,
             coalesce: false,
             storage_connector: None,
+            client: get_scoped_hopsworks_client(),
             state: std::marker::PhantomData::<NoSplit>,
         }
     }
@@ -121,6 +125,7 @@ impl TrainingDatasetBuilder<NoSplit> {
             coalesce: self.coalesce,
             extra_filters: self.extra_filters,
             storage_connector: self.storage_connector,
+            client: self.client,
             state: std::marker::PhantomData::<TestSplit>,
         }
     }
@@ -142,6 +147,7 @@ impl TrainingDatasetBuilder<NoSplit> {
             coalesce: self.coalesce,
             extra_filters: self.extra_filters,
             storage_connector: self.storage_connector,
+            client: self.client,
             state: std::marker::PhantomData::<TestValidationSplit>,
         }
     }
@@ -271,11 +277,19 @@ where
     }
 
     pub async fn register(&self) -> Result<TrainingDataset> {
-        crate::controller::feature_store::training_dataset::register_training_dataset(self).await
+        with_hopsworks_client(
+            self.client.as_ref(),
+            crate::controller::feature_store::training_dataset::register_training_dataset(self),
+        )
+        .await
     }
 
     pub async fn materialize_on_cluster(&self) -> Result<TrainingDataset> {
-        crate::controller::feature_store::training_dataset::materialize_on_cluster(self).await
+        with_hopsworks_client(
+            self.client.as_ref(),
+            crate::controller::feature_store::training_dataset::materialize_on_cluster(self),
+        )
+        .await
     }
 }
 
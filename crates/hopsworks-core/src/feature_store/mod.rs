@@ -5,25 +5,37 @@ pub mod feature_view;
 pub mod query;
 pub mod storage_connector;
 
+use feature_group::ExternalFeatureGroupDefinition;
 pub use feature_group::FeatureGroup;
 pub use feature_view::FeatureView;
 
-use color_eyre::Result;
+use crate::error::Result;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::controller::feature_store::{
-    feature_group::get_feature_group_by_name_and_version,
+    feature_group::{
+        get_feature_group_by_id, get_feature_group_by_name_and_version, get_feature_group_versions,
+        get_feature_groups,
+    },
     feature_view::{create_feature_view, get_feature_view_by_name_and_version},
+    storage_connector::{
+        get_feature_store_storage_connector_by_name, get_feature_store_storage_connector_page,
+        get_feature_store_storage_connectors,
+    },
     training_dataset::get_training_dataset_by_name_and_version,
     transformation_function::get_transformation_function_by_name_and_version,
 };
 
 use crate::cluster_api::feature_store::FeatureStoreDTO;
+use crate::pagination::{paginate, ListOptions};
+use crate::{get_scoped_hopsworks_client, with_hopsworks_client, HopsworksClient};
 use feature_view::{
     training_dataset::TrainingDataset, transformation_function::TransformationFunction,
 };
 use query::Query;
+use storage_connector::StorageConnector;
 
 pub type FeatureGroupBuilder = self::feature_group::FeatureGroupBuilder<(
     (i32,),
@@ -36,6 +48,25 @@ pub type FeatureGroupBuilder = self::feature_group::FeatureGroupBuilder<(
     (),
     (),
     (),
+    (),
+    (),
+    (),
+    (Option<HopsworksClient>,),
+)>;
+
+pub type ExternalFeatureGroupBuilder = self::feature_group::ExternalFeatureGroupBuilder<(
+    (i32,),
+    (std::string::String,),
+    (Option<HopsworksClient>,),
+    (StorageConnector,),
+    (),
+    (),
+    (),
+    (),
+    (),
+    (),
+    (),
+    (),
 )>;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -137,6 +168,8 @@ pub struct FeatureStore {
     online_featurestore_name: String,
     online_featurestore_size: Option<f64>,
     online_enabled: bool,
+    #[serde(skip)]
+    client: Option<HopsworksClient>,
 }
 
 impl FeatureStore {
@@ -155,6 +188,7 @@ impl FeatureStore {
             online_featurestore_name: feature_store_dto.online_featurestore_name,
             online_featurestore_size: feature_store_dto.online_featurestore_size,
             online_enabled: feature_store_dto.online_enabled,
+            client: get_scoped_hopsworks_client(),
         }
     }
 
@@ -169,6 +203,12 @@ impl FeatureStore {
     pub fn id(&self) -> i32 {
         self.featurestore_id
     }
+
+    /// Get the client used by the feature store and its entities,
+    /// `None` if they use the global client initialized by [`login`][crate::login].
+    pub fn hopsworks_client(&self) -> Option<&HopsworksClient> {
+        self.client.as_ref()
+    }
 }
 
 impl From<FeatureStoreDTO> for FeatureStore {
@@ -208,13 +248,72 @@ impl FeatureStore {
         name: &str,
         version: Option<i32>,
     ) -> Result<Option<FeatureGroup>> {
-        if let Some(feature_group_dto) =
-            get_feature_group_by_name_and_version(self.featurestore_id, name, version).await?
-        {
-            Ok(Some(FeatureGroup::from(feature_group_dto)))
-        } else {
-            Ok(None)
-        }
+        // Convert within the scope so that the feature group captures the client of the feature store
+        with_hopsworks_client(self.hopsworks_client(), async {
+            Ok(
+                get_feature_group_by_name_and_version(self.featurestore_id, name, version)
+                    .await?
+                    .map(FeatureGroup::from),
+            )
+        })
+        .await
+    }
+
+    /// Get a [`FeatureGroup`] by id, returns `None` if it does not exist in the [`FeatureStore`].
+    pub async fn get_feature_group_by_id(
+        &self,
+        feature_group_id: i32,
+    ) -> Result<Option<FeatureGroup>> {
+        with_hopsworks_client(self.hopsworks_client(), async {
+            Ok(
+                get_feature_group_by_id(self.featurestore_id, feature_group_id)
+                    .await?
+                    .map(FeatureGroup::from),
+            )
+        })
+        .await
+    }
+
+    /// Get every version of the [`FeatureGroup`] `name`, sorted by ascending version.
+    /// Returns an empty list if no [`FeatureGroup`] with this name exists.
+    pub async fn get_feature_group_versions(&self, name: &str) -> Result<Vec<FeatureGroup>> {
+        with_hopsworks_client(self.hopsworks_client(), async {
+            Ok(get_feature_group_versions(self.featurestore_id, name)
+                .await?
+                .into_iter()
+                .map(FeatureGroup::from)
+                .collect())
+        })
+        .await
+    }
+
+    /// Get the [`FeatureGroup`]s of the [`FeatureStore`], sorted by name and version.
+    ///
+    /// # Arguments
+    /// * `latest_only` - Only return the latest version of each [`FeatureGroup`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use color_eyre::Result;
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///   let feature_store = hopsworks_core::login(None, hopsworks_core::RuntimeConfig::Current).await?.get_feature_store().await?;
+    ///
+    ///   for feature_group in feature_store.get_feature_groups(true).await? {
+    ///     println!("{} v{}", feature_group.name(), feature_group.version());
+    ///   }
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn get_feature_groups(&self, latest_only: bool) -> Result<Vec<FeatureGroup>> {
+        with_hopsworks_client(self.hopsworks_client(), async {
+            Ok(get_feature_groups(self.featurestore_id, latest_only)
+                .await?
+                .into_iter()
+                .map(FeatureGroup::from)
+                .collect())
+        })
+        .await
     }
 
     /// Get a [`FeatureGroup`] by name and optional version. If no version is provided, the latest version is returned.
@@ -341,15 +440,62 @@ impl FeatureStore {
             .version(version)
             .primary_key(primary_key.iter().map(|s| String::from(*s)).collect())
             .online_enabled(online_enabled)
-            .event_time(event_time.map(String::from));
+            .event_time(event_time.map(String::from))
+            .client(self.client.clone());
 
         Ok(builder.build())
     }
 
+    /// Start building an external (on-demand) [`FeatureGroup`] reading its data from `storage_connector`,
+    /// either with a SQL `query`, e.g. on a JDBC connector, or from the files at `path` in the `data_format`,
+    /// e.g. on an S3 or HopsFS connector. The data stays in the external storage and is not ingested into
+    /// the Feature Store, but the [`FeatureGroup`] can be queried and joined with other Feature Groups.
+    ///
+    /// Building fails if neither a query nor a path is set, or if a path is set without a data format.
+    /// The [`FeatureGroup`] is registered with [`FeatureGroup::register_feature_group`], given the names
+    /// and types of its features.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use color_eyre::Result;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///   let feature_store = hopsworks_core::login(None, hopsworks_core::RuntimeConfig::Current).await?.get_feature_store().await?;
+    ///   let connector = feature_store.get_storage_connector("my_jdbc_connector").await?
+    ///     .expect("Storage connector not found");
+    ///
+    ///   let mut feature_group = feature_store
+    ///     .external_feature_group_builder(&connector)
+    ///     .name("my_external_fg")
+    ///     .version(1)
+    ///     .primary_key(vec!["id".to_string()])
+    ///     .query("SELECT id, amount FROM transactions")
+    ///     .build()?;
+    ///   feature_group.register_feature_group(
+    ///     &["id".to_string(), "amount".to_string()],
+    ///     &["bigint".to_string(), "double".to_string()],
+    ///   ).await?;
+    ///
+    ///   Ok(())
+    /// }
+    /// ```
+    pub fn external_feature_group_builder(
+        &self,
+        storage_connector: &StorageConnector,
+    ) -> ExternalFeatureGroupBuilder {
+        ExternalFeatureGroupDefinition::builder()
+            .featurestore_id(self.featurestore_id)
+            .featurestore_name(self.featurestore_name.clone())
+            .client(self.client.clone())
+            .storage_connector(storage_connector.clone())
+    }
+
     pub fn feature_group_builder(&self) -> FeatureGroupBuilder {
         FeatureGroup::builder()
             .featurestore_id(self.featurestore_id)
             .featurestore_name(self.featurestore_name.clone())
+            .client(self.client.clone())
     }
 
     /// Create a [`FeatureView`] with the given name and version. The [`FeatureView`] is the main interface to read data from the Feature Store,
@@ -399,14 +545,17 @@ impl FeatureStore {
         transformation_functions: Option<HashMap<String, TransformationFunction>>,
         description: Option<&str>,
     ) -> Result<FeatureView> {
-        create_feature_view(
-            self.feature_store_id(),
-            self.feature_store_name(),
-            name,
-            version,
-            &query,
-            transformation_functions,
-            description,
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            create_feature_view(
+                self.feature_store_id(),
+                self.feature_store_name(),
+                name,
+                version,
+                &query,
+                transformation_functions,
+                description,
+            ),
         )
         .await
     }
@@ -440,7 +589,11 @@ impl FeatureStore {
         name: &str,
         version: Option<i32>,
     ) -> Result<Option<FeatureView>> {
-        get_feature_view_by_name_and_version(self.featurestore_id, name, version).await
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            get_feature_view_by_name_and_version(self.featurestore_id, name, version),
+        )
+        .await
     }
 
     /// Get a [`TransformationFunction`] by name and optional version. If no version is provided, the latest version is returned.
@@ -485,7 +638,11 @@ impl FeatureStore {
         name: &str,
         version: Option<i32>,
     ) -> Result<Option<TransformationFunction>> {
-        get_transformation_function_by_name_and_version(self.featurestore_id, name, version).await
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            get_transformation_function_by_name_and_version(self.featurestore_id, name, version),
+        )
+        .await
     }
 
     /// Get a [`TrainingDataset`] by name and optional version. If no version is provided, the latest version is returned.
@@ -518,6 +675,68 @@ impl FeatureStore {
         name: &str,
         version: Option<i32>,
     ) -> Result<Option<TrainingDataset>> {
-        get_training_dataset_by_name_and_version(self.featurestore_id, name, version).await
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            get_training_dataset_by_name_and_version(self.featurestore_id, name, version),
+        )
+        .await
+    }
+    /// Get one page of the storage connectors of the [`FeatureStore`].
+    ///
+    /// # Arguments
+    /// * `list_options` - Offset, limit, sorting and filtering of the page.
+    pub async fn get_storage_connectors(
+        &self,
+        list_options: &ListOptions,
+    ) -> Result<Vec<StorageConnector>> {
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            get_feature_store_storage_connectors(self.featurestore_id, list_options),
+        )
+        .await
+    }
+
+    /// Get a storage connector of the [`FeatureStore`] by name, with its
+    /// [`connector_options`][StorageConnector::connector_options].
+    pub async fn get_storage_connector(&self, name: &str) -> Result<Option<StorageConnector>> {
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            get_feature_store_storage_connector_by_name(self.featurestore_id, name),
+        )
+        .await
+    }
+
+    /// Lazily iterate over the storage connectors of the [`FeatureStore`], fetching
+    /// [`ListOptions::page_size`] connectors per request.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use color_eyre::Result;
+    /// use futures::TryStreamExt;
+    /// use hopsworks_core::ListOptions;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///   let feature_store = hopsworks_core::login(None, hopsworks_core::RuntimeConfig::Current).await?.get_feature_store().await?;
+    ///   let mut connectors = feature_store.storage_connectors_stream(ListOptions::new());
+    ///
+    ///   while let Some(connector) = connectors.try_next().await? {
+    ///     println!("{}: {}", connector.name(), connector.storage_connector_type());
+    ///   }
+    ///   Ok(())
+    /// }
+    /// ```
+    pub fn storage_connectors_stream(
+        &self,
+        list_options: ListOptions,
+    ) -> impl Stream<Item = Result<StorageConnector>> + Send + Unpin {
+        let feature_store_id = self.featurestore_id;
+        paginate(
+            list_options,
+            self.client.clone(),
+            move |list_options| async move {
+                get_feature_store_storage_connector_page(feature_store_id, &list_options).await
+            },
+        )
     }
 }
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksError, Result};
 use serde::{Deserialize, Serialize};
 
 use crate::cluster_api::feature_store::feature::FeatureDTO;
@@ -91,8 +91,8 @@ impl QueryFilter {
         T: PartialEq + serde::Serialize + serde::de::DeserializeOwned,
     {
         if condition != QueryFilterCondition::Equal && condition != QueryFilterCondition::NotEqual {
-            return Err(color_eyre::eyre::eyre!(
-                "QueryFilterCondition must be Equal or NotEqual for partial_eq"
+            return Err(HopsworksError::Validation(
+                "QueryFilterCondition must be Equal or NotEqual for partial_eq".to_string(),
             ));
         }
         let value = serde_json::to_value(value)?;
@@ -112,8 +112,8 @@ impl QueryFilter {
             && condition != QueryFilterCondition::LessThan
             && condition != QueryFilterCondition::LessThanOrEqual
         {
-            return Err(color_eyre::eyre::eyre!(
-                "QueryFilterCondition must be GreaterThan, GreaterThanOrEqual, LessThan, or LessThanOrEqual for partial_ord"
+            return Err(HopsworksError::Validation(
+                "QueryFilterCondition must be GreaterThan, GreaterThanOrEqual, LessThan, or LessThanOrEqual for partial_ord".to_string(),
             ));
         }
         let value = serde_json::to_value(value)?;
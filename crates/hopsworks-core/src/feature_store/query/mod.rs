@@ -4,7 +4,7 @@ pub mod enums;
 pub mod filter;
 pub mod join;
 
-use color_eyre::Result;
+use crate::error::Result;
 
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +12,7 @@ pub use filter::{QueryFilter, QueryFilterOrLogic, QueryLogic};
 pub use join::{JoinOptions, JoinQuery};
 
 use crate::feature_store::feature_group::{feature::Feature, FeatureGroup};
+use crate::HopsworksClient;
 
 use super::query::builder::BatchQueryOptions;
 use crate::cluster_api::feature_store::{
@@ -152,6 +153,11 @@ impl Query {
         &self.left_feature_group
     }
 
+    /// Get the client of the left feature group, used to read the query results.
+    pub fn hopsworks_client(&self) -> Option<&HopsworksClient> {
+        self.left_feature_group.hopsworks_client()
+    }
+
     pub fn left_features(&self) -> &Vec<Feature> {
         &self.left_features
     }
@@ -1,9 +1,196 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
 
 use crate::cluster_api::feature_store::storage_connector::{
-    FeatureStoreJdbcConnectorDTO, FeatureStoreKafkaConnectorDTO,
+    FeatureStoreJdbcConnectorDTO, FeatureStoreKafkaConnectorDTO, FeatureStoreStorageConnectorDTO,
+    StorageConnectorDTO,
 };
 
+/// Storage connector of a [`FeatureStore`][crate::feature_store::FeatureStore], as listed by
+/// [`get_storage_connectors`][crate::feature_store::FeatureStore::get_storage_connectors]
+/// or fetched by name with [`get_storage_connector`][crate::feature_store::FeatureStore::get_storage_connector].
+///
+/// The credentials among the options, e.g. a JDBC `password` or an S3 `secret_key`, are redacted
+/// from the debug output and left out when serializing.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StorageConnector {
+    id: i32,
+    name: String,
+    description: Option<String>,
+    storage_connector_type: String,
+    feature_store_id: i32,
+    // Type of the DTO in the REST API, e.g. `featurestoreJdbcConnectorDTO`
+    dto_type: String,
+    #[serde(default, serialize_with = "serialize_public_options")]
+    options: HashMap<String, String>,
+}
+
+impl std::fmt::Debug for StorageConnector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Keep the credentials out of logs and debug output of the feature groups holding the connector
+        let options: BTreeMap<&str, &str> = self
+            .options
+            .iter()
+            .map(|(key, value)| {
+                let value = if is_secret_option(key) {
+                    "<redacted>"
+                } else {
+                    value.as_str()
+                };
+                (key.as_str(), value)
+            })
+            .collect();
+        f.debug_struct("StorageConnector")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("storage_connector_type", &self.storage_connector_type)
+            .field("feature_store_id", &self.feature_store_id)
+            .field("dto_type", &self.dto_type)
+            .field("options", &options)
+            .finish()
+    }
+}
+
+/// Whether the connector option holds a credential, e.g. `password`, `secret_key` or `session_token`.
+pub(crate) fn is_secret_option(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key.contains("password")
+        || key.contains("secret")
+        || key.contains("token")
+        || key == "access_key"
+}
+
+fn serialize_public_options<S: Serializer>(
+    options: &HashMap<String, String>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_map(options.iter().filter(|(key, _)| !is_secret_option(key)))
+}
+
+impl StorageConnector {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Type of the connector, e.g. `JDBC`, `HOPSFS`, `KAFKA` or `S3`.
+    pub fn storage_connector_type(&self) -> &str {
+        &self.storage_connector_type
+    }
+
+    pub fn feature_store_id(&self) -> i32 {
+        self.feature_store_id
+    }
+
+    /// Options to read from the storage connector, e.g. the `url`, `user` and `password` of a JDBC connector
+    /// or the `bucket` and credentials of an S3 connector. Empty for connectors listed by
+    /// [`get_storage_connectors`][crate::feature_store::FeatureStore::get_storage_connectors].
+    pub fn connector_options(&self) -> &HashMap<String, String> {
+        &self.options
+    }
+}
+
+impl From<FeatureStoreStorageConnectorDTO> for StorageConnector {
+    fn from(dto: FeatureStoreStorageConnectorDTO) -> Self {
+        Self {
+            id: dto.id,
+            name: dto.name,
+            description: dto.description,
+            storage_connector_type: dto.storage_connector_type,
+            feature_store_id: dto.feature_store_id,
+            dto_type: dto._type,
+            options: HashMap::new(),
+        }
+    }
+}
+
+impl From<StorageConnectorDTO> for StorageConnector {
+    fn from(dto: StorageConnectorDTO) -> Self {
+        match dto {
+            StorageConnectorDTO::JdbcConnectorDTO(dto) => {
+                let mut options = arguments_to_options(&dto.arguments);
+                options.insert("url".to_string(), dto.connection_string);
+                Self {
+                    id: dto.id,
+                    name: dto.name,
+                    description: Some(dto.description),
+                    storage_connector_type: dto.storage_connector_type,
+                    feature_store_id: dto.feature_store_id,
+                    dto_type: dto._type,
+                    options,
+                }
+            }
+            StorageConnectorDTO::HopsfsConnectorDTO(dto) => Self {
+                id: dto.id,
+                name: dto.name,
+                description: Some(dto.description),
+                storage_connector_type: dto.storage_connector_type,
+                feature_store_id: dto.feature_store_id,
+                dto_type: dto._type,
+                options: HashMap::from([("hopsfs_path".to_string(), dto.hopsfs_path)]),
+            },
+            StorageConnectorDTO::S3ConnectorDTO(dto) => {
+                let mut options = arguments_to_options(&dto.arguments);
+                options.insert("bucket".to_string(), dto.bucket);
+                for (key, value) in [
+                    ("region", dto.region),
+                    ("access_key", dto.access_key),
+                    ("secret_key", dto.secret_key),
+                    ("session_token", dto.session_token),
+                ] {
+                    if let Some(value) = value {
+                        options.insert(key.to_string(), value);
+                    }
+                }
+                Self {
+                    id: dto.id,
+                    name: dto.name,
+                    description: dto.description,
+                    storage_connector_type: dto.storage_connector_type,
+                    feature_store_id: dto.feature_store_id,
+                    dto_type: dto._type,
+                    options,
+                }
+            }
+            StorageConnectorDTO::OtherConnectorDTO(dto) => StorageConnector::from(dto),
+        }
+    }
+}
+
+impl From<&StorageConnector> for FeatureStoreStorageConnectorDTO {
+    fn from(storage_connector: &StorageConnector) -> Self {
+        Self {
+            _type: storage_connector.dto_type.clone(),
+            id: storage_connector.id,
+            description: storage_connector.description.clone(),
+            name: storage_connector.name.clone(),
+            feature_store_id: storage_connector.feature_store_id,
+            storage_connector_type: storage_connector.storage_connector_type.clone(),
+        }
+    }
+}
+
+/// Arguments of a connector are sent by the REST API as a list of `{"name": .., "value": ..}` objects.
+fn arguments_to_options(arguments: &[HashMap<String, String>]) -> HashMap<String, String> {
+    arguments
+        .iter()
+        .filter_map(|argument| {
+            Some((
+                argument.get("name")?.clone(),
+                argument.get("value")?.clone(),
+            ))
+        })
+        .collect()
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FeatureStoreKafkaConnector {
     pub(crate) feature_store_id: i32,
@@ -99,3 +286,49 @@ impl From<FeatureStoreJdbcConnectorDTO> for FeatureStoreOnlineConnector {
 }
 
 pub struct FeatureStoreJdbcConnector {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cluster_api::feature_store::storage_connector::FeatureStoreS3ConnectorDTO;
+
+    #[test]
+    fn test_storage_connector_credentials_are_redacted() {
+        // Arrange
+        let dto: FeatureStoreS3ConnectorDTO = serde_json::from_value(serde_json::json!({
+            "type": "featurestoreS3ConnectorDTO",
+            "id": 1,
+            "name": "s3_data",
+            "featurestoreId": 1,
+            "storageConnectorType": "S3",
+            "bucket": "my-bucket",
+            "accessKey": "my_access_key",
+            "secretKey": "my_secret_key",
+            "sessionToken": "my_session_token",
+            "arguments": [{"name": "password", "value": "my_password"}]
+        }))
+        .unwrap();
+        let dto_debug = format!("{dto:?}");
+        assert!(!dto_debug.contains("my_secret_key"));
+        assert!(!dto_debug.contains("my_password"));
+
+        // Act
+        let connector = StorageConnector::from(StorageConnectorDTO::S3ConnectorDTO(dto));
+        let debug = format!("{connector:?}");
+        let serialized = serde_json::to_string(&connector).unwrap();
+
+        // Assert
+        assert_eq!(connector.connector_options()["secret_key"], "my_secret_key");
+        for secret in [
+            "my_access_key",
+            "my_secret_key",
+            "my_session_token",
+            "my_password",
+        ] {
+            assert!(!debug.contains(secret));
+            assert!(!serialized.contains(secret));
+        }
+        assert!(debug.contains("my-bucket"));
+        assert!(serialized.contains("my-bucket"));
+    }
+}
@@ -87,141 +87,109 @@
 //! }
 //! ```
 
-use color_eyre::Result;
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc, OnceLock,
-};
-use tokio::sync::OnceCell;
+use std::{future::Future, sync::OnceLock};
 use tracing::debug;
 
 pub(crate) mod cluster_api;
 pub mod controller;
+pub mod error;
 pub mod feature_store;
+pub mod pagination;
 pub mod platform;
 pub mod profiles;
 pub mod rest_client;
+pub mod runtime;
 pub mod util;
 
+pub use error::{HopsworksError, Result};
+pub use pagination::ListOptions;
 pub use platform::project::Project;
-use rest_client::HopsworksClient;
-pub use rest_client::HopsworksClientBuilder;
+pub use rest_client::{HopsworksClient, HopsworksClientBuilder, RetryPolicy, TlsConfig};
+pub use runtime::RuntimeConfig;
 
-static NUM_LOGICAL_CPUS: OnceCell<usize> = OnceCell::const_new();
-static THREADED_RUNTIME_NUM_WORKER_THREADS: OnceCell<usize> = tokio::sync::OnceCell::const_new();
-static THREADED_RUNTIME: OnceLock<Arc<tokio::runtime::Runtime>> = OnceLock::new();
-static SINGLE_THREADED_RUNTIME: OnceLock<Arc<tokio::runtime::Runtime>> = OnceLock::new();
 static HOPSWORKS_CLIENT: OnceLock<HopsworksClient> = OnceLock::new();
 
-pub fn get_logical_cpus() -> usize {
-    if NUM_LOGICAL_CPUS.initialized() {
-        *NUM_LOGICAL_CPUS.get().unwrap()
-    } else {
-        let env_requested_threads = std::env::var("HOPSWORKS_NUM_THREADS")
-            .ok()
-            .and_then(|s| s.parse::<usize>().ok());
-        let num_logical_cpus =
-            env_requested_threads.unwrap_or(std::thread::available_parallelism().unwrap().get());
-        tracing::debug!("Detected {} logical CPUs", num_logical_cpus);
-        NUM_LOGICAL_CPUS.set(num_logical_cpus).unwrap();
-        num_logical_cpus
-    }
+tokio::task_local! {
+    pub(crate) static SCOPED_HOPSWORKS_CLIENT: HopsworksClient;
 }
 
-pub fn get_threaded_runtime_num_worker_threads() -> usize {
-    if THREADED_RUNTIME_NUM_WORKER_THREADS.initialized() {
-        *THREADED_RUNTIME_NUM_WORKER_THREADS.get().unwrap()
-    } else {
-        let num_worker_threads = get_logical_cpus();
-        THREADED_RUNTIME_NUM_WORKER_THREADS
-            .set(num_worker_threads)
-            .unwrap();
-        num_worker_threads
+/// Get the [`HopsworksClient`] used to send requests to the cluster.
+///
+/// Inside [`HopsworksClient::scope`] (e.g. any method called on an entity obtained from a [`Project`]),
+/// the scoped client is returned, otherwise the client initialized by [`login`].
+pub async fn get_hopsworks_client() -> Result<HopsworksClient> {
+    if let Ok(client) = SCOPED_HOPSWORKS_CLIENT.try_with(HopsworksClient::clone) {
+        debug!("Access scoped Hopsworks Client");
+        return Ok(client);
     }
+    debug!("Access global Hopsworks Client");
+    HOPSWORKS_CLIENT.get().cloned().ok_or_else(|| {
+        HopsworksError::Auth(
+            "First use hopsworks::login() to initialize the Hopsworks client with your credentials."
+                .to_string(),
+        )
+    })
 }
 
-pub fn get_threaded_runtime() -> &'static Arc<tokio::runtime::Runtime> {
-    if THREADED_RUNTIME.get().is_some() {
-        THREADED_RUNTIME.get().unwrap()
-    } else {
-        let num_worker_threads = get_threaded_runtime_num_worker_threads();
-        let runtime = std::thread::Builder::new()
-            .name("start_hopsworks_runtime".to_string())
-            .spawn(move || {
-                tokio::runtime::Builder::new_multi_thread()
-                    .worker_threads(num_worker_threads)
-                    .enable_all()
-                    .thread_name_fn(|| {
-                        static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
-                        let id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
-                        format!("hopsworks-runtime-worker-{}", id)
-                    })
-                    .build()
-                    .unwrap()
-            })
-            .unwrap()
-            .join()
-            .unwrap();
-
-        THREADED_RUNTIME.set(Arc::new(runtime)).unwrap();
-        tracing::debug!(
-            "Initialized multi-threaded runtime with {} worker threads",
-            num_worker_threads
-        );
-        THREADED_RUNTIME.get().unwrap()
-    }
+/// Log out of the session of the client returned by [`get_hopsworks_client`],
+/// see [`HopsworksClient::logout`].
+pub async fn logout() -> Result<()> {
+    get_hopsworks_client().await?.logout().await
 }
 
-pub fn get_single_threaded_runtime() -> &'static Arc<tokio::runtime::Runtime> {
-    if SINGLE_THREADED_RUNTIME.get().is_some() {
-        SINGLE_THREADED_RUNTIME.get().unwrap()
-    } else {
-        SINGLE_THREADED_RUNTIME
-            .set(Arc::new(
-                tokio::runtime::Builder::new_multi_thread()
-                    .worker_threads(1)
-                    .enable_all()
-                    .build()
-                    .unwrap(),
-            ))
-            .unwrap();
-        SINGLE_THREADED_RUNTIME.get().unwrap()
-    }
+/// Client of the enclosing [`HopsworksClient::scope`], captured by entities when they are created.
+pub(crate) fn get_scoped_hopsworks_client() -> Option<HopsworksClient> {
+    SCOPED_HOPSWORKS_CLIENT
+        .try_with(HopsworksClient::clone)
+        .ok()
 }
 
-pub fn get_hopsworks_runtime(multithreaded: bool) -> &'static Arc<tokio::runtime::Runtime> {
-    if multithreaded {
-        get_threaded_runtime()
-    } else {
-        get_single_threaded_runtime()
-    }
-}
-
-pub async fn get_hopsworks_client() -> &'static HopsworksClient {
-    debug!("Access global Hopsworks Client");
-    match HOPSWORKS_CLIENT.get() {
-        Some(client) => client,
-        None => panic!(
-            "First use hopsworks::login() to initialize the Hopsworks client with your credentials."
-        ),
+/// Run `future` with `client` as the client returned by [`get_hopsworks_client`],
+/// falls back to the enclosing scope or the global client if `client` is `None`.
+pub async fn with_hopsworks_client<F: Future>(
+    client: Option<&HopsworksClient>,
+    future: F,
+) -> F::Output {
+    match client {
+        Some(client) => client.scope(future).await,
+        None => future.await,
     }
 }
 
+/// Login to Hopsworks and store the client globally, so that it is used by default
+/// by every entity of the returned [`Project`] and by functions not tied to an entity.
+///
+/// `runtime` is the runtime used by the blocking API of the session, see [`RuntimeConfig`].
+///
+/// Only one global client can be initialized per process, use [`connect`] to open
+/// additional sessions, e.g. to another project or cluster.
 pub async fn login(
     client_builder: Option<HopsworksClientBuilder>,
-    multithreaded: bool,
+    runtime: RuntimeConfig,
 ) -> Result<Project> {
     if HOPSWORKS_CLIENT.get().is_some() {
-        Err(color_eyre::eyre::eyre!(
-            "Hopsworks client already initialized"
+        Err(HopsworksError::Validation(
+            "Hopsworks client already initialized".to_string(),
         ))
     } else {
-        let rt = get_hopsworks_runtime(multithreaded).clone();
-        let _guard = rt.enter();
-
-        let client = client_builder.unwrap_or_default().build().await?;
+        let mut client = client_builder.unwrap_or_default().build().await?;
+        client.runtime = runtime.resolve()?;
         let init_client = HOPSWORKS_CLIENT.get_or_init(|| client);
         let project_dto = init_client.login().await?;
-        Ok(Project::from(&project_dto))
+        Ok(Project::new_from_dto(&project_dto, init_client.clone()))
     }
 }
+
+/// Login to Hopsworks with a client independent of the global one and of any other session.
+///
+/// The returned [`Project`] and every entity obtained from it (Feature Store, Feature Groups, Jobs, ...)
+/// send their requests with this client, so several sessions can be used side by side in the same process.
+pub async fn connect(
+    client_builder: Option<HopsworksClientBuilder>,
+    runtime: RuntimeConfig,
+) -> Result<Project> {
+    let mut client = client_builder.unwrap_or_default().build().await?;
+    client.runtime = runtime.resolve()?;
+    let project_dto = client.login().await?;
+    Ok(Project::new_from_dto(&project_dto, client))
+}
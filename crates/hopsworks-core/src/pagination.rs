@@ -0,0 +1,287 @@
+//! Pagination, sorting and filtering of the list endpoints of the Hopsworks REST API.
+//!
+//! List methods taking [`ListOptions`] fetch a single page, the `*_stream` methods walk
+//! the pages lazily and yield the items one by one, e.g. [`Job::executions_stream`][crate::platform::job::Job::executions_stream].
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use serde::Deserialize;
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::error::{HopsworksError, Result};
+use crate::{with_hopsworks_client, HopsworksClient};
+
+/// Number of items requested per page by the `*_stream` methods if not set in [`ListOptions`].
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Offset, limit, sorting and filtering of a list request.
+///
+/// Sort and filter expressions use the syntax of the Hopsworks REST API,
+/// e.g. `submissiontime:desc` or `state:RUNNING`.
+///
+/// # Example
+/// ```
+/// use hopsworks_core::ListOptions;
+///
+/// let options = ListOptions::new()
+///     .with_limit(50)
+///     .with_sort_by("submissiontime:desc")
+///     .with_filter_by("state:FAILED");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListOptions {
+    offset: Option<usize>,
+    limit: Option<usize>,
+    page_size: Option<usize>,
+    sort_by: Vec<String>,
+    filter_by: Vec<String>,
+}
+
+impl ListOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip the first `offset` items.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Return at most `limit` items, across all pages for the `*_stream` methods.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Number of items fetched per request by the `*_stream` methods, defaults to [`DEFAULT_PAGE_SIZE`].
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size.max(1));
+        self
+    }
+
+    /// Add a sort expression, e.g. `submissiontime:desc`. Expressions are applied in order.
+    pub fn with_sort_by(mut self, sort_by: &str) -> Self {
+        self.sort_by.push(sort_by.to_string());
+        self
+    }
+
+    /// Add a filter expression, e.g. `state:RUNNING`. All filters must match.
+    pub fn with_filter_by(mut self, filter_by: &str) -> Self {
+        self.filter_by.push(filter_by.to_string());
+        self
+    }
+
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size.unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+
+    pub fn sort_by(&self) -> &[String] {
+        &self.sort_by
+    }
+
+    pub fn filter_by(&self) -> &[String] {
+        &self.filter_by
+    }
+
+    /// Use `sort_by` if no sort expression is set.
+    pub(crate) fn with_default_sort_by(self, sort_by: &str) -> Self {
+        if self.sort_by.is_empty() {
+            self.with_sort_by(sort_by)
+        } else {
+            self
+        }
+    }
+
+    pub(crate) fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![];
+        if let Some(offset) = self.offset {
+            params.push(("offset", offset.to_string()));
+        }
+        if let Some(limit) = self.limit {
+            params.push(("limit", limit.to_string()));
+        }
+        if !self.sort_by.is_empty() {
+            params.push(("sort_by", self.sort_by.join(",")));
+        }
+        for filter_by in &self.filter_by {
+            params.push(("filter_by", filter_by.clone()));
+        }
+        params
+    }
+}
+
+/// Page of a list endpoint, either wrapped in `{"items": [...], "count": n}` or a bare array.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum PageDTO<T> {
+    List(Vec<T>),
+    Items {
+        // Omitted by the REST API if the page is empty
+        #[serde(default = "Vec::new")]
+        items: Vec<T>,
+        count: Option<usize>,
+    },
+}
+
+impl<T> PageDTO<T> {
+    pub(crate) fn count(&self) -> Option<usize> {
+        match self {
+            PageDTO::List(_) => None,
+            PageDTO::Items { count, .. } => *count,
+        }
+    }
+
+    pub(crate) fn map<U>(self, f: impl FnMut(T) -> U) -> PageDTO<U> {
+        match self {
+            PageDTO::List(items) => PageDTO::List(items.into_iter().map(f).collect()),
+            PageDTO::Items { items, count } => PageDTO::Items {
+                items: items.into_iter().map(f).collect(),
+                count,
+            },
+        }
+    }
+
+    pub(crate) fn items_mut(&mut self) -> &mut Vec<T> {
+        match self {
+            PageDTO::List(items) | PageDTO::Items { items, .. } => items,
+        }
+    }
+
+    pub(crate) fn into_items(self) -> Vec<T> {
+        match self {
+            PageDTO::List(items) | PageDTO::Items { items, .. } => items,
+        }
+    }
+}
+
+struct PageCursor {
+    offset: usize,
+    remaining: Option<usize>,
+    done: bool,
+}
+
+/// Lazily walk the pages returned by `fetch_page` for the offset and limit of each request,
+/// sending the requests with `client` or the global client if `None`.
+///
+/// Stops after a short page, after `count` items if the endpoint returns it,
+/// or after the first page if the endpoint ignores the limit.
+pub(crate) fn paginate<T, F, Fut>(
+    options: ListOptions,
+    client: Option<HopsworksClient>,
+    fetch_page: F,
+) -> impl Stream<Item = Result<T>> + Send + Unpin
+where
+    T: Send + 'static,
+    F: Fn(ListOptions) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<PageDTO<T>>> + Send + 'static,
+{
+    let cursor = PageCursor {
+        offset: options.offset.unwrap_or(0),
+        remaining: options.limit,
+        done: false,
+    };
+    let page_size = options.page_size();
+    let fetch_page = Arc::new(fetch_page);
+
+    stream::try_unfold(cursor, move |mut cursor| {
+        let page_options = options.clone();
+        let client = client.clone();
+        let fetch_page = Arc::clone(&fetch_page);
+        async move {
+            if cursor.done || cursor.remaining == Some(0) {
+                return Ok::<_, HopsworksError>(None);
+            }
+            let limit = cursor
+                .remaining
+                .map_or(page_size, |remaining| remaining.min(page_size));
+            let page = with_hopsworks_client(
+                client.as_ref(),
+                fetch_page(page_options.with_offset(cursor.offset).with_limit(limit)),
+            )
+            .await?;
+
+            let count = page.count();
+            let mut items = page.into_items();
+            if items.len() > limit {
+                // The endpoint does not paginate, all items were returned at once
+                cursor.done = true;
+                if let Some(remaining) = cursor.remaining {
+                    items.truncate(remaining);
+                }
+            } else {
+                cursor.offset += items.len();
+                cursor.done = items.len() < limit || count.is_some_and(|c| cursor.offset >= c);
+                cursor.remaining = cursor.remaining.map(|r| r - items.len());
+            }
+
+            if items.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some((items, cursor)))
+            }
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn fake_endpoint(
+        total: usize,
+        requests: Arc<Mutex<Vec<(usize, usize)>>>,
+    ) -> impl Fn(ListOptions) -> std::future::Ready<Result<PageDTO<usize>>> {
+        move |options: ListOptions| {
+            let offset = options.offset().unwrap();
+            let limit = options.limit().unwrap();
+            requests.lock().unwrap().push((offset, limit));
+            let items = (offset..total.min(offset + limit)).collect();
+            std::future::ready(Ok(PageDTO::Items {
+                items,
+                count: Some(total),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paginate() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let options = ListOptions::new().with_page_size(2).with_offset(1);
+        let items: Vec<usize> = paginate(options, None, fake_endpoint(6, requests.clone()))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, vec![1, 2, 3, 4, 5]);
+        assert_eq!(*requests.lock().unwrap(), vec![(1, 2), (3, 2), (5, 2)]);
+
+        requests.lock().unwrap().clear();
+        let options = ListOptions::new().with_page_size(2).with_limit(3);
+        let items: Vec<usize> = paginate(options, None, fake_endpoint(6, requests.clone()))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, vec![0, 1, 2]);
+        assert_eq!(*requests.lock().unwrap(), vec![(0, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn test_page_dto() {
+        let page: PageDTO<i32> = serde_json::from_str(r#"{"href": "x", "count": 0}"#).unwrap();
+        assert_eq!(page.count(), Some(0));
+        assert!(page.into_items().is_empty());
+        let page: PageDTO<i32> = serde_json::from_str("[1, 2]").unwrap();
+        assert_eq!(page.into_items(), vec![1, 2]);
+    }
+}
@@ -1,5 +1,5 @@
 //! Interact with your Project's File System
-use color_eyre::Result;
+use crate::error::Result;
 
 use crate::controller::platform::file_system::util::UploadOptions;
 
@@ -1,11 +1,16 @@
 //! Manage and Create Jobs on Hopsworks Cluster
-use color_eyre::Result;
+use crate::error::Result;
+use futures::{Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 
-use crate::controller::platform::job_execution::start_new_execution_for_named_job;
+use crate::controller::platform::job_execution::{
+    get_job_execution_page, get_job_executions, start_new_execution_for_named_job,
+};
 
 use super::job_execution::JobExecution;
 use crate::cluster_api::platform::job::JobDTO;
+use crate::pagination::{paginate, ListOptions};
+use crate::{get_scoped_hopsworks_client, with_hopsworks_client, HopsworksClient};
 /// Job on Hopsworks Cluster. A job is akin to a script which can be executed on the cluster.
 /// Jobs can be of different types, e.g. PySpark, Spark, Python, etc.
 /// Jobs can be executed on the cluster and the [`JobExecution`] can be monitored.
@@ -20,6 +25,8 @@ pub struct Job {
     creation_time: String,
     job_type: String,
     configuration: serde_json::Value,
+    #[serde(skip)]
+    client: Option<HopsworksClient>,
 }
 
 impl Job {
@@ -31,6 +38,7 @@ impl Job {
             creation_time: job_dto.creation_time,
             job_type: job_dto.job_type,
             configuration: job_dto.config,
+            client: get_scoped_hopsworks_client(),
         }
     }
 
@@ -53,6 +61,12 @@ impl Job {
     pub fn href(&self) -> &str {
         self.href.as_str()
     }
+
+    /// Get the client used to manage the job,
+    /// `None` if it uses the global client initialized by [`login`][crate::login].
+    pub fn hopsworks_client(&self) -> Option<&HopsworksClient> {
+        self.client.as_ref()
+    }
 }
 
 impl From<JobDTO> for Job {
@@ -87,15 +101,21 @@ impl Job {
     /// }
     /// ```
     pub async fn run(&self, args: Option<&str>, await_termination: bool) -> Result<JobExecution> {
-        let exec =
-            JobExecution::from(start_new_execution_for_named_job(self.name.as_str(), args).await?);
-        if await_termination {
-            exec.await_termination().await?;
-        }
-        Ok(exec)
+        with_hopsworks_client(self.hopsworks_client(), async {
+            let exec = JobExecution::from(
+                start_new_execution_for_named_job(self.name.as_str(), args).await?,
+            );
+            if await_termination {
+                exec.await_termination().await?;
+            }
+            Ok(exec)
+        })
+        .await
     }
 
     /// Get the [`JobExecution`]s of this job sorted by submission date starting with most recent.
+    /// The executions are fetched [`DEFAULT_PAGE_SIZE`][crate::pagination::DEFAULT_PAGE_SIZE] at a time,
+    /// use [`Job::executions_stream`] to process them without loading all of them in memory.
     ///
     /// # Returns
     /// * `Result<Vec<JobExecution>>` - The [`JobExecution`]s of this job.
@@ -117,12 +137,64 @@ impl Job {
     /// }
     /// ```
     pub async fn get_executions(&self) -> Result<Vec<JobExecution>> {
-        match crate::controller::platform::job_execution::get_job_executions(self.name.as_str())
+        self.executions_stream(ListOptions::default())
+            .try_collect()
             .await
-        {
-            Ok(executions) => Ok(executions.into_iter().map(JobExecution::from).collect()),
-            Err(e) => Err(e),
-        }
+    }
+
+    /// Get one page of the [`JobExecution`]s of this job, sorted by submission date
+    /// starting with most recent unless a sort expression is set.
+    ///
+    /// # Arguments
+    /// * `list_options` - Offset, limit, sorting and filtering of the page, e.g. `state:RUNNING`.
+    pub async fn get_executions_with_options(
+        &self,
+        list_options: &ListOptions,
+    ) -> Result<Vec<JobExecution>> {
+        with_hopsworks_client(self.hopsworks_client(), async {
+            Ok(get_job_executions(self.name.as_str(), list_options)
+                .await?
+                .into_iter()
+                .map(JobExecution::from)
+                .collect())
+        })
+        .await
+    }
+
+    /// Lazily iterate over the [`JobExecution`]s of this job, fetching [`ListOptions::page_size`]
+    /// executions per request.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use color_eyre::Result;
+    /// use futures::TryStreamExt;
+    /// use hopsworks_core::ListOptions;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///  let project = hopsworks_core::login(None, hopsworks_core::RuntimeConfig::Current).await?;
+    ///  let job = project.get_job("my_backfilling_job").await?;
+    ///  let mut executions = job.executions_stream(ListOptions::new().with_filter_by("state:FAILED"));
+    ///
+    ///  while let Some(execution) = executions.try_next().await? {
+    ///    println!("Failed execution {}", execution.id());
+    ///  }
+    ///  Ok(())
+    /// }
+    /// ```
+    pub fn executions_stream(
+        &self,
+        list_options: ListOptions,
+    ) -> impl Stream<Item = Result<JobExecution>> + Send + Unpin {
+        let job_name = self.name.clone();
+        paginate(list_options, self.client.clone(), move |list_options| {
+            let job_name = job_name.clone();
+            async move {
+                Ok(get_job_execution_page(job_name.as_str(), &list_options)
+                    .await?
+                    .map(JobExecution::from))
+            }
+        })
     }
 
     /// Update the job configuration. This will not affect running [`JobExecution`]s of this job.
@@ -148,8 +220,11 @@ impl Job {
     /// }
     /// ```
     pub async fn save(&self, updated_job_config: serde_json::Value) -> Result<Job> {
-        match crate::controller::platform::job::update_job(self.name.as_str(), updated_job_config)
-            .await
+        match with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::platform::job::update_job(self.name.as_str(), updated_job_config),
+        )
+        .await
         {
             Ok(job) => Ok(job),
             Err(e) => Err(e),
@@ -173,7 +248,12 @@ impl Job {
     /// }
     /// ```
     pub async fn delete(&self) -> Result<()> {
-        match crate::controller::platform::job::delete_job(self.name.as_str()).await {
+        match with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::platform::job::delete_job(self.name.as_str()),
+        )
+        .await
+        {
             Ok(_) => Ok(()),
             Err(e) => Err(e),
         }
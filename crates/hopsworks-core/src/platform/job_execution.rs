@@ -1,9 +1,10 @@
 use core::fmt;
 
-use color_eyre::Result;
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::cluster_api::platform::job_execution::JobExecutionDTO;
+use crate::{get_scoped_hopsworks_client, with_hopsworks_client, HopsworksClient};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct JobExecutionUserDTO {
@@ -20,6 +21,8 @@ pub struct JobExecution {
     job_name: String,
     state: JobExecutionState,
     submission_time: String,
+    #[serde(skip)]
+    client: Option<HopsworksClient>,
 }
 
 impl JobExecution {
@@ -32,6 +35,7 @@ impl JobExecution {
                 .expect("Job name not provided in job execution DTO"),
             state: execution_dto.state.into(),
             submission_time: execution_dto.submission_time,
+            client: get_scoped_hopsworks_client(),
         }
     }
 }
@@ -73,10 +77,13 @@ impl JobExecution {
     /// }
     /// ```
     pub async fn download_logs(&self, local_dir: Option<&str>) -> Result<()> {
-        crate::controller::platform::job_execution::download_job_execution_logs(
-            self.job_name.as_str(),
-            self.id,
-            local_dir,
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::platform::job_execution::download_job_execution_logs(
+                self.job_name.as_str(),
+                self.id,
+                local_dir,
+            ),
         )
         .await
     }
@@ -84,9 +91,12 @@ impl JobExecution {
     /// Delete the job execution from the hopsworks cluster. Note that if the job_execution is still running
     /// it will be terminated before deletion. The deletion cleans up the logs on the file system.
     pub async fn delete(&self) -> Result<()> {
-        crate::controller::platform::job_execution::delete_job_execution(
-            self.job_name.as_str(),
-            self.id,
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::platform::job_execution::delete_job_execution(
+                self.job_name.as_str(),
+                self.id,
+            ),
         )
         .await
     }
@@ -129,9 +139,12 @@ impl JobExecution {
     /// }
     /// ```
     pub async fn await_termination(&self) -> Result<()> {
-        crate::controller::platform::job_execution::await_termination(
-            self.job_name.as_str(),
-            self.id,
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::platform::job_execution::await_termination(
+                self.job_name.as_str(),
+                self.id,
+            ),
         )
         .await
     }
@@ -163,14 +176,17 @@ impl JobExecution {
     ///  Ok(())
     /// ```
     pub async fn stop(&self) -> Result<JobExecution> {
-        Ok(
-            crate::controller::platform::job_execution::stop_job_execution(
-                self.job_name.as_str(),
-                self.id,
+        with_hopsworks_client(self.hopsworks_client(), async {
+            Ok(
+                crate::controller::platform::job_execution::stop_job_execution(
+                    self.job_name.as_str(),
+                    self.id,
+                )
+                .await?
+                .into(),
             )
-            .await?
-            .into(),
-        )
+        })
+        .await
     }
 
     /// Get the current state of the job execution from the hopsworks cluster.
@@ -210,15 +226,16 @@ impl JobExecution {
     /// }
     /// ```
     pub async fn get_current_state(&self) -> Result<JobExecutionState> {
-        Ok(
+        Ok(with_hopsworks_client(
+            self.hopsworks_client(),
             crate::controller::platform::job_execution::get_job_execution_by_id(
                 self.job_name.as_str(),
                 self.id,
-            )
-            .await?
-            .state
-            .into(),
+            ),
         )
+        .await?
+        .state
+        .into())
     }
 
     /// Get the id of the [`JobExecution`].
@@ -243,6 +260,12 @@ impl JobExecution {
     pub fn submission_time(&self) -> String {
         self.submission_time.clone()
     }
+
+    /// Get the client used to monitor the job execution,
+    /// `None` if it uses the global client initialized by [`login`][crate::login].
+    pub fn hopsworks_client(&self) -> Option<&HopsworksClient> {
+        self.client.as_ref()
+    }
 }
 
 /// Represents the state of a job execution.
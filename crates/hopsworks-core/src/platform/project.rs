@@ -4,12 +4,17 @@
 //! it is intended to hold multiple [`FeatureGroup`][crate::feature_store::FeatureGroup]s and
 //! [`FeatureView`][crate::feature_store::FeatureView]s, the [`Job`]s to backfill
 //! or create [`TrainingDataset`][crate::feature_store::feature_view::training_dataset::TrainingDataset]s, managing [`User`][super::user::User]s access, etc...
-use color_eyre::Result;
+use crate::error::Result;
+use futures::{Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 
 use crate::cluster_api::platform::project::ProjectDTO;
 use crate::controller::feature_store;
 use crate::feature_store::FeatureStore;
+use crate::pagination::{paginate, ListOptions};
+use crate::{
+    get_hopsworks_client, get_scoped_hopsworks_client, with_hopsworks_client, HopsworksClient,
+};
 
 use super::job::Job;
 
@@ -17,6 +22,8 @@ use super::job::Job;
 pub struct Project {
     project_name: String,
     id: i32,
+    #[serde(skip)]
+    client: Option<HopsworksClient>,
 }
 
 impl From<&ProjectDTO> for Project {
@@ -24,11 +31,26 @@ impl From<&ProjectDTO> for Project {
         Self {
             project_name: project_dto.name.clone(),
             id: project_dto.id,
+            client: get_scoped_hopsworks_client(),
         }
     }
 }
 
 impl Project {
+    pub(crate) fn new_from_dto(project_dto: &ProjectDTO, client: HopsworksClient) -> Self {
+        Self {
+            project_name: project_dto.name.clone(),
+            id: project_dto.id,
+            client: Some(client),
+        }
+    }
+
+    /// Get the client used by the project and its entities,
+    /// `None` if they use the global client initialized by [`login`][crate::login].
+    pub fn hopsworks_client(&self) -> Option<&HopsworksClient> {
+        self.client.as_ref()
+    }
+
     /// Get the name of the project.
     pub fn name(&self) -> &str {
         self.project_name.as_str()
@@ -39,6 +61,32 @@ impl Project {
         self.id
     }
 
+    /// Get another project of the session, fetching its credentials and certificates.
+    ///
+    /// The returned project uses a [detached][HopsworksClient::detached] copy of the client,
+    /// this project and the entities obtained from it keep sending their requests to this project.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use color_eyre::Result;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///   let project = hopsworks_core::login(None, hopsworks_core::RuntimeConfig::Current).await?;
+    ///   let other_project = project.switch_to("my_other_project").await?;
+    ///   let fs = other_project.get_feature_store().await?;
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn switch_to(&self, project_name: &str) -> Result<Project> {
+        let client = match self.hopsworks_client() {
+            Some(client) => client.detached().await,
+            None => get_hopsworks_client().await?.detached().await,
+        };
+        let project_dto = client.set_project(project_name).await?;
+        Ok(Project::new_from_dto(&project_dto, client))
+    }
+
     /// Get the default [`FeatureStore`] for the project. Use it once the connection is established to start
     /// managing the Feature Store, e.g. creating/updating Feature Groups and Feature Views, inserting or reading
     /// feature data.
@@ -59,7 +107,11 @@ impl Project {
     /// }
     /// ```
     pub async fn get_feature_store(&self) -> Result<FeatureStore> {
-        feature_store::get_project_default_feature_store(self.project_name.as_str()).await
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            feature_store::get_project_default_feature_store(self.project_name.as_str()),
+        )
+        .await
     }
 
     /// Get a [`Job`] by name. Use it to manage the job, e.g. run it or update the configuration.
@@ -86,11 +138,16 @@ impl Project {
     /// }
     /// ```
     pub async fn get_job(&self, job_name: &str) -> Result<Job> {
-        crate::controller::platform::job::get_job_by_name(job_name).await
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::platform::job::get_job_by_name(job_name),
+        )
+        .await
     }
 
     /// Get a list of all [`Job`]s in the project.
     /// Use it to list all jobs in the project and manage them, e.g. get a job by name, run it, or update the configuration.
+    /// The jobs are fetched [`DEFAULT_PAGE_SIZE`][crate::pagination::DEFAULT_PAGE_SIZE] at a time, see [`Project::jobs_stream`].
     ///
     /// # Example
     /// ```no_run
@@ -106,7 +163,52 @@ impl Project {
     /// }
     /// ```
     pub async fn get_jobs(&self) -> Result<Vec<Job>> {
-        crate::controller::platform::job::get_job_list().await
+        self.jobs_stream(ListOptions::default()).try_collect().await
+    }
+
+    /// Get one page of the [`Job`]s in the project, sorted by submission date
+    /// starting with most recent unless a sort expression is set.
+    ///
+    /// # Arguments
+    /// * `list_options` - Offset, limit, sorting and filtering of the page, e.g. `jobtype:PYTHON`.
+    pub async fn get_jobs_with_options(&self, list_options: &ListOptions) -> Result<Vec<Job>> {
+        with_hopsworks_client(
+            self.hopsworks_client(),
+            crate::controller::platform::job::get_job_list(list_options),
+        )
+        .await
+    }
+
+    /// Lazily iterate over the [`Job`]s in the project, fetching [`ListOptions::page_size`] jobs per request.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use color_eyre::Result;
+    /// use futures::TryStreamExt;
+    /// use hopsworks_core::ListOptions;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///   let project = hopsworks_core::login(None, hopsworks_core::RuntimeConfig::Current).await?;
+    ///   let mut jobs = project.jobs_stream(ListOptions::new().with_page_size(20));
+    ///
+    ///   while let Some(job) = jobs.try_next().await? {
+    ///     println!("Job: {}", job.name());
+    ///   }
+    ///   Ok(())
+    /// }
+    /// ```
+    pub fn jobs_stream(
+        &self,
+        list_options: ListOptions,
+    ) -> impl Stream<Item = Result<Job>> + Send + Unpin {
+        paginate(
+            list_options,
+            self.client.clone(),
+            |list_options| async move {
+                crate::controller::platform::job::get_job_page(&list_options).await
+            },
+        )
     }
 }
 
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::error::{HopsworksError, Result};
+
 pub mod read;
 /// Configuration Templates for Hopsworks CLI
 
@@ -52,10 +54,47 @@ pub struct HopsworksProfileConfig {
 /// This is the main configuration file for the Hopsworks CLI and SDK
 /// It contains the default profiles, clusters and users and sets the default to be used,
 /// when no environment variable is set.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct HopsworksTomlConfig {
     /// Name of the default profile to be used, when no environment variable is set
     pub default_profile: Option<String>,
     /// List of profiles to apply when running the CLI
+    #[serde(default)]
     pub profiles: HashMap<String, HopsworksProfileConfig>,
+    /// Values of environment variables (e.g. `HOPSWORKS_API_KEY`), used when they are not set
+    /// in the process environment
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+impl HopsworksTomlConfig {
+    /// Value of the environment variable `key`, falling back to the `[env]` table.
+    pub fn get_env_var(&self, key: &str) -> Option<String> {
+        std::env::var(key)
+            .ok()
+            .or_else(|| self.env.get(key).cloned())
+    }
+
+    /// Remove and return the profile named `profile_name`, or the default profile if None.
+    /// Returns None if no profile is specified and no default profile is set.
+    pub fn take_profile(
+        &mut self,
+        profile_name: Option<&str>,
+    ) -> Result<Option<HopsworksProfileConfig>> {
+        let profile_name = match profile_name {
+            Some(name) => name.to_string(),
+            None => match self.default_profile.clone() {
+                Some(name) => name,
+                None => return Ok(None),
+            },
+        };
+
+        match self.profiles.remove(&profile_name) {
+            Some(profile) => Ok(Some(profile)),
+            None => Err(HopsworksError::NotFound(format!(
+                "No profile found with name: {:?}",
+                profile_name
+            ))),
+        }
+    }
 }
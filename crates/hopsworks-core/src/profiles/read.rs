@@ -1,5 +1,6 @@
-use color_eyre::eyre::Result;
+use crate::error::{HopsworksError, Result};
 use directories::BaseDirs;
+use std::path::Path;
 use tracing::debug;
 
 use super::{HopsworksProfileConfig, HopsworksTomlConfig};
@@ -26,39 +27,30 @@ pub fn get_hopsworks_profiles_config_file() -> Result<std::path::PathBuf> {
 
         Ok(config_file)
     } else {
-        Err(color_eyre::eyre::eyre!("Failed to get config directory exists, check that directories::BaseDirs::new() is supported on this platform."))
+        Err(HopsworksError::Validation("Failed to get config directory exists, check that directories::BaseDirs::new() is supported on this platform.".to_string()))
     }
 }
 
-pub fn get_hopsworks_profiles() -> Result<HopsworksTomlConfig> {
-    let config_file = get_hopsworks_profiles_config_file()?;
+pub fn read_hopsworks_toml_config(config_file: &Path) -> Result<HopsworksTomlConfig> {
     let config_str = std::fs::read_to_string(config_file)?;
     debug!("Config file content: {:?}", config_str);
-    let profiles: HopsworksTomlConfig = toml::from_str(&config_str)?;
-    debug!("Available profiles: {:?}", profiles);
+    let config: HopsworksTomlConfig = toml::from_str(&config_str)?;
+    debug!("Available profiles: {:?}", config.profiles.keys());
 
-    Ok(profiles)
+    Ok(config)
 }
 
-pub fn get_hopsworks_profile(profile_name: Option<&str>) -> Result<HopsworksProfileConfig> {
-    let mut profiles = get_hopsworks_profiles()?;
-    let profile_name = match profile_name {
-        Some(name) => name,
-        None => match profiles.default_profile {
-            Some(ref name) => name,
-            None => {
-                return Err(color_eyre::eyre::eyre!(
-                    "No profile specified and no default profile found."
-                ))
-            }
-        },
-    };
+pub fn get_hopsworks_profiles() -> Result<HopsworksTomlConfig> {
+    let config_file = get_hopsworks_profiles_config_file()?;
+    read_hopsworks_toml_config(&config_file)
+}
 
-    match profiles.profiles.remove(profile_name) {
-        Some(profile) => Ok(profile),
-        None => Err(color_eyre::eyre::eyre!(
-            "No profile found with name: {:?}",
-            profile_name
-        )),
-    }
+pub fn get_hopsworks_profile(profile_name: Option<&str>) -> Result<HopsworksProfileConfig> {
+    get_hopsworks_profiles()?
+        .take_profile(profile_name)?
+        .ok_or_else(|| {
+            HopsworksError::Validation(
+                "No profile specified and no default profile found.".to_string(),
+            )
+        })
 }
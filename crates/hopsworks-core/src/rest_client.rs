@@ -1,6 +1,16 @@
-use color_eyre::Result;
-use reqwest::{header::HeaderValue, Method};
-use std::{path::Path, sync::Arc};
+use crate::error::{HopsworksError, Result};
+use rand::Rng;
+use reqwest::{
+    header::{HeaderName, HeaderValue, RETRY_AFTER},
+    Method, StatusCode,
+};
+use serde::Serialize;
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 
@@ -8,7 +18,15 @@ use crate::cluster_api::platform::project::{
     service::get_project_and_user_list,
     {ProjectAndUserDTO, ProjectDTO},
 };
-use crate::controller::platform::credentials::write_locally_project_credentials_on_login;
+use crate::controller::platform::credentials::{
+    delete_locally_project_credentials, write_locally_project_credentials_on_login,
+};
+use crate::profiles::{
+    read::{get_hopsworks_profiles_config_file, read_hopsworks_toml_config},
+    HopsworksTomlConfig,
+};
+use crate::runtime::RuntimeConfig;
+use crate::SCOPED_HOPSWORKS_CLIENT;
 
 pub const DEFAULT_CLIENT_URL: &str = "https://c.app.hopsworks.ai/hopsworks-api/api";
 pub const DEFAULT_CLIENT_CERT_DIR: &str = "/tmp/";
@@ -16,12 +34,231 @@ pub const DEFAULT_ENV_HOPSWORKS_API_KEY: &str = "HOPSWORKS_API_KEY";
 pub const DEFAULT_ENV_HOPSWORKS_PROJECT_NAME: &str = "HOPSWORKS_PROJECT_NAME";
 pub const DEFAULT_ENV_HOPSWORKS_URL: &str = "HOPSWORKS_URL";
 
+/// Policy to retry requests failing with a transient error, e.g. a 503 response
+/// while the cluster is restarting a service or a 429 response when rate limited.
+///
+/// Only requests with an idempotent method are retried, as replaying a request which
+/// reached the server could otherwise create the same resource twice.
+/// The delay between two attempts grows exponentially from `initial_backoff` up to `max_backoff`,
+/// unless the server asks to wait longer with a `Retry-After` header.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    respect_retry_after: bool,
+    retry_statuses: Vec<StatusCode>,
+    idempotent_methods: Vec<Method>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            respect_retry_after: true,
+            retry_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            idempotent_methods: vec![
+                Method::GET,
+                Method::HEAD,
+                Method::OPTIONS,
+                Method::PUT,
+                Method::DELETE,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        RetryPolicy::default()
+    }
+
+    /// Policy sending every request exactly once.
+    pub fn disabled() -> Self {
+        RetryPolicy::default().with_max_attempts(1)
+    }
+
+    /// Total number of attempts, including the first one.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Backoff bounds, the delay before the n-th retry is `initial_backoff * multiplier^(n-1)` capped at `max_backoff`.
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff.max(initial_backoff);
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Randomize each delay between half and the full backoff, to avoid clients retrying in lockstep.
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Wait for the delay given by the `Retry-After` header of the response, when present.
+    pub fn with_respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    pub fn with_retry_statuses(mut self, retry_statuses: Vec<StatusCode>) -> Self {
+        self.retry_statuses = retry_statuses;
+        self
+    }
+
+    /// Methods considered safe to replay, e.g. add `Method::POST` if your pipeline can tolerate duplicates.
+    pub fn with_idempotent_methods(mut self, idempotent_methods: Vec<Method>) -> Self {
+        self.idempotent_methods = idempotent_methods;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    fn is_retryable_method(&self, method: &Method) -> bool {
+        self.max_attempts > 1 && self.idempotent_methods.contains(method)
+    }
+
+    fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    fn is_retryable_error(&self, error: &reqwest::Error) -> bool {
+        error.is_connect() || error.is_timeout()
+    }
+
+    /// Delay to wait before the retry following the given (1-based) failed attempt,
+    /// also used to retry other operations than requests, e.g. Kafka deliveries.
+    pub fn attempt_backoff(&self, attempt: u32) -> Duration {
+        let exponential = self.initial_backoff.as_secs_f64()
+            * self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        let backoff = Duration::from_secs_f64(exponential.min(self.max_backoff.as_secs_f64()));
+        if self.jitter {
+            backoff.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+        } else {
+            backoff
+        }
+    }
+
+    /// Same as [`RetryPolicy::attempt_backoff`], waiting at least `retry_after` if respected.
+    fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let backoff = self.attempt_backoff(attempt);
+        match retry_after {
+            Some(retry_after) if self.respect_retry_after => {
+                backoff.max(retry_after.min(self.max_backoff))
+            }
+            _ => backoff,
+        }
+    }
+}
+
+/// Parse the `Retry-After` header, only the delay in seconds form is supported.
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// TLS settings of the connections to the cluster, shared by the REST API,
+/// Arrow Flight and OpenSearch clients.
+///
+/// Certificates are verified against the system trust store by default. Clusters using a
+/// self-signed certificate need either a custom CA bundle, e.g. the `ca_chain.pem` written in
+/// the project certificate directory on login, or to explicitly accept invalid certificates.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    ca_cert_path: Option<PathBuf>,
+    accept_invalid_certs: bool,
+}
+
+impl TlsConfig {
+    pub fn new() -> Self {
+        TlsConfig::default()
+    }
+
+    /// Trust the CA certificate(s) of the PEM bundle at `ca_cert_path` in addition to the system ones.
+    pub fn with_ca_cert_path(mut self, ca_cert_path: impl Into<PathBuf>) -> Self {
+        self.ca_cert_path = Some(ca_cert_path.into());
+        self
+    }
+
+    /// Disable certificate verification. This is insecure and should only be used
+    /// to connect to a development cluster.
+    pub fn with_danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
+    pub fn ca_cert_path(&self) -> Option<&Path> {
+        self.ca_cert_path.as_deref()
+    }
+
+    pub fn accept_invalid_certs(&self) -> bool {
+        self.accept_invalid_certs
+    }
+
+    /// Read the PEM bundle of the custom CA, if any.
+    pub fn read_ca_cert_pem(&self) -> Result<Option<Vec<u8>>> {
+        match self.ca_cert_path() {
+            Some(path) => std::fs::read(path).map(Some).map_err(|e| {
+                HopsworksError::Validation(format!(
+                    "Failed to read CA certificate bundle {}: {}",
+                    path.display(),
+                    e
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    fn build_reqwest_client(&self) -> Result<reqwest::Client> {
+        let mut builder = reqwest::ClientBuilder::new();
+        if let Some(ca_cert_pem) = self.read_ca_cert_pem()? {
+            for certificate in reqwest::Certificate::from_pem_bundle(&ca_cert_pem)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        if self.accept_invalid_certs {
+            warn!("TLS certificate verification is disabled for the Hopsworks client.");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        Ok(builder.build()?)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HopsworksClientBuilder {
     url: String,
     api_key: Option<String>,
     cert_dir: String,
     project_name: Option<String>,
+    retry_policy: RetryPolicy,
+    tls_config: TlsConfig,
 }
 
 impl Default for HopsworksClientBuilder {
@@ -31,6 +268,8 @@ impl Default for HopsworksClientBuilder {
             api_key: None,
             cert_dir: DEFAULT_CLIENT_CERT_DIR.to_string(),
             project_name: None,
+            retry_policy: RetryPolicy::default(),
+            tls_config: TlsConfig::default(),
         }
     }
 }
@@ -66,17 +305,20 @@ impl HopsworksClientBuilder {
             api_key,
             cert_dir: DEFAULT_CLIENT_CERT_DIR.to_string(),
             project_name,
+            retry_policy: RetryPolicy::default(),
+            tls_config: TlsConfig::default(),
         }
     }
 
     /// Create a new HopsworksClientBuilder with the following override priority:
     ///   - values provided as args will always take precedence over loaded config or environment variables.
     ///   - values set in the environment variables will be used if not provided as args
-    ///   - (todo) values set in the provided toml config filepath will be used if not provided as args or in the environment variables
     ///   - static hard-coded default values will be used as last resort where possible
     ///
+    /// Use [`HopsworksClientBuilder::from_config_file`] to also load values from a toml config file.
+    ///
     /// Note:
-    ///   - A valid api_key is required and must be provided as an argument, environment variable or set in config toml.
+    ///   - A valid api_key is required and must be provided as an argument or environment variable.
     pub fn new_provided_or_from_env(
         api_key_value: Option<&str>,
         url: Option<&str>,
@@ -94,10 +336,60 @@ impl HopsworksClientBuilder {
             api_key: None,
             cert_dir: DEFAULT_CLIENT_CERT_DIR.to_string(),
             project_name: project_name.map(|s| s.to_string()),
+            retry_policy: RetryPolicy::default(),
+            tls_config: TlsConfig::default(),
         }
         .with_api_key(&api_key)
     }
 
+    /// Create a new HopsworksClientBuilder from a profile of the `hopsworks-profiles.toml` file
+    /// in the user config directory, see [`HopsworksClientBuilder::from_config_file`].
+    pub fn from_profile(profile_name: Option<&str>) -> Result<Self> {
+        let config_file = get_hopsworks_profiles_config_file()?;
+        HopsworksClientBuilder::from_config_file(config_file, profile_name)
+    }
+
+    /// Create a new HopsworksClientBuilder from a toml config file with the following override priority:
+    ///   - values provided with the `with_*` methods of the returned builder
+    ///   - values set in the `HOPSWORKS_*` environment variables
+    ///   - values set for the same variables in the `[env]` table of the config file
+    ///   - values of the profile `profile_name`, or of the `default_profile` of the config file if None
+    ///   - static hard-coded default values will be used as last resort where possible
+    ///
+    /// Note:
+    ///   - A config file without profiles, e.g. `configs/config-template.toml`, only sets the `[env]` table.
+    pub fn from_config_file(path: impl AsRef<Path>, profile_name: Option<&str>) -> Result<Self> {
+        let config = read_hopsworks_toml_config(path.as_ref())?;
+        HopsworksClientBuilder::from_toml_config(config, profile_name)
+    }
+
+    fn from_toml_config(
+        mut config: HopsworksTomlConfig,
+        profile_name: Option<&str>,
+    ) -> Result<Self> {
+        let profile = config.take_profile(profile_name)?;
+        let mut builder = HopsworksClientBuilder::default();
+
+        if let Some(url) = config
+            .get_env_var(DEFAULT_ENV_HOPSWORKS_URL)
+            .or_else(|| profile.as_ref().map(|p| p.cluster.get_api_url()))
+        {
+            builder.url = url;
+        }
+        builder.api_key = config
+            .get_env_var(DEFAULT_ENV_HOPSWORKS_API_KEY)
+            .or_else(|| profile.as_ref().map(|p| p.user.api_key.clone()));
+        builder.project_name = config
+            .get_env_var(DEFAULT_ENV_HOPSWORKS_PROJECT_NAME)
+            .or_else(|| profile.map(|p| p.project.name));
+
+        debug!(
+            "HopsworksClientBuilder: New client from toml config.\n url: {}\n  project_name: {:?}",
+            builder.url, builder.project_name
+        );
+        Ok(builder)
+    }
+
     pub fn with_url(mut self, url: &str) -> Self {
         self.url = url.to_string();
         self
@@ -118,53 +410,105 @@ impl HopsworksClientBuilder {
         self
     }
 
+    /// TLS settings of the connections to the cluster, certificates are verified by default.
+    pub fn with_tls_config(mut self, tls_config: TlsConfig) -> Self {
+        self.tls_config = tls_config;
+        self
+    }
+
+    /// Trust the CA certificate(s) of the PEM bundle at `ca_cert_path`,
+    /// see [`TlsConfig::with_ca_cert_path`].
+    pub fn with_ca_cert_path(mut self, ca_cert_path: &str) -> Self {
+        self.tls_config = self.tls_config.with_ca_cert_path(ca_cert_path);
+        self
+    }
+
+    /// Opt into insecure mode, see [`TlsConfig::with_danger_accept_invalid_certs`].
+    pub fn with_danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.tls_config = self
+            .tls_config
+            .with_danger_accept_invalid_certs(accept_invalid_certs);
+        self
+    }
+
+    /// Retry policy applied to every request sent to the Hopsworks REST API,
+    /// use [`RetryPolicy::disabled`] to fail on the first error.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     pub async fn build(self) -> Result<HopsworksClient> {
-        let api_key = std::env::var(DEFAULT_ENV_HOPSWORKS_API_KEY)
-            .ok()
-            .or(self.api_key);
+        let api_key = self
+            .api_key
+            .or_else(|| std::env::var(DEFAULT_ENV_HOPSWORKS_API_KEY).ok());
         if api_key.is_none() {
-            return Err(color_eyre::eyre::eyre!(
-                "No API key provided. Provide an API key using the {} environment variable or the with_api_key() method of the HopsworksClientBuilder.",
+            return Err(HopsworksError::Auth(format!(
+                "No API key provided. Provide an API key using the {} environment variable or the with_api_key() method.",
                 DEFAULT_ENV_HOPSWORKS_API_KEY
-            ));
+            )));
         }
 
         debug!(
             "HopsworksClientBuilder: Building client with url: {}",
             self.url.as_str()
         );
-        let client = HopsworksClient::new(self.url, self.cert_dir);
+        let mut client = HopsworksClient::new(self.url, self.cert_dir);
+        client.client = self.tls_config.build_reqwest_client()?;
+        client.tls_config = self.tls_config;
+        client.retry_policy = Arc::new(self.retry_policy);
 
         client.set_api_key(api_key.as_deref()).await;
         Ok(client)
     }
 }
 
-#[derive(Debug, Clone)]
+/// Client holding the credentials and project of a Hopsworks session.
+///
+/// Cloning is cheap and clones share the same session state.
+#[derive(Clone)]
 pub struct HopsworksClient {
     client: reqwest::Client,
     pub(crate) url: String,
     pub(crate) cert_dir: Arc<Mutex<String>>,
+    /// Directory holding one sub-directory of certificates per project.
+    base_cert_dir: String,
     api_key: Arc<Mutex<Option<HeaderValue>>>,
     project_id: Arc<Mutex<Option<i32>>>,
     cert_key: Arc<Mutex<Option<String>>>,
     project_name: Arc<Mutex<Option<String>>>,
+    retry_policy: Arc<RetryPolicy>,
+    tls_config: TlsConfig,
+    pub(crate) runtime: RuntimeConfig,
+}
+
+impl std::fmt::Debug for HopsworksClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Keep the api key and cert key out of logs and debug output of the entities holding the client
+        f.debug_struct("HopsworksClient")
+            .field("url", &self.url)
+            .field(
+                "project_id",
+                &self.project_id.try_lock().ok().and_then(|id| *id),
+            )
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for HopsworksClient {
     fn default() -> Self {
         HopsworksClient {
-            // client: reqwest::Client::new(),
-            client: reqwest::ClientBuilder::new()
-                .danger_accept_invalid_certs(true)
-                .build()
-                .unwrap(),
+            client: reqwest::Client::new(),
             url: DEFAULT_CLIENT_URL.to_string(),
             cert_dir: Arc::new(Mutex::new(DEFAULT_CLIENT_CERT_DIR.to_string())),
+            base_cert_dir: DEFAULT_CLIENT_CERT_DIR.to_string(),
             api_key: Arc::new(Mutex::new(None)),
             project_id: Arc::new(Mutex::new(None)),
             cert_key: Arc::new(Mutex::new(None)),
             project_name: Arc::new(Mutex::new(None)),
+            retry_policy: Arc::new(RetryPolicy::default()),
+            tls_config: TlsConfig::default(),
+            runtime: RuntimeConfig::default(),
         }
     }
 }
@@ -184,7 +528,8 @@ impl HopsworksClient {
                 "HopsworksClient: New client overrides default cert_dir with: {}",
                 cert_dir
             );
-            client.cert_dir = Arc::new(Mutex::new(cert_dir));
+            client.cert_dir = Arc::new(Mutex::new(cert_dir.clone()));
+            client.base_cert_dir = cert_dir;
         }
         client
     }
@@ -193,25 +538,64 @@ impl HopsworksClient {
         HopsworksClientBuilder::new()
     }
 
+    /// Run `future` with this client as the one returned by [`get_hopsworks_client`][crate::get_hopsworks_client],
+    /// instead of the global client initialized by [`login`][crate::login].
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        SCOPED_HOPSWORKS_CLIENT.scope(self.clone(), future).await
+    }
+
     pub async fn login(&self) -> Result<ProjectDTO> {
+        self.scope(self.login_in_scope()).await
+    }
+
+    async fn login_in_scope(&self) -> Result<ProjectDTO> {
         info!("Connecting to Hopsworks...");
 
         if self.get_api_key().lock().await.is_none() {
-            panic!("Use an API key to authenticate. You can provide it via the {} environment variable or via HopsworksClientBuilder.", DEFAULT_ENV_HOPSWORKS_API_KEY)
+            return Err(HopsworksError::Auth(format!(
+                "Use an API key to authenticate. You can provide it via the {} environment variable or via HopsworksClientBuilder.",
+                DEFAULT_ENV_HOPSWORKS_API_KEY
+            )));
         }
 
-        let project = self
-            .get_the_project_or_default(self.get_project_name().lock().await.as_deref())
-            .await?;
+        let project_name = self.get_project_name().lock().await.clone();
+        self.set_project_in_scope(project_name.as_deref()).await
+    }
+
+    /// Copy of this client with its own session state, starting from the current api key and project.
+    /// Unlike clones, changing the project or logging out of the copy leaves this client untouched.
+    pub async fn detached(&self) -> HopsworksClient {
+        HopsworksClient {
+            cert_dir: Arc::new(Mutex::new(self.get_cert_dir().lock().await.clone())),
+            api_key: Arc::new(Mutex::new(self.get_api_key().lock().await.clone())),
+            project_id: Arc::new(Mutex::new(*self.get_project_id().lock().await)),
+            cert_key: Arc::new(Mutex::new(self.get_cert_key().lock().await.clone())),
+            project_name: Arc::new(Mutex::new(self.get_project_name().lock().await.clone())),
+            ..self.clone()
+        }
+    }
+
+    /// Switch the session to the project `project_name`, fetching its credentials and certificates.
+    ///
+    /// Clones of this client share the session, entities obtained from the previous project
+    /// will send their requests to the new one. Use [`HopsworksClient::detached`] to keep them
+    /// on the previous project.
+    pub async fn set_project(&self, project_name: &str) -> Result<ProjectDTO> {
+        self.scope(self.set_project_in_scope(Some(project_name)))
+            .await
+    }
+
+    async fn set_project_in_scope(&self, project_name: Option<&str>) -> Result<ProjectDTO> {
+        let project = self.get_the_project_or_default(project_name).await?;
         self.set_project_id(Some(project.id)).await;
+        self.set_project_name(Some(project.name.clone())).await;
         info!(
             "Connected to Hopsworks project : {} at url {} !",
             project.name, self.url
         );
 
-        let cert_dir = self.get_cert_dir().lock().await.clone();
         self.set_cert_dir(
-            Path::new(cert_dir.as_str())
+            Path::new(self.base_cert_dir.as_str())
                 .join(project.name.as_str())
                 .to_str()
                 .unwrap()
@@ -227,6 +611,22 @@ impl HopsworksClient {
         Ok(project)
     }
 
+    /// End the session: remove the API key and project of the client and delete
+    /// the certificates written in the project `cert_dir` on login.
+    pub async fn logout(&self) -> Result<()> {
+        if self.get_project_id().lock().await.is_some() {
+            delete_locally_project_credentials(self.get_cert_dir().lock().await.as_str()).await?;
+        }
+        self.set_cert_dir(self.base_cert_dir.clone()).await;
+        self.set_cert_key(None).await;
+        self.set_project_id(None).await;
+        self.set_project_name(None).await;
+        self.set_api_key(None).await;
+        info!("Logged out of Hopsworks at url {}.", self.url);
+
+        Ok(())
+    }
+
     fn get_api_key(&self) -> Arc<Mutex<Option<HeaderValue>>> {
         Arc::clone(&self.api_key)
     }
@@ -243,6 +643,16 @@ impl HopsworksClient {
         Arc::clone(&self.project_name)
     }
 
+    /// TLS settings to reuse when connecting to other services of the cluster.
+    pub fn get_tls_config(&self) -> &TlsConfig {
+        &self.tls_config
+    }
+
+    /// Runtime used by the blocking API for this session, set on [`login`][crate::login].
+    pub fn runtime(&self) -> &RuntimeConfig {
+        &self.runtime
+    }
+
     pub fn get_cert_dir(&self) -> Arc<Mutex<String>> {
         Arc::clone(&self.cert_dir)
     }
@@ -252,6 +662,11 @@ impl HopsworksClient {
         *self.get_project_id().lock().await = project_id;
     }
 
+    async fn set_project_name(&self, project_name: Option<String>) {
+        debug!("Setting HopsworksClient project name to {:?}", project_name);
+        *self.get_project_name().lock().await = project_name;
+    }
+
     async fn set_cert_key(&self, cert_key: Option<String>) {
         debug!("Setting HopsworksClient cert_key");
         *self.get_cert_key().lock().await = cert_key;
@@ -281,6 +696,7 @@ impl HopsworksClient {
         }
     }
 
+    /// Build a request to the Hopsworks REST API, sending it applies the client [`RetryPolicy`].
     #[tracing::instrument(skip(self))]
     pub async fn request(
         &self,
@@ -288,18 +704,20 @@ impl HopsworksClient {
         url: &str,
         with_authorization: bool,
         with_project_id: bool,
-    ) -> Result<reqwest::RequestBuilder> {
-        let request_builder = self
-            .client
-            .request(method, self.endpoint_url(url, with_project_id).await?);
+    ) -> Result<HopsworksRequestBuilder> {
+        let mut request_builder = self.client.request(
+            method.clone(),
+            self.endpoint_url(url, with_project_id).await?,
+        );
         if with_authorization {
-            Ok(
-                request_builder
-                    .header("authorization", self.get_authorization_header_value().await),
-            )
-        } else {
-            Ok(request_builder)
+            request_builder = request_builder
+                .header("authorization", self.get_authorization_header_value().await);
         }
+        Ok(HopsworksRequestBuilder {
+            builder: request_builder,
+            method,
+            retry_policy: Arc::clone(&self.retry_policy),
+        })
     }
 
     async fn get_authorization_header_value(&self) -> HeaderValue {
@@ -323,11 +741,9 @@ impl HopsworksClient {
         } else {
             // with_project_id only applies for relative url
             if with_project_id {
-                let project_id = self
-                    .get_project_id()
-                    .lock()
-                    .await
-                    .expect("Project id not set, please login first.");
+                let project_id = self.get_project_id().lock().await.ok_or_else(|| {
+                    HopsworksError::Auth("Project id not set, please login first.".to_string())
+                })?;
 
                 full_url = format!("{}/project/{}/{url}", self.url.clone(), project_id,)
             } else {
@@ -342,7 +758,10 @@ impl HopsworksClient {
         let projects: Vec<ProjectAndUserDTO> = get_project_and_user_list().await?;
 
         if projects.is_empty() {
-            panic!("No project found for this user, please create a project in the UI first.");
+            Err(HopsworksError::NotFound(
+                "No project found for this user, please create a project in the UI first."
+                    .to_string(),
+            ))
         } else if project_name.is_none() {
             Ok(projects[0].project.to_owned())
         } else {
@@ -353,16 +772,110 @@ impl HopsworksClient {
             if let Some(the_project) = opt_match {
                 Ok(the_project.project.to_owned())
             } else {
-                panic!("No project with name {} found for this user.", name);
+                Err(HopsworksError::NotFound(format!(
+                    "No project with name {} found for this user.",
+                    name
+                )))
             }
         }
     }
 }
 
+/// Wrapper around [`reqwest::RequestBuilder`] retrying transient failures on [`send`][HopsworksRequestBuilder::send].
+pub struct HopsworksRequestBuilder {
+    builder: reqwest::RequestBuilder,
+    method: Method,
+    retry_policy: Arc<RetryPolicy>,
+}
+
+impl HopsworksRequestBuilder {
+    pub fn header(mut self, key: HeaderName, value: HeaderValue) -> Self {
+        self.builder = self.builder.header(key, value);
+        self
+    }
+
+    pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+        self.builder = self.builder.query(query);
+        self
+    }
+
+    pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+        self.builder = self.builder.json(json);
+        self
+    }
+
+    pub fn form<T: Serialize + ?Sized>(mut self, form: &T) -> Self {
+        self.builder = self.builder.form(form);
+        self
+    }
+
+    pub fn body<T: Into<reqwest::Body>>(mut self, body: T) -> Self {
+        self.builder = self.builder.body(body);
+        self
+    }
+
+    pub fn build(self) -> Result<reqwest::Request> {
+        Ok(self.builder.build()?)
+    }
+
+    /// Send the request, retrying according to the client [`RetryPolicy`].
+    /// Non-success responses which are not retried are returned as is for the caller to handle.
+    pub async fn send(self) -> Result<reqwest::Response> {
+        let policy = self.retry_policy;
+        if !policy.is_retryable_method(&self.method) {
+            return Ok(self.builder.send().await?);
+        }
+
+        let mut attempt = 1;
+        loop {
+            // Streaming bodies cannot be replayed, send those only once
+            let Some(builder) = self.builder.try_clone() else {
+                return Ok(self.builder.send().await?);
+            };
+            let delay = match builder.send().await {
+                Ok(response)
+                    if attempt < policy.max_attempts
+                        && policy.is_retryable_status(response.status()) =>
+                {
+                    let delay = policy.backoff(attempt, retry_after(&response));
+                    warn!(
+                        method = %self.method,
+                        url = %response.url(),
+                        status = %response.status(),
+                        attempt,
+                        max_attempts = policy.max_attempts,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying Hopsworks request after transient error response"
+                    );
+                    delay
+                }
+                Err(error)
+                    if attempt < policy.max_attempts && policy.is_retryable_error(&error) =>
+                {
+                    let delay = policy.backoff(attempt, None);
+                    warn!(
+                        method = %self.method,
+                        url = ?error.url().map(|url| url.as_str()),
+                        error = %error,
+                        attempt,
+                        max_attempts = policy.max_attempts,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying Hopsworks request after transport error"
+                    );
+                    delay
+                }
+                result => return Ok(result?),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use color_eyre::Result;
+    use crate::error::Result;
 
     #[tokio::test]
     async fn test_builder() {
@@ -386,6 +899,66 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_logout() -> Result<()> {
+        let base_cert_dir = std::env::temp_dir().join("hopsworks_test_logout");
+        let cert_dir = base_cert_dir.join("my_project");
+        std::fs::create_dir_all(&cert_dir)?;
+        std::fs::write(cert_dir.join("client_cert.pem"), "cert")?;
+
+        let client = HopsworksClientBuilder::new()
+            .with_api_key("api_key")
+            .with_cert_dir(base_cert_dir.to_str().unwrap())
+            .build()
+            .await?;
+        client.set_project_id(Some(42)).await;
+        client
+            .set_cert_dir(cert_dir.to_str().unwrap().to_string())
+            .await;
+
+        client.logout().await?;
+        assert!(!cert_dir.exists());
+        assert!(client.get_api_key().lock().await.is_none());
+        assert!(client.get_project_id().lock().await.is_none());
+        assert_eq!(
+            *client.get_cert_dir().lock().await,
+            base_cert_dir.to_str().unwrap()
+        );
+        std::fs::remove_dir(base_cert_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_from_toml_config() -> Result<()> {
+        let config: HopsworksTomlConfig = toml::from_str(
+            r#"
+            default_profile = "sandbox"
+
+            [env]
+            HOPSWORKS_PROJECT_NAME = "env_project"
+
+            [profiles.sandbox]
+            cluster = { host = "sandbox.hopsworks.ai", port = 8181 }
+            user = { api_key = "toml_api_key" }
+            project = { name = "toml_project" }
+            "#,
+        )?;
+
+        let builder =
+            HopsworksClientBuilder::from_toml_config(config, None)?.with_api_key("api_key");
+        assert_eq!(
+            builder.url,
+            "https://sandbox.hopsworks.ai:8181/hopsworks-api/api"
+        );
+        assert_eq!(builder.api_key.as_deref(), Some("api_key"));
+        assert_eq!(builder.project_name.as_deref(), Some("env_project"));
+
+        let missing =
+            HopsworksClientBuilder::from_toml_config(HopsworksTomlConfig::default(), Some("prod"));
+        assert!(matches!(missing, Err(HopsworksError::NotFound(_))));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_client() {
         let client = HopsworksClient::new(
@@ -521,6 +1094,93 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::new()
+            .with_jitter(false)
+            .with_backoff(Duration::from_millis(500), Duration::from_secs(3));
+
+        assert_eq!(policy.backoff(1, None), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2, None), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3, None), Duration::from_secs(2));
+        assert_eq!(policy.backoff(4, None), Duration::from_secs(3));
+
+        // Retry-After takes precedence over a shorter backoff but is capped by max_backoff
+        assert_eq!(
+            policy.backoff(1, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            policy.backoff(1, Some(Duration::from_secs(60))),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            policy
+                .with_respect_retry_after(false)
+                .backoff(1, Some(Duration::from_secs(2))),
+            Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn test_retry_policy_jitter() {
+        let policy =
+            RetryPolicy::new().with_backoff(Duration::from_secs(1), Duration::from_secs(1));
+
+        for _ in 0..10 {
+            let backoff = policy.backoff(1, None);
+            assert!(backoff >= Duration::from_millis(500) && backoff <= Duration::from_secs(1));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tls_config() {
+        let tls_config = TlsConfig::default();
+        assert!(!tls_config.accept_invalid_certs());
+        assert!(tls_config.read_ca_cert_pem().unwrap().is_none());
+
+        let builder = HopsworksClientBuilder::new()
+            .with_api_key("api_key")
+            .with_ca_cert_path("/non/existing/ca_chain.pem");
+        assert!(matches!(
+            builder.build().await,
+            Err(HopsworksError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_retry_policy_retryable() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.is_retryable_method(&Method::GET));
+        assert!(policy.is_retryable_method(&Method::DELETE));
+        assert!(!policy.is_retryable_method(&Method::POST));
+        assert!(policy.is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(policy.is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!policy.is_retryable_status(StatusCode::NOT_FOUND));
+
+        assert!(!RetryPolicy::disabled().is_retryable_method(&Method::GET));
+        assert!(RetryPolicy::default()
+            .with_idempotent_methods(vec![Method::POST])
+            .is_retryable_method(&Method::POST));
+    }
+
+    #[tokio::test]
+    async fn test_scoped_client() -> Result<()> {
+        let url = "https://my-hopsworks-domain.com/hopsworks-api/api";
+        let client = HopsworksClient::new(url.to_string(), DEFAULT_CLIENT_CERT_DIR.to_string());
+        client.set_project_id(Some(42)).await;
+
+        let scoped_client = client.scope(crate::get_hopsworks_client()).await?;
+        assert_eq!(scoped_client.url, url);
+        assert_eq!(*scoped_client.get_project_id().lock().await, Some(42));
+
+        // Outside the scope, falls back to the global client which is not initialized in tests
+        assert!(crate::get_hopsworks_client().await.is_err());
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[should_panic(
         expected = "No API key provided. Provide an API key using the HOPSWORKS_API_KEY environment variable or the with_api_key() method."
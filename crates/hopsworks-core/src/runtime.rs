@@ -0,0 +1,227 @@
+//! Tokio runtime driving the blocking API of the SDK.
+//!
+//! The async API runs on whatever runtime polls it, the runtime is only needed to block on
+//! a future, e.g. by the `*_blocking` functions of the `hopsworks` crate or the Python bindings.
+//! It is chosen with the [`RuntimeConfig`] passed to [`login`][crate::login] or [`connect`][crate::connect]
+//! and stored in the [`HopsworksClient`] of the session.
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        OnceLock,
+    },
+};
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+
+use crate::error::{HopsworksError, Result};
+use crate::{
+    get_scoped_hopsworks_client, with_hopsworks_client, HopsworksClient, HOPSWORKS_CLIENT,
+};
+
+static NUM_LOGICAL_CPUS: OnceLock<usize> = OnceLock::new();
+static THREADED_RUNTIME: OnceLock<Runtime> = OnceLock::new();
+static SINGLE_THREADED_RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Runtime used by the blocking API of a session.
+///
+/// Use [`RuntimeConfig::Current`] or [`RuntimeConfig::Handle`] when the application already runs
+/// a tokio runtime, so that the SDK does not start a second one.
+///
+/// # Example
+/// ```no_run
+/// use hopsworks_core::{HopsworksClientBuilder, RuntimeConfig};
+///
+/// # async fn run() -> hopsworks_core::Result<()> {
+/// // Reuse the runtime of the service calling login
+/// let project = hopsworks_core::connect(Some(HopsworksClientBuilder::new()), RuntimeConfig::Current).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub enum RuntimeConfig {
+    /// Multi-threaded runtime managed by the SDK, started on first use with one worker thread
+    /// per logical CPU or `HOPSWORKS_NUM_THREADS` worker threads if set.
+    #[default]
+    MultiThreaded,
+    /// Runtime managed by the SDK with a single worker thread, started on first use.
+    SingleThreaded,
+    /// Runtime of the caller of [`login`][crate::login], resolved to [`RuntimeConfig::Handle`] on login.
+    Current,
+    /// Runtime owned by the application.
+    Handle(Handle),
+}
+
+impl From<Handle> for RuntimeConfig {
+    fn from(handle: Handle) -> Self {
+        RuntimeConfig::Handle(handle)
+    }
+}
+
+impl From<&Runtime> for RuntimeConfig {
+    fn from(runtime: &Runtime) -> Self {
+        RuntimeConfig::Handle(runtime.handle().clone())
+    }
+}
+
+impl RuntimeConfig {
+    /// Replace [`RuntimeConfig::Current`] by the handle of the runtime of the caller.
+    pub(crate) fn resolve(self) -> Result<Self> {
+        match self {
+            RuntimeConfig::Current => {
+                Handle::try_current()
+                    .map(RuntimeConfig::Handle)
+                    .map_err(|_| {
+                        HopsworksError::Validation(
+                            "RuntimeConfig::Current requires to login from within a tokio runtime."
+                                .to_string(),
+                        )
+                    })
+            }
+            config => Ok(config),
+        }
+    }
+
+    /// Handle of the runtime, starting the SDK managed runtime if needed.
+    pub fn handle(&self) -> Result<Handle> {
+        match self {
+            RuntimeConfig::MultiThreaded => Ok(get_threaded_runtime().handle().clone()),
+            RuntimeConfig::SingleThreaded => Ok(get_single_threaded_runtime().handle().clone()),
+            RuntimeConfig::Current => self.clone().resolve()?.handle(),
+            RuntimeConfig::Handle(handle) => Ok(handle.clone()),
+        }
+    }
+
+    /// Run `future` to completion on the runtime, blocking the current thread.
+    ///
+    /// Can be called from a worker of a multi-threaded tokio runtime, the worker is then handed over
+    /// with [`tokio::task::block_in_place`]. Returns an error when called from a current-thread runtime,
+    /// which cannot be blocked without stalling every other task, use the async API instead.
+    pub fn block_on<T>(&self, future: impl Future<Output = Result<T>>) -> Result<T> {
+        let handle = self.handle()?;
+        match Handle::try_current() {
+            Err(_) => handle.block_on(future),
+            Ok(current) if current.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| handle.block_on(future))
+            }
+            Ok(_) => Err(HopsworksError::Validation(
+                "Cannot block within a current-thread tokio runtime, use the async API instead."
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+/// Block on `future` with the runtime of `client`, sending its requests with `client`.
+///
+/// Falls back to the enclosing scope or the global client if `client` is `None`,
+/// and to the default [`RuntimeConfig`] if no client was initialized.
+pub fn block_on<T>(
+    client: Option<&HopsworksClient>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let client = client
+        .cloned()
+        .or_else(get_scoped_hopsworks_client)
+        .or_else(|| HOPSWORKS_CLIENT.get().cloned());
+    let runtime = client
+        .as_ref()
+        .map(|client| client.runtime().clone())
+        .unwrap_or_default();
+    runtime.block_on(with_hopsworks_client(client.as_ref(), future))
+}
+
+pub fn get_logical_cpus() -> usize {
+    *NUM_LOGICAL_CPUS.get_or_init(|| {
+        let env_requested_threads = std::env::var("HOPSWORKS_NUM_THREADS")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok());
+        let num_logical_cpus =
+            env_requested_threads.unwrap_or(std::thread::available_parallelism().unwrap().get());
+        tracing::debug!("Detected {} logical CPUs", num_logical_cpus);
+        num_logical_cpus
+    })
+}
+
+/// Number of worker threads of the multi-threaded runtime managed by the SDK.
+pub fn get_threaded_runtime_num_worker_threads() -> usize {
+    get_logical_cpus()
+}
+
+fn get_threaded_runtime() -> &'static Runtime {
+    THREADED_RUNTIME.get_or_init(|| {
+        let num_worker_threads = get_threaded_runtime_num_worker_threads();
+        // Built on a dedicated thread, building a runtime from a runtime worker panics
+        let runtime = std::thread::Builder::new()
+            .name("start_hopsworks_runtime".to_string())
+            .spawn(move || {
+                tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(num_worker_threads)
+                    .enable_all()
+                    .thread_name_fn(|| {
+                        static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
+                        let id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
+                        format!("hopsworks-runtime-worker-{}", id)
+                    })
+                    .build()
+                    .unwrap()
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        tracing::debug!(
+            "Initialized multi-threaded runtime with {} worker threads",
+            num_worker_threads
+        );
+        runtime
+    })
+}
+
+fn get_single_threaded_runtime() -> &'static Runtime {
+    SINGLE_THREADED_RUNTIME.get_or_init(|| {
+        std::thread::Builder::new()
+            .name("start_hopsworks_runtime".to_string())
+            .spawn(|| {
+                tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(1)
+                    .enable_all()
+                    .build()
+                    .unwrap()
+            })
+            .unwrap()
+            .join()
+            .unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_on_outside_runtime() {
+        let value = RuntimeConfig::SingleThreaded
+            .block_on(async { Ok(1) })
+            .unwrap();
+        assert_eq!(value, 1);
+        assert!(RuntimeConfig::Current.resolve().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_block_on_current_runtime() {
+        let runtime = RuntimeConfig::Current.resolve().unwrap();
+        assert!(matches!(runtime, RuntimeConfig::Handle(_)));
+        let value = runtime
+            .block_on(async {
+                tokio::task::yield_now().await;
+                Ok(2)
+            })
+            .unwrap();
+        assert_eq!(value, 2);
+    }
+
+    #[tokio::test]
+    async fn test_block_on_current_thread_runtime() {
+        let result = RuntimeConfig::Current.block_on(async { Ok(()) });
+        assert!(matches!(result, Err(HopsworksError::Validation(_))));
+    }
+}
@@ -0,0 +1,25 @@
+//! Shared setup of the tests running the SDK against the mock server of hopsworks-testkit.
+use hopsworks_core::feature_store::FeatureStore;
+use hopsworks_core::platform::project::Project;
+use hopsworks_testkit::{
+    fixtures::{FeatureGroupFixture, ProjectFixture},
+    MockHopsworks,
+};
+
+pub const PROJECT_NAME: &str = "test_project";
+
+/// Start a mock Hopsworks serving the project [`PROJECT_NAME`] with `feature_groups`,
+/// then connect to it and return the mock, the project and its feature store.
+pub async fn connect_with_feature_groups(
+    feature_groups: impl IntoIterator<Item = FeatureGroupFixture>,
+) -> (MockHopsworks, Project, FeatureStore) {
+    let mock = MockHopsworks::start().await.unwrap();
+    mock.add_project(ProjectFixture::new(PROJECT_NAME));
+    for feature_group in feature_groups {
+        mock.add_feature_group(PROJECT_NAME, feature_group);
+    }
+
+    let project = mock.connect(None).await.unwrap();
+    let fs = project.get_feature_store().await.unwrap();
+    (mock, project, fs)
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{connect_with_feature_groups, PROJECT_NAME};
+use hopsworks_core::feature_store::feature_group::expectation_suite::{
+    ExpectationConfiguration, ExpectationSuite, IngestionResult, ValidationIngestionPolicy,
+    ValidationReport, ValidationResult,
+};
+use hopsworks_testkit::fixtures::FeatureGroupFixture;
+
+#[tokio::test]
+async fn test_expectation_suite() {
+    let (mock, _project, fs) = connect_with_feature_groups([
+        FeatureGroupFixture::new("fg", 1).with_primary_key("id", "bigint")
+    ])
+    .await;
+    let fg = fs.get_feature_group("fg", Some(1)).await.unwrap().unwrap();
+    assert!(fg.get_expectation_suite().await.unwrap().is_none());
+
+    let expectation = ExpectationConfiguration::new(
+        "expect_column_values_to_be_unique",
+        serde_json::json!({ "column": "id" }),
+    );
+    let suite = ExpectationSuite::new("fg_suite")
+        .with_expectation(expectation.clone())
+        .with_validation_ingestion_policy(ValidationIngestionPolicy::Strict);
+    let saved = fg.save_expectation_suite(&suite).await.unwrap();
+    assert!(saved.id().is_some());
+    let fetched = fg.get_expectation_suite().await.unwrap().unwrap();
+    assert_eq!(fetched, saved);
+    assert_eq!(fetched.expectations(), &[expectation.clone()]);
+    assert_eq!(
+        fetched.validation_ingestion_policy(),
+        ValidationIngestionPolicy::Strict
+    );
+
+    let report = ValidationReport::new(vec![ValidationResult {
+        success: false,
+        expectation_config: expectation,
+        result: serde_json::json!({ "unexpected_count": 1 }),
+        exception_info: serde_json::json!({ "raised_exception": false }),
+    }])
+    .with_ingestion_result(IngestionResult::Rejected);
+    let uploaded = fg.save_validation_report(&report).await.unwrap();
+    assert!(!uploaded.success());
+    assert_eq!(uploaded.ingestion_result(), IngestionResult::Rejected);
+    assert_eq!(uploaded.results()[0].result["unexpected_count"], 1);
+    let stored = mock.validation_reports(PROJECT_NAME);
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0]["ingestionResult"], "REJECTED");
+
+    fg.delete_expectation_suite().await.unwrap();
+    assert!(fg.get_expectation_suite().await.unwrap().is_none());
+}
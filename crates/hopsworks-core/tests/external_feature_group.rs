@@ -0,0 +1,91 @@
+mod common;
+
+use common::{connect_with_feature_groups, PROJECT_NAME};
+use hopsworks_core::controller::feature_store::query::construct_query;
+use hopsworks_core::feature_store::query::{enums::JoinType, JoinOptions};
+use hopsworks_core::HopsworksError;
+use hopsworks_testkit::fixtures::{FeatureGroupFixture, StorageConnectorFixture};
+
+#[tokio::test]
+async fn test_external_feature_group() {
+    let (mock, project, fs) = connect_with_feature_groups([
+        FeatureGroupFixture::new("fg", 1).with_primary_key("id", "bigint")
+    ])
+    .await;
+    mock.add_storage_connector(
+        PROJECT_NAME,
+        StorageConnectorFixture::s3("s3_data", "testkit-bucket"),
+    );
+    let connector = fs.get_storage_connector("s3_data").await.unwrap().unwrap();
+    assert_eq!(connector.storage_connector_type(), "S3");
+    assert_eq!(connector.connector_options()["bucket"], "testkit-bucket");
+    assert!(fs.get_storage_connector("missing").await.unwrap().is_none());
+    assert!(matches!(
+        fs.external_feature_group_builder(&connector)
+            .name("ext_fg")
+            .version(1)
+            .primary_key(vec!["id".to_string()])
+            .build(),
+        Err(HopsworksError::Validation(_))
+    ));
+    assert!(matches!(
+        fs.external_feature_group_builder(&connector)
+            .name("ext_fg")
+            .version(1)
+            .path("transactions/")
+            .build(),
+        Err(HopsworksError::Validation(_))
+    ));
+
+    let mut ext_fg = fs
+        .external_feature_group_builder(&connector)
+        .name("ext_fg")
+        .version(1)
+        .primary_key(vec!["id".to_string()])
+        .path("transactions/")
+        .data_format("PARQUET")
+        .build()
+        .unwrap();
+    ext_fg
+        .register_feature_group(
+            &["id".to_string(), "amount".to_string()],
+            &["bigint".to_string(), "double".to_string()],
+        )
+        .await
+        .unwrap();
+    let stored = mock.feature_group(PROJECT_NAME, "ext_fg", 1).unwrap();
+    assert_eq!(stored["type"], "onDemandFeaturegroupDTO");
+    assert_eq!(stored["storageConnector"]["name"], "s3_data");
+
+    let ext_fg = fs
+        .get_feature_group("ext_fg", Some(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(ext_fg.is_external());
+    assert_eq!(ext_fg.path(), Some("transactions/"));
+    assert_eq!(
+        ext_fg.storage_connector().unwrap().connector_options()["bucket"],
+        "testkit-bucket"
+    );
+
+    let fg = fs.get_feature_group("fg", Some(1)).await.unwrap().unwrap();
+    let query = fg.select(&["id"]).unwrap().join(
+        ext_fg.select(&["amount"]).unwrap(),
+        JoinOptions::new(JoinType::Inner).with_on(&["id"]),
+    );
+    let fs_query = project
+        .hopsworks_client()
+        .unwrap()
+        .scope(construct_query(&query))
+        .await
+        .unwrap();
+    assert_eq!(fs_query.hudi_cached_feature_groups[0].alias, "fg0");
+    assert_eq!(fs_query.on_demand_feature_groups[0].alias, "fg1");
+    assert_eq!(
+        fs_query.on_demand_feature_groups[0]
+            .on_demand_feature_group
+            .id,
+        ext_fg.id().unwrap()
+    );
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use common::{connect_with_feature_groups, PROJECT_NAME};
+use hopsworks_core::feature_store::feature_group::{feature::Feature, TimeTravelFormat};
+use hopsworks_core::HopsworksError;
+use hopsworks_testkit::fixtures::{FeatureFixture, FeatureGroupFixture};
+
+#[tokio::test]
+async fn test_list_feature_groups() {
+    let (_mock, _project, fs) = connect_with_feature_groups(
+        [("fg_b", 1), ("fg_a", 2), ("fg_a", 1)].map(|(name, version)| {
+            FeatureGroupFixture::new(name, version).with_primary_key("id", "bigint")
+        }),
+    )
+    .await;
+
+    let names_and_versions = |fgs: Vec<hopsworks_core::feature_store::FeatureGroup>| {
+        fgs.iter()
+            .map(|fg| (fg.name().to_string(), fg.version()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        names_and_versions(fs.get_feature_groups(false).await.unwrap()),
+        vec![
+            ("fg_a".to_string(), 1),
+            ("fg_a".to_string(), 2),
+            ("fg_b".to_string(), 1)
+        ]
+    );
+    assert_eq!(
+        names_and_versions(fs.get_feature_groups(true).await.unwrap()),
+        vec![("fg_a".to_string(), 2), ("fg_b".to_string(), 1)]
+    );
+
+    let versions = fs.get_feature_group_versions("fg_a").await.unwrap();
+    assert_eq!(versions.len(), 2);
+    assert!(fs
+        .get_feature_group_versions("missing")
+        .await
+        .unwrap()
+        .is_empty());
+    let by_id = fs
+        .get_feature_group_by_id(versions[1].id().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((by_id.name(), by_id.version()), ("fg_a", 2));
+    assert!(fs.get_feature_group_by_id(-1).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_append_features() {
+    let (mock, _project, fs) = connect_with_feature_groups([FeatureGroupFixture::new("fg", 1)
+        .with_primary_key("id", "bigint")
+        .with_feature(FeatureFixture::new("value", "double"))])
+    .await;
+    let mut fg = fs.get_feature_group("fg", Some(1)).await.unwrap().unwrap();
+    let appended = fg
+        .evolve_schema(
+            &["id".to_string(), "value".to_string(), "label".to_string()],
+            &[
+                "bigint".to_string(),
+                "double".to_string(),
+                "string".to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(appended.len(), 1);
+    assert_eq!(appended[0].name(), "label");
+    assert_eq!(fg.feature_names(), vec!["id", "value", "label"]);
+    assert_eq!(
+        mock.feature_group(PROJECT_NAME, "fg", 1).unwrap()["features"][2]["name"],
+        "label"
+    );
+
+    assert!(fg
+        .append_features(&[Feature::new("value", "double")])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_update_feature_group_metadata() {
+    let (mock, _project, fs) = connect_with_feature_groups([FeatureGroupFixture::new("fg", 1)
+        .with_primary_key("id", "bigint")
+        .with_feature(FeatureFixture::new("value", "double"))])
+    .await;
+    let mut fg = fs.get_feature_group("fg", Some(1)).await.unwrap().unwrap();
+    fg.update_description("updated").await.unwrap();
+    fg.update_feature_description("value", "a value")
+        .await
+        .unwrap();
+    fg.enable_online().await.unwrap();
+    let mut statistics_config = fg.statistics_config().unwrap().clone();
+    statistics_config.histograms = true;
+    fg.update_statistics_config(&statistics_config)
+        .await
+        .unwrap();
+
+    assert_eq!(fg.description(), Some("updated"));
+    assert_eq!(
+        fg.feature_by_name("value").unwrap().description(),
+        Some("a value")
+    );
+    assert!(fg.is_online_enabled());
+    assert!(fg.statistics_config().unwrap().histograms);
+    let stored = mock.feature_group(PROJECT_NAME, "fg", 1).unwrap();
+    assert_eq!(stored["description"], "updated");
+    assert_eq!(stored["onlineEnabled"], true);
+
+    fg.disable_online().await.unwrap();
+    assert!(!fg.is_online_enabled());
+    assert!(fg
+        .update_feature_description("missing", "a value")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_create_partitioned_feature_group() {
+    let (mock, _project, fs) = connect_with_feature_groups([]).await;
+    let feature_names = ["id".to_string(), "day".to_string(), "ts".to_string()];
+    let feature_types = [
+        "bigint".to_string(),
+        "date".to_string(),
+        "timestamp".to_string(),
+    ];
+    let mut fg = fs
+        .feature_group_builder()
+        .name("partitioned_fg".to_string())
+        .version(1)
+        .primary_key(vec!["id".to_string()])
+        .partition_key(vec!["day".to_string()])
+        .hudi_precombine_key(Some("ts".to_string()))
+        .online_enabled(false)
+        .build();
+    fg.register_feature_group(&feature_names, &feature_types)
+        .await
+        .unwrap();
+
+    let stored = mock
+        .feature_group(PROJECT_NAME, "partitioned_fg", 1)
+        .unwrap();
+    assert_eq!(stored["timeTravelFormat"], "HUDI");
+    assert_eq!(stored["features"][1]["partition"], true);
+    assert_eq!(stored["features"][2]["hudiPrecombineKey"], true);
+    let fetched = fs
+        .get_feature_group("partitioned_fg", Some(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fetched.partition_keys(), vec!["day"]);
+    assert_eq!(fetched.hudi_precombine_key(), Some("ts"));
+
+    let mut delta_fg = fs
+        .feature_group_builder()
+        .name("delta_fg".to_string())
+        .version(1)
+        .primary_key(vec!["id".to_string()])
+        .time_travel_format(TimeTravelFormat::Delta)
+        .online_enabled(false)
+        .build();
+    delta_fg
+        .register_feature_group(&feature_names, &feature_types)
+        .await
+        .unwrap();
+    assert_eq!(delta_fg.time_travel_format(), "DELTA");
+
+    let mut missing_partition_fg = fs
+        .feature_group_builder()
+        .name("missing_partition_fg".to_string())
+        .version(1)
+        .primary_key(vec!["id".to_string()])
+        .partition_key(vec!["missing".to_string()])
+        .online_enabled(false)
+        .build();
+    assert!(matches!(
+        missing_partition_fg
+            .register_feature_group(&feature_names, &feature_types)
+            .await,
+        Err(HopsworksError::Validation(_))
+    ));
+    let mut precombine_without_hudi_fg = fs
+        .feature_group_builder()
+        .name("precombine_without_hudi_fg".to_string())
+        .version(1)
+        .primary_key(vec!["id".to_string()])
+        .hudi_precombine_key(Some("ts".to_string()))
+        .time_travel_format(TimeTravelFormat::None)
+        .online_enabled(false)
+        .build();
+    assert!(matches!(
+        precombine_without_hudi_fg
+            .register_feature_group(&feature_names, &feature_types)
+            .await,
+        Err(HopsworksError::Validation(_))
+    ));
+    assert!(mock
+        .feature_group(PROJECT_NAME, "missing_partition_fg", 1)
+        .is_none());
+}
@@ -0,0 +1,22 @@
+mod common;
+
+use common::{connect_with_feature_groups, PROJECT_NAME};
+use hopsworks_testkit::fixtures::{FeatureGroupFixture, ProjectFixture};
+
+#[tokio::test]
+async fn test_switch_to_leaves_the_project_untouched() {
+    let (mock, project, fs) = connect_with_feature_groups([
+        FeatureGroupFixture::new("fg", 1).with_primary_key("id", "bigint")
+    ])
+    .await;
+    mock.add_project(ProjectFixture::new("other_project"));
+
+    let other_project = project.switch_to("other_project").await.unwrap();
+    assert_eq!(other_project.name(), "other_project");
+    let other_fs = other_project.get_feature_store().await.unwrap();
+    assert_ne!(other_fs.id(), fs.id());
+
+    assert_eq!(project.name(), PROJECT_NAME);
+    assert_eq!(project.get_feature_store().await.unwrap().id(), fs.id());
+    assert!(fs.get_feature_group("fg", Some(1)).await.unwrap().is_some());
+}
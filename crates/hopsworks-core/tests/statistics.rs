@@ -0,0 +1,38 @@
+mod common;
+
+use common::connect_with_feature_groups;
+use hopsworks_core::feature_store::feature_group::statistics::{
+    FeatureDescriptiveStatistics, HistogramBin, Statistics,
+};
+use hopsworks_testkit::fixtures::FeatureGroupFixture;
+
+#[tokio::test]
+async fn test_statistics() {
+    let (_mock, _project, fs) = connect_with_feature_groups([
+        FeatureGroupFixture::new("fg", 1).with_primary_key("id", "bigint")
+    ])
+    .await;
+    let fg = fs.get_feature_group("fg", Some(1)).await.unwrap().unwrap();
+    assert!(fg.get_statistics().await.unwrap().is_none());
+
+    let mut id_statistics = FeatureDescriptiveStatistics::new("id");
+    id_statistics.count = Some(2);
+    id_statistics.mean = Some(1.5);
+    id_statistics.histogram = Some(vec![HistogramBin {
+        value: "1".to_string(),
+        count: 2,
+        ratio: 1.0,
+    }]);
+    for millis in [1_700_000_000_000, 1_700_000_060_000] {
+        let statistics = Statistics::new(vec![id_statistics.clone()])
+            .with_computation_time(chrono::DateTime::from_timestamp_millis(millis).unwrap());
+        fg.save_statistics(&statistics).await.unwrap();
+    }
+
+    let fetched = fg.get_statistics().await.unwrap().unwrap();
+    assert_eq!(
+        fetched.computation_time().timestamp_millis(),
+        1_700_000_060_000
+    );
+    assert_eq!(fetched.feature_statistics("id"), Some(&id_statistics));
+}
@@ -0,0 +1,424 @@
+//! Consume the rows written on the online topic of a feature group as polars DataFrames.
+//!
+//! Each message produced when inserting into a feature group holds the Avro encoding of a row,
+//! following a version of the feature group subject schema, with the feature group id and the
+//! schema id in its headers.
+use futures::Stream;
+use polars::prelude::{ArrowSchema, DataFrame, PolarsResult, Series};
+use polars_arrow::io::avro::avro_schema::file::Block;
+use polars_arrow::io::avro::avro_schema::schema::{Record, Schema as AvroSchema};
+use polars_arrow::io::avro::read;
+use rdkafka::consumer::{Consumer, StreamConsumer};
+use rdkafka::message::{Headers, Message};
+use rdkafka::{Offset, TopicPartitionList};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use hopsworks_core::controller::feature_store::storage_connector;
+use hopsworks_core::controller::platform::kafka::{
+    get_kafka_schema_by_id, get_kafka_topic_subject,
+};
+use hopsworks_core::HopsworksError;
+
+use crate::error::Result;
+use crate::kafka_configuration::setup_kafka_consumer_configuration;
+use crate::target::FeatureGroupTarget;
+
+/// Position in the online topic from which a [`FeatureGroupConsumer`] starts reading each partition.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartOffset {
+    /// The oldest message still retained in the topic.
+    Earliest,
+    /// Only the messages produced after the consumer is created.
+    #[default]
+    Latest,
+    /// The first message produced at or after the timestamp, in milliseconds since the epoch.
+    Timestamp(i64),
+    /// The message at the offset, in every partition of the topic.
+    Offset(i64),
+}
+
+/// Options of a [`FeatureGroupConsumer`], by default reading the messages produced after
+/// its creation in micro-batches of at most 1000 rows or 1 second.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerOptions {
+    start_offset: StartOffset,
+    max_batch_size: usize,
+    max_batch_wait: Duration,
+    group_id: Option<String>,
+}
+
+impl Default for ConsumerOptions {
+    fn default() -> Self {
+        ConsumerOptions {
+            start_offset: StartOffset::default(),
+            max_batch_size: 1000,
+            max_batch_wait: Duration::from_secs(1),
+            group_id: None,
+        }
+    }
+}
+
+impl ConsumerOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_start_offset(mut self, start_offset: StartOffset) -> Self {
+        self.start_offset = start_offset;
+        self
+    }
+
+    /// Maximum number of rows in a micro-batch.
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Maximum time spent waiting for the rows of a micro-batch.
+    pub fn with_max_batch_wait(mut self, max_batch_wait: Duration) -> Self {
+        self.max_batch_wait = max_batch_wait;
+        self
+    }
+
+    /// Consumer group of the consumer, defaults to a group specific to the topic and the process.
+    /// Offsets are never committed, the group only shows up in the Kafka monitoring.
+    pub fn with_group_id(mut self, group_id: impl Into<String>) -> Self {
+        self.group_id = Some(group_id.into());
+        self
+    }
+
+    pub fn start_offset(&self) -> StartOffset {
+        self.start_offset
+    }
+
+    pub fn max_batch_size(&self) -> usize {
+        self.max_batch_size
+    }
+
+    pub fn max_batch_wait(&self) -> Duration {
+        self.max_batch_wait
+    }
+}
+
+/// Consumer tailing the online topic of a registered feature group.
+///
+/// Messages of other feature groups sharing the topic and tombstones of deleted rows are skipped.
+/// Payloads are decoded with the schema whose id is in their `subjectId` header, fetched once
+/// and cached, falling back to the latest schema of the feature group subject without the header.
+/// Batches always have the columns of the latest schema when the consumer was created: features
+/// missing from older schemas are null, features added afterwards are dropped.
+/// Complex features are left as binary columns holding their own Avro encoding.
+pub struct FeatureGroupConsumer {
+    consumer: StreamConsumer,
+    feature_group_id: String,
+    subject_id: i32,
+    schemas: Mutex<HashMap<i32, Arc<SubjectSchema>>>,
+    empty_batch: DataFrame,
+    options: ConsumerOptions,
+}
+
+/// Avro record of a version of the feature group subject, with the matching arrow schema.
+struct SubjectSchema {
+    record: Record,
+    arrow_schema: ArrowSchema,
+}
+
+impl SubjectSchema {
+    fn parse(schema: &str) -> Result<Self> {
+        let record = parse_subject_schema(schema)?;
+        let arrow_schema = read::infer_schema(&record)?;
+        Ok(Self {
+            record,
+            arrow_schema,
+        })
+    }
+
+    fn decode_rows(&self, rows: usize, data: Vec<u8>) -> Result<DataFrame> {
+        decode_rows(rows, data, &self.arrow_schema, &self.record)
+    }
+}
+
+impl FeatureGroupConsumer {
+    #[tracing::instrument]
+    pub async fn new(target: &FeatureGroupTarget, options: ConsumerOptions) -> Result<Self> {
+        let subject = get_kafka_topic_subject(target.subject_name().as_str(), None).await?;
+        let latest_schema = SubjectSchema::parse(subject.schema())?;
+        let empty_batch = latest_schema.decode_rows(0, vec![])?;
+
+        let kafka_connector =
+            storage_connector::get_feature_store_kafka_connector(target.feature_store_id, true)
+                .await?;
+        let online_topic_name = target.online_topic_name.as_str();
+        let group_id = options
+            .group_id
+            .clone()
+            .unwrap_or_else(|| format!("hopsworks_sdk_{online_topic_name}_{}", std::process::id()));
+        let consumer: StreamConsumer = setup_kafka_consumer_configuration(
+            kafka_connector,
+            target.cert_dir.as_str(),
+            group_id.as_str(),
+        )?
+        .create()?;
+        assign_partitions(&consumer, online_topic_name, options.start_offset)?;
+
+        Ok(Self {
+            consumer,
+            feature_group_id: target.feature_group_id.to_string(),
+            subject_id: subject.id(),
+            schemas: Mutex::new(HashMap::from([(subject.id(), Arc::new(latest_schema))])),
+            empty_batch,
+            options,
+        })
+    }
+
+    /// Wait for the next micro-batch of rows, empty if none was produced within
+    /// [`ConsumerOptions::with_max_batch_wait`].
+    pub async fn next_batch(&self) -> Result<DataFrame> {
+        let deadline = tokio::time::Instant::now() + self.options.max_batch_wait;
+        let mut rows = 0;
+        // Consecutive rows sharing a schema, decoded together.
+        let mut chunks: Vec<(i32, usize, Vec<u8>)> = vec![];
+        while rows < self.options.max_batch_size {
+            let Ok(message) = tokio::time::timeout_at(deadline, self.consumer.recv()).await else {
+                break;
+            };
+            let message = message?;
+            let is_other_feature_group = header(&message, "featureGroupId")
+                .is_some_and(|value| value != self.feature_group_id.as_bytes());
+            let Some(payload) = message.payload().filter(|_| !is_other_feature_group) else {
+                continue;
+            };
+            let subject_id = header(&message, "subjectId")
+                .and_then(|value| std::str::from_utf8(value).ok()?.parse().ok())
+                .unwrap_or(self.subject_id);
+            match chunks.last_mut() {
+                Some((chunk_subject_id, chunk_rows, data)) if *chunk_subject_id == subject_id => {
+                    data.extend_from_slice(payload);
+                    *chunk_rows += 1;
+                }
+                _ => chunks.push((subject_id, 1, payload.to_vec())),
+            }
+            rows += 1;
+        }
+
+        let mut batch = self.empty_batch.clone();
+        for (subject_id, rows, data) in chunks {
+            let schema = self.subject_schema(subject_id).await?;
+            let chunk = schema.decode_rows(rows, data)?;
+            batch.vstack_mut(&conform_to(&chunk, &self.empty_batch)?)?;
+        }
+        Ok(batch)
+    }
+
+    /// Schema registered under `subject_id`, fetched on first use.
+    async fn subject_schema(&self, subject_id: i32) -> Result<Arc<SubjectSchema>> {
+        let mut schemas = self.schemas.lock().await;
+        if let Some(schema) = schemas.get(&subject_id) {
+            return Ok(schema.clone());
+        }
+        tracing::debug!("Fetching the schema {subject_id} of the feature group subject");
+        let schema = Arc::new(SubjectSchema::parse(
+            get_kafka_schema_by_id(subject_id).await?.as_str(),
+        )?);
+        schemas.insert(subject_id, schema.clone());
+        Ok(schema)
+    }
+
+    /// Stream the non-empty micro-batches of rows, see [`FeatureGroupConsumer::next_batch`].
+    /// The stream never ends, drop it to stop consuming.
+    pub fn into_stream(self) -> impl Stream<Item = Result<DataFrame>> {
+        futures::stream::unfold(self, |consumer| async move {
+            loop {
+                match consumer.next_batch().await {
+                    Ok(batch) if batch.height() == 0 => continue,
+                    batch => return Some((batch, consumer)),
+                }
+            }
+        })
+    }
+}
+
+fn header<'a, M: Message>(message: &'a M, key: &str) -> Option<&'a [u8]> {
+    message
+        .headers()?
+        .iter()
+        .find(|header| header.key == key)?
+        .value
+}
+
+fn parse_subject_schema(schema: &str) -> Result<Record> {
+    match serde_json::from_str::<AvroSchema>(schema)
+        .map_err(|e| HopsworksError::Serialization(Box::new(e)))?
+    {
+        AvroSchema::Record(record) => Ok(record),
+        other => Err(HopsworksError::Validation(format!(
+            "The schema of a feature group subject must be an Avro record, got {other:?}."
+        ))
+        .into()),
+    }
+}
+
+fn assign_partitions(
+    consumer: &StreamConsumer,
+    topic_name: &str,
+    start_offset: StartOffset,
+) -> Result<()> {
+    let timeout = Duration::from_secs(10);
+    let metadata = consumer
+        .client()
+        .fetch_metadata(Some(topic_name), timeout)?;
+    let offset = match start_offset {
+        StartOffset::Earliest => Offset::Beginning,
+        StartOffset::Latest => Offset::End,
+        // Resolved to the offset of the timestamp in each partition below.
+        StartOffset::Timestamp(timestamp) => Offset::Offset(timestamp),
+        StartOffset::Offset(offset) => Offset::Offset(offset),
+    };
+
+    let mut partitions = TopicPartitionList::new();
+    for topic_metadata in metadata.topics().iter().filter(|t| t.name() == topic_name) {
+        for partition in topic_metadata.partitions() {
+            partitions.add_partition_offset(topic_name, partition.id(), offset)?;
+        }
+    }
+    if partitions.count() == 0 {
+        return Err(HopsworksError::NotFound(format!(
+            "No partition found for the online topic {topic_name}, check that it exists."
+        ))
+        .into());
+    }
+    if let StartOffset::Timestamp(_) = start_offset {
+        partitions = consumer.offsets_for_times(partitions, timeout)?;
+    }
+    tracing::debug!("Assigning partitions {:?}", partitions);
+    consumer.assign(&partitions)?;
+    Ok(())
+}
+
+/// Decode the concatenated Avro encoding of `rows` rows following the subject `record`.
+fn decode_rows(
+    rows: usize,
+    data: Vec<u8>,
+    arrow_schema: &ArrowSchema,
+    record: &Record,
+) -> Result<DataFrame> {
+    let block = Block::new(rows, data);
+    let chunk = read::deserialize(
+        &block,
+        &arrow_schema.fields,
+        &record.fields,
+        &vec![true; record.fields.len()],
+    )?;
+    let columns = chunk
+        .into_arrays()
+        .into_iter()
+        .zip(arrow_schema.fields.iter())
+        .map(|(array, field)| Series::from_arrow(field.name.as_str(), array))
+        .collect::<PolarsResult<Vec<Series>>>()?;
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Align rows decoded with a version of the subject schema on the columns of `template`,
+/// filling the features missing from that version with nulls.
+fn conform_to(df: &DataFrame, template: &DataFrame) -> Result<DataFrame> {
+    let columns = template
+        .get_columns()
+        .iter()
+        .map(|column| match df.column(column.name()) {
+            Ok(series) => series.cast(column.dtype()),
+            Err(_) => Ok(Series::full_null(
+                column.name(),
+                df.height(),
+                column.dtype(),
+            )),
+        })
+        .collect::<PolarsResult<Vec<Series>>>()?;
+    Ok(DataFrame::new(columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    const SUBJECT_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "fg_1",
+        "namespace": "test_featurestore.db",
+        "fields": [
+            {"name": "id", "type": ["null", "long"]},
+            {"name": "name", "type": ["null", "string"]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_decode_rows() {
+        // Arrange
+        let record = parse_subject_schema(SUBJECT_SCHEMA).unwrap();
+        let arrow_schema = read::infer_schema(&record).unwrap();
+        // {"id": 1, "name": "a"} then {"id": null, "name": "bc"}
+        let data = vec![2, 2, 2, 2, b'a', 0, 2, 4, b'b', b'c'];
+
+        // Act
+        let df = decode_rows(2, data, &arrow_schema, &record).unwrap();
+
+        // Assert
+        let expected = df!(
+            "id" => [Some(1i64), None],
+            "name" => [Some("a"), Some("bc")],
+        )
+        .unwrap();
+        assert!(df.equals_missing(&expected));
+    }
+
+    #[test]
+    fn test_decode_no_rows() {
+        // Arrange
+        let record = parse_subject_schema(SUBJECT_SCHEMA).unwrap();
+        let arrow_schema = read::infer_schema(&record).unwrap();
+
+        // Act
+        let df = decode_rows(0, vec![], &arrow_schema, &record).unwrap();
+
+        // Assert
+        assert_eq!(df.height(), 0);
+        assert_eq!(df.get_column_names(), vec!["id", "name"]);
+    }
+
+    #[test]
+    fn test_decode_rows_of_an_older_schema() {
+        // Arrange
+        let latest_schema = SubjectSchema::parse(SUBJECT_SCHEMA).unwrap();
+        let empty_batch = latest_schema.decode_rows(0, vec![]).unwrap();
+        let older_schema = SubjectSchema::parse(
+            r#"{
+                "type": "record",
+                "name": "fg_1",
+                "namespace": "test_featurestore.db",
+                "fields": [{"name": "id", "type": ["null", "long"]}]
+            }"#,
+        )
+        .unwrap();
+        // {"id": 1}
+        let chunk = older_schema.decode_rows(1, vec![2, 2]).unwrap();
+
+        // Act
+        let df = conform_to(&chunk, &empty_batch).unwrap();
+
+        // Assert
+        let expected = df!(
+            "id" => [Some(1i64)],
+            "name" => [None::<&str>],
+        )
+        .unwrap();
+        assert!(df.equals_missing(&expected));
+    }
+
+    #[test]
+    fn test_parse_subject_schema_requires_a_record() {
+        assert!(parse_subject_schema(r#""long""#).is_err());
+    }
+}
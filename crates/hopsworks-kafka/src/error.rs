@@ -0,0 +1,42 @@
+use hopsworks_core::HopsworksError;
+
+pub type Result<T, E = HopsworksKafkaError> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HopsworksKafkaError {
+    #[error(transparent)]
+    Hopsworks(#[from] HopsworksError),
+    #[error(transparent)]
+    Kafka(#[from] rdkafka::error::KafkaError),
+    #[error(transparent)]
+    Polars(#[from] polars::error::PolarsError),
+    #[cfg(feature = "arrow")]
+    #[error(transparent)]
+    Arrow(#[from] arrow::error::ArrowError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+    #[error("Insert worker channel closed: {0}")]
+    WorkerChannel(String),
+}
+
+impl From<HopsworksKafkaError> for HopsworksError {
+    fn from(err: HopsworksKafkaError) -> Self {
+        match err {
+            HopsworksKafkaError::Hopsworks(e) => e,
+            HopsworksKafkaError::Kafka(e) => HopsworksError::transport(e),
+            HopsworksKafkaError::Io(e) => HopsworksError::Io(e),
+            HopsworksKafkaError::Polars(e) => HopsworksError::other(e),
+            // Record batches not matching the features of the feature group
+            #[cfg(feature = "arrow")]
+            HopsworksKafkaError::Arrow(arrow::error::ArrowError::SchemaError(msg)) => {
+                HopsworksError::Validation(msg)
+            }
+            #[cfg(feature = "arrow")]
+            HopsworksKafkaError::Arrow(e) => HopsworksError::other(e),
+            HopsworksKafkaError::Join(e) => HopsworksError::other(e),
+            HopsworksKafkaError::WorkerChannel(_) => HopsworksError::other(err),
+        }
+    }
+}
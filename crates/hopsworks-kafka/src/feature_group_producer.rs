@@ -0,0 +1,116 @@
+use polars::prelude::DataFrame;
+use rdkafka::message::OwnedHeaders;
+use rdkafka::producer::{FutureProducer, Producer};
+use rdkafka::ClientConfig;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hopsworks_core::platform::job_execution::JobExecution;
+
+use crate::error::Result;
+use crate::helper::get_kafka_offsets;
+use crate::insert::{
+    log_failed_deliveries, setup_feature_group_producer, start_offline_materialization,
+    MaterializationOperation,
+};
+use crate::kafka_configuration::KafkaProducerOptions;
+use crate::produce_polars_df::produce_df_with_producers;
+use crate::report::{DeliveryRetryPolicy, InsertReport};
+use crate::target::FeatureGroupTarget;
+
+/// Long-lived producer writing into the online topic of a registered feature group.
+///
+/// Unlike [`insert_in_registered_feature_group`](crate::insert::insert_in_registered_feature_group),
+/// the Kafka producer, its configuration and the message headers are set up once and reused
+/// by every call to [`produce`](Self::produce). The offline materialization job is only started
+/// when calling [`start_offline_materialization`](Self::start_offline_materialization), from the
+/// offsets of the topic saved before the first call to `produce` since the previous materialization.
+/// A single producer is shared by the concurrent chunks of each call, regardless of
+/// [`KafkaProducerOptions::multi_producer`].
+pub struct FeatureGroupProducer {
+    producer: FutureProducer,
+    kafka_config: ClientConfig,
+    headers: OwnedHeaders,
+    topic_name: Arc<String>,
+    target: FeatureGroupTarget,
+    // Offsets of the topic before the rows not materialized yet, see `get_kafka_offsets`
+    checkpoint: Mutex<Option<String>>,
+}
+
+impl FeatureGroupProducer {
+    #[tracing::instrument]
+    pub async fn new(
+        target: FeatureGroupTarget,
+        producer_options: &KafkaProducerOptions,
+    ) -> Result<Self> {
+        let (kafka_config, headers) =
+            setup_feature_group_producer(&target, producer_options).await?;
+        let producer: FutureProducer = kafka_config.create()?;
+
+        Ok(Self {
+            producer,
+            kafka_config,
+            headers,
+            topic_name: Arc::new(target.online_topic_name.clone()),
+            target,
+            checkpoint: Mutex::new(None),
+        })
+    }
+
+    pub fn topic_name(&self) -> &str {
+        self.topic_name.as_str()
+    }
+
+    /// Produce the rows of `dataframe` on the online topic, see
+    /// [`insert_in_registered_feature_group`](crate::insert::insert_in_registered_feature_group).
+    #[tracing::instrument(
+        skip(self, dataframe),
+        fields(topic = %self.topic_name, df_rows = dataframe.height()))]
+    pub async fn produce(
+        &self,
+        dataframe: &mut DataFrame,
+        retry_policy: &DeliveryRetryPolicy,
+    ) -> Result<InsertReport> {
+        self.save_checkpoint()?;
+        let report = produce_df_with_producers(
+            self.headers.clone(),
+            self.topic_name.clone(),
+            self.target.primary_keys(),
+            |_| Ok(self.producer.clone()),
+            retry_policy,
+            dataframe,
+        )
+        .await?;
+        log_failed_deliveries(&report, self.topic_name.as_str());
+        Ok(report)
+    }
+
+    /// Start the job materializing the rows produced since the previous materialization
+    /// in the offline feature store.
+    pub async fn start_offline_materialization(&self) -> Result<JobExecution> {
+        self.producer.flush(Duration::from_secs(120))?;
+        self.save_checkpoint()?;
+        let checkpoint = self.lock_checkpoint().take().unwrap_or_default();
+        start_offline_materialization(&self.target, &checkpoint, MaterializationOperation::Upsert)
+            .await
+    }
+
+    /// Save the current offsets of the topic unless rows were produced since the previous materialization.
+    fn save_checkpoint(&self) -> Result<()> {
+        let mut checkpoint = self.lock_checkpoint();
+        if checkpoint.is_none() {
+            *checkpoint = Some(get_kafka_offsets(
+                self.kafka_config.clone(),
+                self.topic_name.as_str(),
+                true,
+            )?);
+        }
+        Ok(())
+    }
+
+    fn lock_checkpoint(&self) -> std::sync::MutexGuard<'_, Option<String>> {
+        self.checkpoint
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 use rdkafka::{
     consumer::{BaseConsumer, Consumer},
     message::{Header, OwnedHeaders},
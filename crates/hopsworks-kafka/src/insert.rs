@@ -1,70 +1,248 @@
-use color_eyre::Result;
+use crate::error::Result;
 use polars::prelude::*;
+use rdkafka::{message::OwnedHeaders, ClientConfig};
 use std::sync::Arc;
 
 use hopsworks_core::controller::feature_store::storage_connector;
-use hopsworks_core::platform::job_execution::JobExecution;
 use hopsworks_core::controller::platform::job_execution;
 use hopsworks_core::controller::platform::kafka::get_kafka_topic_subject;
-use hopsworks_core::get_hopsworks_client;
+use hopsworks_core::platform::job_execution::JobExecution;
+use hopsworks_core::{get_hopsworks_client, HopsworksError};
 
-use crate::kafka_configuration::setup_kafka_configuration;
 use crate::helper::{get_kafka_offsets, make_custom_headers};
-use crate::produce_polars_df::produce_df;
+use crate::kafka_configuration::{setup_kafka_configuration, KafkaProducerOptions};
+use crate::produce_polars_df::{produce_df, produce_tombstones};
+use crate::report::{DeliveryRetryPolicy, InsertReport};
+use crate::target::FeatureGroupTarget;
 
 #[tracing::instrument(
     skip(dataframe),
     fields(df_rows = dataframe.height(), df_columns = dataframe.width()))]
 pub async fn insert_in_registered_feature_group(
     dataframe: &mut DataFrame,
-    feature_store_id: i32,
-    feature_group_id: i32,
-    feature_group_name: &str,
-    feature_group_version: i32,
-    online_topic_name: &str,
-    primary_keys: &[&str],
-    cert_dir: &str,
-) -> Result<JobExecution> {
-    let kafka_connector =
-        storage_connector::get_feature_store_kafka_connector(feature_store_id, true).await?;
-    let kafka_config = setup_kafka_configuration(kafka_connector, cert_dir)?;
-
-    let subject = get_kafka_topic_subject(format!("{}_{}", feature_group_name, feature_group_version).as_str(), None).await?;
-    let project_id = get_hopsworks_client()
-            .await
-            .get_project_id()
-            .lock()
-            .await
-            .expect("Project ID not set, login to Hopsworks to set it");
+    target: &FeatureGroupTarget,
+    retry_policy: &DeliveryRetryPolicy,
+    producer_options: &KafkaProducerOptions,
+) -> Result<(JobExecution, InsertReport)> {
+    let (kafka_config, headers) = setup_feature_group_producer(target, producer_options).await?;
+    let kafka_offsets = get_kafka_offsets(
+        kafka_config.clone(),
+        target.online_topic_name.as_str(),
+        true,
+    )?;
 
     // These value are wrapped into an Arc to allow read-only access across threads
     // meaning clone only increases the ref count, no extra-memory is allocated
-    let topic_name = Arc::new(online_topic_name.to_string());
-    let headers = make_custom_headers(
-        project_id,
-        feature_group_id,
-        subject.id(),
-        feature_group_version,
-    );
+    let topic_name = Arc::new(target.online_topic_name.clone());
 
-    produce_df(
+    let report = produce_df(
         headers,
         topic_name.clone(),
-        primary_keys.to_vec(),
+        target.primary_keys(),
         kafka_config.clone(),
+        producer_options.multi_producer(),
+        retry_policy,
         dataframe,
     )
     .await?;
+    log_failed_deliveries(&report, topic_name.as_str());
 
-    let kafka_offsets = get_kafka_offsets(kafka_config, topic_name.as_str(), true)?;
+    // Materialize the delivered rows even if some deliveries failed,
+    // the caller finds the failed rows in the report to insert them again.
+    let job_execution =
+        start_offline_materialization(target, &kafka_offsets, MaterializationOperation::Upsert)
+            .await?;
+    Ok((job_execution, report))
+}
+
+#[cfg(feature = "arrow")]
+pub use crate::produce_arrow::{cast_to_feature_types, project_to_features};
 
-    let job_name = format!(
-        "{}_{}_offline_fg_materialization",
-        feature_group_name, feature_group_version
+/// Insert the rows of the arrow-rs record `batches`, e.g. a [`RecordBatchReader`](arrow::record_batch::RecordBatchReader),
+/// in the feature group and start the offline materialization job. Unlike [`insert_in_registered_feature_group`],
+/// the rows are serialized to Avro without converting them to a polars DataFrame.
+/// The columns of the batches must follow the order of the features, see [`project_to_features`],
+/// and have their types, see [`cast_to_feature_types`].
+#[cfg(feature = "arrow")]
+#[tracing::instrument(skip(batches))]
+pub async fn insert_record_batches_in_registered_feature_group<I>(
+    batches: I,
+    target: &FeatureGroupTarget,
+    retry_policy: &DeliveryRetryPolicy,
+    producer_options: &KafkaProducerOptions,
+) -> Result<(JobExecution, InsertReport)>
+where
+    I: IntoIterator<
+        Item = std::result::Result<arrow::record_batch::RecordBatch, arrow::error::ArrowError>,
+    >,
+{
+    let (kafka_config, headers) = setup_feature_group_producer(target, producer_options).await?;
+    let kafka_offsets = get_kafka_offsets(
+        kafka_config.clone(),
+        target.online_topic_name.as_str(),
+        true,
+    )?;
+
+    let report = crate::produce_arrow::produce_record_batches(
+        headers,
+        target.online_topic_name.as_str(),
+        target.primary_keys().as_slice(),
+        kafka_config,
+        retry_policy,
+        batches,
+    )
+    .await?;
+    log_failed_deliveries(&report, target.online_topic_name.as_str());
+
+    let job_execution =
+        start_offline_materialization(target, &kafka_offsets, MaterializationOperation::Upsert)
+            .await?;
+    Ok((job_execution, report))
+}
+
+/// Delete the rows of the feature group with the primary keys of `dataframe`, other columns are ignored.
+///
+/// A tombstone, i.e. a message without payload, is produced on the online topic for each primary key,
+/// with the same headers as the inserted rows. The online feature store deletes the rows on receipt,
+/// the offline materialization job is started with [`MaterializationOperation::Delete`] to delete them
+/// from the offline feature store. Rows with a null primary key are rejected before producing anything.
+#[tracing::instrument(
+    skip(dataframe),
+    fields(df_rows = dataframe.height(), df_columns = dataframe.width()))]
+pub async fn delete_records_in_registered_feature_group(
+    dataframe: &DataFrame,
+    target: &FeatureGroupTarget,
+    producer_options: &KafkaProducerOptions,
+) -> Result<JobExecution> {
+    let primary_keys = target.primary_keys();
+    let missing_primary_keys: Vec<&str> = primary_keys
+        .iter()
+        .filter(|pk| dataframe.column(pk).is_err())
+        .copied()
+        .collect();
+    if primary_keys.is_empty() || !missing_primary_keys.is_empty() {
+        return Err(HopsworksError::Validation(format!(
+            "Deleting records requires the primary key columns {:?} of feature group {}, missing {:?}.",
+            primary_keys, target.feature_group_name, missing_primary_keys
+        ))
+        .into());
+    }
+    let primary_key_df = dataframe.select(primary_keys.iter().copied())?;
+
+    let (kafka_config, headers) = setup_feature_group_producer(target, producer_options).await?;
+    let kafka_offsets = get_kafka_offsets(
+        kafka_config.clone(),
+        target.online_topic_name.as_str(),
+        true,
+    )?;
+
+    produce_tombstones(
+        headers,
+        target.online_topic_name.as_str(),
+        primary_keys,
+        kafka_config,
+        &primary_key_df,
+    )
+    .await?;
+
+    start_offline_materialization(target, &kafka_offsets, MaterializationOperation::Delete).await
+}
+
+pub(crate) fn log_failed_deliveries(report: &InsertReport, topic_name: &str) {
+    if !report.is_complete() {
+        tracing::warn!(
+            "{} of {} rows could not be delivered to topic {}, first failure: {:?}",
+            report.failed.len(),
+            report.rows,
+            topic_name,
+            report.failed.first()
+        );
+    }
+}
+
+/// Kafka producer configuration and message headers to write into a registered feature group.
+pub(crate) async fn setup_feature_group_producer(
+    target: &FeatureGroupTarget,
+    producer_options: &KafkaProducerOptions,
+) -> Result<(ClientConfig, OwnedHeaders)> {
+    let kafka_connector =
+        storage_connector::get_feature_store_kafka_connector(target.feature_store_id, true).await?;
+    let kafka_config =
+        setup_kafka_configuration(kafka_connector, target.cert_dir.as_str(), producer_options)?;
+
+    let subject = get_kafka_topic_subject(target.subject_name().as_str(), None).await?;
+    let project_id = get_hopsworks_client()
+        .await?
+        .get_project_id()
+        .lock()
+        .await
+        .ok_or_else(|| {
+            HopsworksError::Auth("Project ID not set, login to Hopsworks to set it".to_string())
+        })?;
+
+    let headers = make_custom_headers(
+        project_id,
+        target.feature_group_id,
+        subject.id(),
+        target.feature_group_version,
     );
-    let job_args = format!(" -initialCheckpointString {topic_name}{kafka_offsets}");
+    Ok((kafka_config, headers))
+}
+
+/// Operation applied by the offline materialization job to the messages it reads from the online topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MaterializationOperation {
+    /// Upsert the rows of the messages, the default of the job.
+    Upsert,
+    /// Delete the rows with the keys of the tombstones, passed to the job as `-operation delete`.
+    Delete,
+}
+
+/// Arguments of the materialization job, reading the online topic from the checkpoint `kafka_offsets`
+/// as formatted by [`get_kafka_offsets`], i.e. `partition:offset` pairs separated by commas.
+/// The checkpoint is `topic,partition:offset,...` as written by the Python client.
+fn materialization_job_args(
+    topic_name: &str,
+    kafka_offsets: &str,
+    operation: MaterializationOperation,
+) -> String {
+    let job_args = format!(" -initialCheckpointString {topic_name},{kafka_offsets}");
+    match operation {
+        MaterializationOperation::Upsert => job_args,
+        MaterializationOperation::Delete => format!("{job_args} -operation delete"),
+    }
+}
+
+/// Start the job materializing the messages of the online topic in the offline feature store,
+/// from the `kafka_offsets` of the topic read with [`get_kafka_offsets`] before producing them.
+pub(crate) async fn start_offline_materialization(
+    target: &FeatureGroupTarget,
+    kafka_offsets: &str,
+    operation: MaterializationOperation,
+) -> Result<JobExecution> {
+    let job_name = target.materialization_job_name();
+    let job_args =
+        materialization_job_args(target.online_topic_name.as_str(), kafka_offsets, operation);
 
     Ok(JobExecution::from(
-        job_execution::start_new_execution_for_named_job(job_name.as_str(), Some(&job_args)).await?,
+        job_execution::start_new_execution_for_named_job(job_name.as_str(), Some(&job_args))
+            .await?,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_materialization_job_args() {
+        assert_eq!(
+            materialization_job_args("fg_topic", "0:10,1:12", MaterializationOperation::Upsert),
+            " -initialCheckpointString fg_topic,0:10,1:12"
+        );
+        assert_eq!(
+            materialization_job_args("fg_topic", "0:10,1:12", MaterializationOperation::Delete),
+            " -initialCheckpointString fg_topic,0:10,1:12 -operation delete"
+        );
+    }
+}
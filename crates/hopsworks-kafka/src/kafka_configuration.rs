@@ -1,29 +1,354 @@
-use color_eyre::Result;
+use crate::error::Result;
 use rdkafka::ClientConfig;
+use std::collections::BTreeMap;
+use std::time::Duration;
 
 use hopsworks_core::feature_store::storage_connector::FeatureStoreKafkaConnector;
 
-#[tracing::instrument]
+/// Compression codec of the produced message batches, `compression.codec` in rdkafka.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionCodec {
+    #[default]
+    None,
+    Gzip,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+impl std::fmt::Display for CompressionCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let codec = match self {
+            CompressionCodec::None => "none",
+            CompressionCodec::Gzip => "gzip",
+            CompressionCodec::Snappy => "snappy",
+            CompressionCodec::Lz4 => "lz4",
+            CompressionCodec::Zstd => "zstd",
+        };
+        write!(f, "{codec}")
+    }
+}
+
+/// Number of in-sync replicas acknowledging a message before its delivery succeeds, `acks` in rdkafka.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Acks {
+    /// The delivery succeeds without waiting for the broker.
+    None,
+    /// The delivery succeeds once the leader has written the message.
+    Leader,
+    /// The delivery succeeds once all in-sync replicas have written the message.
+    All,
+}
+
+impl std::fmt::Display for Acks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let acks = match self {
+            Acks::None => "0",
+            Acks::Leader => "1",
+            Acks::All => "all",
+        };
+        write!(f, "{acks}")
+    }
+}
+
+/// Configuration of the Kafka producers writing into the online topic of a feature group.
+///
+/// Options left unset fall back to the `HOPSWORKS_KAFKA_*` environment variables, if set,
+/// then to the SDK defaults. The security settings are taken from the project and its certificates,
+/// `overrides` are applied last and can set any rdkafka property, see the
+/// [librdkafka documentation](https://github.com/confluentinc/librdkafka/blob/master/CONFIGURATION.md).
+/// As they may hold credentials, e.g. `sasl.password`, their values are redacted from the debug output and logs.
+///
+/// # Example
+/// ```
+/// use hopsworks_kafka::kafka_configuration::{Acks, CompressionCodec, KafkaProducerOptions};
+/// use std::time::Duration;
+///
+/// let options = KafkaProducerOptions::new()
+///     .with_linger(Duration::from_millis(50))
+///     .with_compression(CompressionCodec::Zstd)
+///     .with_acks(Acks::All)
+///     .with_idempotence(true)
+///     .with_override("statistics.interval.ms", "60000");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct KafkaProducerOptions {
+    bootstrap_servers: Option<String>,
+    linger: Option<Duration>,
+    batch_num_messages: Option<usize>,
+    batch_size: Option<usize>,
+    queue_buffering_max_messages: Option<usize>,
+    queue_buffering_max_kbytes: Option<usize>,
+    compression: Option<CompressionCodec>,
+    acks: Option<Acks>,
+    idempotence: Option<bool>,
+    message_timeout: Option<Duration>,
+    multi_producer: Option<bool>,
+    debug: Option<String>,
+    overrides: BTreeMap<String, String>,
+}
+
+impl std::fmt::Debug for KafkaProducerOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaProducerOptions")
+            .field("bootstrap_servers", &self.bootstrap_servers)
+            .field("linger", &self.linger)
+            .field("batch_num_messages", &self.batch_num_messages)
+            .field("batch_size", &self.batch_size)
+            .field(
+                "queue_buffering_max_messages",
+                &self.queue_buffering_max_messages,
+            )
+            .field(
+                "queue_buffering_max_kbytes",
+                &self.queue_buffering_max_kbytes,
+            )
+            .field("compression", &self.compression)
+            .field("acks", &self.acks)
+            .field("idempotence", &self.idempotence)
+            .field("message_timeout", &self.message_timeout)
+            .field("multi_producer", &self.multi_producer)
+            .field("debug", &self.debug)
+            .field("overrides", &self.overrides.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl KafkaProducerOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Brokers to connect to instead of the ones of the feature store Kafka connector,
+    /// falls back to `HOPSWORKS_KAFKA_BROKERS`.
+    pub fn with_bootstrap_servers(mut self, bootstrap_servers: impl Into<String>) -> Self {
+        self.bootstrap_servers = Some(bootstrap_servers.into());
+        self
+    }
+
+    /// Time to wait for messages to accumulate before sending a batch, `linger.ms` in rdkafka.
+    /// Falls back to `HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_MS`, defaults to 5ms.
+    pub fn with_linger(mut self, linger: Duration) -> Self {
+        self.linger = Some(linger);
+        self
+    }
+
+    /// Maximum number of messages in a batch. Falls back to
+    /// `HOPSWORKS_KAFKA_PRODUCER_BATCH_NUM_MESSAGES`, defaults to 10000.
+    pub fn with_batch_num_messages(mut self, batch_num_messages: usize) -> Self {
+        self.batch_num_messages = Some(batch_num_messages);
+        self
+    }
+
+    /// Maximum size of a batch in bytes, `batch.size` in rdkafka.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Maximum number of messages buffered by a producer. Falls back to
+    /// `HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_MESSAGES`, defaults to 100000.
+    pub fn with_queue_buffering_max_messages(mut self, max_messages: usize) -> Self {
+        self.queue_buffering_max_messages = Some(max_messages);
+        self
+    }
+
+    /// Maximum size of the messages buffered by a producer in kilobytes. Falls back to
+    /// `HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_KBYTES`, defaults to 4000000.
+    pub fn with_queue_buffering_max_kbytes(mut self, max_kbytes: usize) -> Self {
+        self.queue_buffering_max_kbytes = Some(max_kbytes);
+        self
+    }
+
+    pub fn with_compression(mut self, compression: CompressionCodec) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    pub fn with_acks(mut self, acks: Acks) -> Self {
+        self.acks = Some(acks);
+        self
+    }
+
+    /// Produce every message exactly once and in order, `enable.idempotence` in rdkafka.
+    /// Requires [`Acks::All`], which rdkafka sets if `acks` is left unset.
+    pub fn with_idempotence(mut self, idempotence: bool) -> Self {
+        self.idempotence = Some(idempotence);
+        self
+    }
+
+    /// Time after which an undelivered message fails, `message.timeout.ms` in rdkafka, defaults to 5 minutes.
+    pub fn with_message_timeout(mut self, message_timeout: Duration) -> Self {
+        self.message_timeout = Some(message_timeout);
+        self
+    }
+
+    /// Create a producer per chunk of the inserted DataFrame instead of sharing the first one
+    /// with the first chunk. Falls back to `HOPSWORKS_KAFKA_MULTI_PRODUCER`, defaults to `false`.
+    pub fn with_multi_producer(mut self, multi_producer: bool) -> Self {
+        self.multi_producer = Some(multi_producer);
+        self
+    }
+
+    /// Comma-separated rdkafka debug contexts, e.g. `broker,topic,msg`, logged at debug level.
+    /// Falls back to `HOPSWORKS_KAFKA_PRODUCER_LOG_DEBUG`.
+    pub fn with_debug(mut self, debug: impl Into<String>) -> Self {
+        self.debug = Some(debug.into());
+        self
+    }
+
+    /// Set the rdkafka property `key` to `value`, taking precedence over every other option.
+    pub fn with_override(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn overrides(&self) -> &BTreeMap<String, String> {
+        &self.overrides
+    }
+
+    /// Whether a producer is created per chunk of the inserted DataFrame, see [`Self::with_multi_producer`].
+    pub fn multi_producer(&self) -> bool {
+        self.multi_producer
+            .unwrap_or_else(|| env_var("HOPSWORKS_KAFKA_MULTI_PRODUCER").as_deref() == Some("true"))
+    }
+
+    fn debug(&self) -> Option<String> {
+        self.debug
+            .clone()
+            .or_else(|| env_var("HOPSWORKS_KAFKA_PRODUCER_LOG_DEBUG"))
+            // Kept for backward compatibility with the misspelled variable
+            .or_else(|| env_var("HOPOSWORKS_KAFKA_PRODUCER_LOG_DEBUG"))
+            .filter(|debug| !debug.is_empty())
+    }
+}
+
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok()
+}
+
+fn option_or_env<T: ToString>(option: Option<T>, key: &str, default: &str) -> String {
+    option
+        .map(|value| value.to_string())
+        .or_else(|| env_var(key))
+        .unwrap_or(default.to_string())
+}
+
+#[tracing::instrument(skip(options))]
 pub fn setup_kafka_configuration(
     kafka_connector: FeatureStoreKafkaConnector,
     cert_dir: &str,
+    options: &KafkaProducerOptions,
+) -> Result<ClientConfig> {
+    let bootstrap_servers = option_or_env(
+        options.bootstrap_servers.as_ref(),
+        "HOPSWORKS_KAFKA_BROKERS",
+        kafka_connector.bootstrap_servers(),
+    );
+    let queue_buffering_max_ms = option_or_env(
+        options.linger.map(|linger| linger.as_millis()),
+        "HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_MS",
+        "5",
+    ); // Equivalent to linger.ms
+    let batch_num_messages = option_or_env(
+        options.batch_num_messages,
+        "HOPSWORKS_KAFKA_PRODUCER_BATCH_NUM_MESSAGES",
+        "10000",
+    );
+    let queue_buffering_max_messages = option_or_env(
+        options.queue_buffering_max_messages,
+        "HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_MESSAGES",
+        "100000",
+    );
+    let queue_buffering_max_kbytes = option_or_env(
+        options.queue_buffering_max_kbytes,
+        "HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_KBYTES",
+        "4000000",
+    );
+    let message_timeout_ms = options
+        .message_timeout
+        .map_or("300000".to_string(), |timeout| {
+            timeout.as_millis().to_string()
+        });
+
+    let mut config = hopsworks_ssl_configuration(bootstrap_servers, cert_dir);
+    config
+        .set("message.timeout.ms", message_timeout_ms)
+        .set("queue.buffering.max.ms", queue_buffering_max_ms)
+        .set("batch.num.messages", batch_num_messages)
+        .set("queue.buffering.max.messages", queue_buffering_max_messages)
+        .set("queue.buffering.max.kbytes", queue_buffering_max_kbytes);
+
+    if let Some(batch_size) = options.batch_size {
+        config.set("batch.size", batch_size.to_string());
+    }
+    if let Some(compression) = options.compression {
+        config.set("compression.codec", compression.to_string());
+    }
+    if let Some(acks) = options.acks {
+        config.set("acks", acks.to_string());
+    }
+    if let Some(idempotence) = options.idempotence {
+        config.set("enable.idempotence", idempotence.to_string());
+    }
+    if let Some(debug_kafka) = options.debug() {
+        config.set("debug", debug_kafka);
+        config.set_log_level(rdkafka::config::RDKafkaLogLevel::Debug);
+    }
+    for (key, value) in options.overrides.iter() {
+        config.set(key, value);
+    }
+    tracing::info!("Setting up Hopsworks Kafka producer");
+    tracing::debug!(
+        "Kafka producer config: {:#?}",
+        redacted_config(&config, options)
+    );
+
+    Ok(config)
+}
+
+/// Properties of `config` with the values of the `overrides` of `options` redacted.
+fn redacted_config<'a>(
+    config: &'a ClientConfig,
+    options: &KafkaProducerOptions,
+) -> BTreeMap<&'a str, &'a str> {
+    config
+        .config_map()
+        .iter()
+        .map(|(key, value)| {
+            let value = if options.overrides.contains_key(key) {
+                "<redacted>"
+            } else {
+                value.as_str()
+            };
+            (key.as_str(), value)
+        })
+        .collect()
+}
+
+/// Configuration of a consumer in the consumer group `group_id`, reading the project topics with the
+/// project certificates. Offsets are not committed, the consumer decides where to start reading.
+#[tracing::instrument]
+pub fn setup_kafka_consumer_configuration(
+    kafka_connector: FeatureStoreKafkaConnector,
+    cert_dir: &str,
+    group_id: &str,
 ) -> Result<ClientConfig> {
-    let bootstrap_servers = std::env::var("HOPSWORKS_KAFKA_BROKERS")
+    let bootstrap_servers = env_var("HOPSWORKS_KAFKA_BROKERS")
         .unwrap_or(kafka_connector.bootstrap_servers().to_string());
 
-    // Experiment with different configurations
-    let queue_buffering_max_ms =
-        std::env::var("HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_MS").unwrap_or("5".to_string()); // Equivalent to linger.ms
-    let batch_num_messages =
-        std::env::var("HOPSWORKS_KAFKA_PRODUCER_BATCH_NUM_MESSAGES").unwrap_or("10000".to_string());
-    let queue_buffering_max_messages =
-        std::env::var("HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_MESSAGES")
-            .unwrap_or("100000".to_string());
-    let queue_buffering_max_kbytes =
-        std::env::var("HOPSWORKS_KAFKA_PRODUCER_QUEUE_BUFFERING_MAX_KBYTES")
-            .unwrap_or("4000000".to_string());
-    let log_debug_kafka = std::env::var("HOPOSWORKS_KAFKA_PRODUCER_LOG_DEBUG");
+    let mut config = hopsworks_ssl_configuration(bootstrap_servers, cert_dir);
+    config
+        .set("group.id", group_id)
+        .set("enable.auto.commit", "false")
+        .set("enable.partition.eof", "false");
+    tracing::info!("Setting up Hopsworks Kafka consumer");
+    tracing::debug!("Kafka consumer config: {:#?}", config);
+
+    Ok(config)
+}
 
+fn hopsworks_ssl_configuration(bootstrap_servers: String, cert_dir: &str) -> ClientConfig {
     let mut config = ClientConfig::new();
     config
         .set("bootstrap.servers", bootstrap_servers)
@@ -35,30 +360,9 @@ pub fn setup_kafka_configuration(
             "ssl.certificate.location",
             format!("{cert_dir}/client_cert.pem"),
         )
-        .set("ssl.key.location", format!("{cert_dir}/client_key.pem"))
-        // End of Hopsworks specific configuration
-        .set("message.timeout.ms", "300000")
-        .set("queue.buffering.max.ms", queue_buffering_max_ms.as_str())
-        .set("batch.num.messages", batch_num_messages.as_str())
-        .set(
-            "queue.buffering.max.messages",
-            queue_buffering_max_messages.as_str(),
-        )
-        .set(
-            "queue.buffering.max.kbytes",
-            queue_buffering_max_kbytes.as_str(),
-        );
-
-    if let Ok(debug_kafka) = log_debug_kafka {
-        if !debug_kafka.is_empty() {
-            config.set("debug", debug_kafka.as_str());
-            config.set_log_level(rdkafka::config::RDKafkaLogLevel::Debug);
-        }
-    }
-    tracing::info!("Setting up Hopsworks Kafka producer");
-    tracing::debug!("Kafka producer config: {:#?}", config);
-
-    Ok(config)
+        .set("ssl.key.location", format!("{cert_dir}/client_key.pem"));
+    // End of Hopsworks specific configuration
+    config
 }
 
 #[cfg(test)]
@@ -72,9 +376,59 @@ mod tests {
         let cert_dir = "test_cert_dir";
 
         // Act
-        let result = setup_kafka_configuration(kafka_connector, cert_dir);
+        let result =
+            setup_kafka_configuration(kafka_connector, cert_dir, &KafkaProducerOptions::default());
 
         // Assert
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_setup_kafka_configuration_with_options() {
+        // Arrange
+        let kafka_connector = FeatureStoreKafkaConnector::new_test();
+        let options = KafkaProducerOptions::new()
+            .with_bootstrap_servers("broker:9091")
+            .with_linger(Duration::from_millis(50))
+            .with_batch_size(1_000_000)
+            .with_compression(CompressionCodec::Zstd)
+            .with_acks(Acks::All)
+            .with_idempotence(true)
+            .with_message_timeout(Duration::from_secs(30))
+            .with_override("batch.num.messages", "42");
+
+        // Act
+        let config = setup_kafka_configuration(kafka_connector, "test_cert_dir", &options).unwrap();
+
+        // Assert
+        assert_eq!(config.get("bootstrap.servers"), Some("broker:9091"));
+        assert_eq!(config.get("queue.buffering.max.ms"), Some("50"));
+        assert_eq!(config.get("batch.size"), Some("1000000"));
+        assert_eq!(config.get("compression.codec"), Some("zstd"));
+        assert_eq!(config.get("acks"), Some("all"));
+        assert_eq!(config.get("enable.idempotence"), Some("true"));
+        assert_eq!(config.get("message.timeout.ms"), Some("30000"));
+        assert_eq!(config.get("batch.num.messages"), Some("42"));
+        assert_eq!(config.get("security.protocol"), Some("SSL"));
+    }
+
+    #[test]
+    fn test_override_values_are_redacted() {
+        // Arrange
+        let kafka_connector = FeatureStoreKafkaConnector::new_test();
+        let options = KafkaProducerOptions::new()
+            .with_acks(Acks::All)
+            .with_override("sasl.password", "my_password");
+
+        // Act
+        let config = setup_kafka_configuration(kafka_connector, "test_cert_dir", &options).unwrap();
+        let logged_config = format!("{:?}", redacted_config(&config, &options));
+
+        // Assert
+        assert_eq!(config.get("sasl.password"), Some("my_password"));
+        assert!(!logged_config.contains("my_password"));
+        assert!(logged_config.contains("sasl.password"));
+        assert!(!format!("{options:?}").contains("my_password"));
+        assert!(format!("{options:?}").contains("sasl.password"));
+    }
 }
@@ -1,4 +1,11 @@
+pub mod consume;
+pub mod error;
+pub mod feature_group_producer;
 mod helper;
 pub mod insert;
 pub mod kafka_configuration;
+#[cfg(feature = "arrow")]
+mod produce_arrow;
 mod produce_polars_df;
+pub mod report;
+pub mod target;
@@ -0,0 +1,524 @@
+//! Produce arrow-rs [`RecordBatch`]es on the online topic of a feature group, serialized to Avro
+//! without converting them to polars.
+//!
+//! Rows are encoded as Avro records whose fields are all in a union with null. Complex features,
+//! i.e. lists and structs, are encoded on their own and written in the record as bytes,
+//! the same way as [`produce_df`](crate::produce_polars_df::produce_df) encodes polars DataFrames.
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::compute::{cast_with_options, CastOptions};
+use arrow::datatypes::{
+    DataType, Date32Type, Decimal128Type, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, Schema, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use rdkafka::message::OwnedHeaders;
+use rdkafka::producer::{FutureProducer, Producer};
+use rdkafka::ClientConfig;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Result;
+use crate::report::{send_with_retry, DeliveryRetryPolicy, InsertReport};
+use hopsworks_core::HopsworksError;
+
+/// Produce the rows of `batches` on `topic_name`, keyed by the concatenation of their primary key values.
+/// Failed deliveries are retried following `retry_policy` and reported in the returned [`InsertReport`].
+///
+/// Rows are serialized in the order of the columns, which must be the order of the features in the
+/// subject schema of the feature group, see [`project_to_features`], and have the Avro types of
+/// the features, see [`cast_to_feature_types`].
+#[tracing::instrument(skip(batches, producer_config))]
+pub async fn produce_record_batches<I>(
+    headers: OwnedHeaders,
+    topic_name: &str,
+    primary_keys: &[&str],
+    producer_config: ClientConfig,
+    retry_policy: &DeliveryRetryPolicy,
+    batches: I,
+) -> Result<InsertReport>
+where
+    I: IntoIterator<Item = std::result::Result<RecordBatch, ArrowError>>,
+{
+    let start_time = std::time::Instant::now();
+    let producer: FutureProducer = producer_config.create()?;
+    let retry_policy = Arc::new(retry_policy.clone());
+    let mut report = InsertReport::default();
+
+    for batch in batches {
+        let batch = batch?;
+        let primary_key_columns = primary_keys
+            .iter()
+            .map(|pk| {
+                batch.column_by_name(pk).ok_or_else(|| {
+                    HopsworksError::Validation(format!(
+                        "Primary key column {pk} missing from the record batch."
+                    ))
+                })
+            })
+            .collect::<std::result::Result<Vec<&ArrayRef>, HopsworksError>>()?;
+
+        let mut produced_handles = tokio::task::JoinSet::new();
+        for row in 0..batch.num_rows() {
+            let composite_key = composite_key(&primary_key_columns, row)?;
+            let mut payload = vec![];
+            serialize_row(batch.columns(), row, &mut payload)?;
+
+            let producer = producer.clone();
+            let topic_name = topic_name.to_string();
+            let headers = headers.clone();
+            let retry_policy = retry_policy.clone();
+            produced_handles.spawn(async move {
+                send_with_retry(
+                    &producer,
+                    topic_name.as_str(),
+                    composite_key,
+                    &payload,
+                    headers,
+                    &retry_policy,
+                )
+                .await
+            });
+        }
+        while let Some(res) = produced_handles.join_next().await {
+            report.record(res?);
+        }
+    }
+    producer.flush(Duration::from_secs(120))?;
+    report.elapsed = start_time.elapsed();
+    tracing::debug!(
+        "Produced {} rows from record batches in {:?}",
+        report.rows,
+        report.elapsed
+    );
+    Ok(report)
+}
+
+/// Reorder the columns of `batch` following `feature_names`, the features of the feature group.
+/// Fails if a feature has no column in the batch or a column is not a feature.
+pub fn project_to_features(
+    batch: RecordBatch,
+    feature_names: &[&str],
+) -> std::result::Result<RecordBatch, ArrowError> {
+    let schema = batch.schema();
+    if let Some(field) = schema
+        .fields()
+        .iter()
+        .find(|field| !feature_names.contains(&field.name().as_str()))
+    {
+        return Err(ArrowError::SchemaError(format!(
+            "Column {} is not a feature of the Feature Group.",
+            field.name()
+        )));
+    }
+    let indices = feature_names
+        .iter()
+        .map(|name| {
+            schema.index_of(name).map_err(|_| {
+                ArrowError::SchemaError(format!(
+                    "Feature {name} of the Feature Group is missing from the record batch."
+                ))
+            })
+        })
+        .collect::<std::result::Result<Vec<usize>, ArrowError>>()?;
+    batch.project(&indices)
+}
+
+/// Cast the columns of `batch` to the Avro types of the features, `feature_types` being the type of
+/// the feature of each column, e.g. a Float64 column of a `float` feature to Float32.
+/// Fails if a column cannot be cast to its feature type, e.g. a string column of a `bigint`
+/// feature, or if a value does not fit the feature type.
+pub fn cast_to_feature_types(
+    batch: RecordBatch,
+    feature_types: &[&str],
+) -> std::result::Result<RecordBatch, ArrowError> {
+    let schema = batch.schema();
+    if feature_types.len() != batch.num_columns() {
+        return Err(ArrowError::SchemaError(format!(
+            "Record batch has {} columns for {} features.",
+            batch.num_columns(),
+            feature_types.len()
+        )));
+    }
+    let mut fields = Vec::with_capacity(batch.num_columns());
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    for ((field, column), feature_type) in schema
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .zip(feature_types)
+    {
+        let feature_type = feature_type.to_lowercase().replace(' ', "");
+        if is_compatible(&feature_type, column.data_type()) {
+            fields.push(field.clone());
+            columns.push(column.clone());
+            continue;
+        }
+        let target = arrow_data_type(&feature_type)
+            .filter(|target| is_castable(target, column.data_type()))
+            .ok_or_else(|| {
+                ArrowError::SchemaError(format!(
+                    "Column {} has type {}, which cannot be inserted in a {} feature.",
+                    field.name(),
+                    column.data_type(),
+                    feature_type
+                ))
+            })?;
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        let casted = cast_with_options(column, &target, &options).map_err(|e| {
+            ArrowError::SchemaError(format!(
+                "Cannot cast column {} to {}: {}",
+                field.name(),
+                feature_type,
+                e
+            ))
+        })?;
+        fields.push(Arc::new(field.as_ref().clone().with_data_type(target)));
+        columns.push(casted);
+    }
+    RecordBatch::try_new(
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone())),
+        columns,
+    )
+}
+
+// Types serialized as the Avro type of the feature without casting
+fn is_compatible(feature_type: &str, data_type: &DataType) -> bool {
+    match feature_type {
+        "boolean" => *data_type == DataType::Boolean,
+        "tinyint" | "smallint" | "int" => matches!(
+            data_type,
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16
+        ),
+        "bigint" => data_type.is_integer(),
+        "float" => *data_type == DataType::Float32,
+        "double" => *data_type == DataType::Float64,
+        "string" => matches!(
+            data_type,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+        ),
+        "binary" => matches!(
+            data_type,
+            DataType::Binary | DataType::LargeBinary | DataType::BinaryView
+        ),
+        "date" => *data_type == DataType::Date32,
+        "timestamp" => matches!(data_type, DataType::Timestamp(_, _)),
+        // Complex features can also be inserted already encoded
+        _ if feature_type.starts_with("array<") => matches!(
+            data_type,
+            DataType::List(_)
+                | DataType::LargeList(_)
+                | DataType::FixedSizeList(_, _)
+                | DataType::Binary
+        ),
+        _ if feature_type.starts_with("struct<") => {
+            matches!(data_type, DataType::Struct(_) | DataType::Binary)
+        }
+        _ if feature_type.starts_with("decimal") => {
+            arrow_data_type(feature_type).as_ref() == Some(data_type)
+        }
+        // Unknown feature types are left to the schema registry
+        _ => true,
+    }
+}
+
+fn is_castable(target: &DataType, data_type: &DataType) -> bool {
+    (data_type.is_numeric() && target.is_numeric())
+        || (data_type.is_temporal() && target.is_temporal())
+}
+
+// Arrow type to cast a column to for the feature type, if it is not complex
+fn arrow_data_type(feature_type: &str) -> Option<DataType> {
+    let data_type = match feature_type {
+        "boolean" => DataType::Boolean,
+        "tinyint" | "smallint" | "int" => DataType::Int32,
+        "bigint" => DataType::Int64,
+        "float" => DataType::Float32,
+        "double" => DataType::Float64,
+        "string" => DataType::Utf8,
+        "binary" => DataType::Binary,
+        "date" => DataType::Date32,
+        "timestamp" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        _ => {
+            let (precision, scale) = feature_type
+                .strip_prefix("decimal(")?
+                .strip_suffix(')')?
+                .split_once(',')?;
+            DataType::Decimal128(precision.parse().ok()?, scale.parse().ok()?)
+        }
+    };
+    Some(data_type)
+}
+
+fn composite_key(primary_key_columns: &[&ArrayRef], row: usize) -> Result<String> {
+    let mut composite_key = String::new();
+    for column in primary_key_columns {
+        if column.is_null(row) {
+            return Err(HopsworksError::Validation(format!(
+                "Primary key value of row {row} is null."
+            ))
+            .into());
+        }
+        let formatter = ArrayFormatter::try_new(column.as_ref(), &FormatOptions::default())?;
+        composite_key.push_str(&formatter.value(row).to_string());
+    }
+    Ok(composite_key)
+}
+
+/// Append the Avro encoding of the row `row` of `columns` to `buf`.
+fn serialize_row(columns: &[ArrayRef], row: usize, buf: &mut Vec<u8>) -> Result<()> {
+    for column in columns {
+        if is_complex(column.data_type()) && column.is_valid(row) {
+            let mut encoded = vec![];
+            write_nullable_value(column.as_ref(), row, &mut encoded)?;
+            write_long(1, buf);
+            write_bytes(&encoded, buf);
+        } else {
+            write_nullable_value(column.as_ref(), row, buf)?;
+        }
+    }
+    Ok(())
+}
+
+fn is_complex(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::List(_)
+            | DataType::LargeList(_)
+            | DataType::FixedSizeList(_, _)
+            | DataType::Struct(_)
+    )
+}
+
+// Value in a union with null, null being the first variant
+fn write_nullable_value(array: &dyn Array, row: usize, buf: &mut Vec<u8>) -> Result<()> {
+    if array.is_null(row) {
+        write_long(0, buf);
+        return Ok(());
+    }
+    write_long(1, buf);
+    write_value(array, row, buf)
+}
+
+fn write_value(array: &dyn Array, row: usize, buf: &mut Vec<u8>) -> Result<()> {
+    match array.data_type() {
+        DataType::Boolean => buf.push(array.as_boolean().value(row) as u8),
+        DataType::Int8 => write_long(array.as_primitive::<Int8Type>().value(row) as i64, buf),
+        DataType::Int16 => write_long(array.as_primitive::<Int16Type>().value(row) as i64, buf),
+        DataType::Int32 => write_long(array.as_primitive::<Int32Type>().value(row) as i64, buf),
+        DataType::Int64 => write_long(array.as_primitive::<Int64Type>().value(row), buf),
+        DataType::UInt8 => write_long(array.as_primitive::<UInt8Type>().value(row) as i64, buf),
+        DataType::UInt16 => write_long(array.as_primitive::<UInt16Type>().value(row) as i64, buf),
+        DataType::UInt32 => write_long(array.as_primitive::<UInt32Type>().value(row) as i64, buf),
+        DataType::UInt64 => {
+            let value = array.as_primitive::<UInt64Type>().value(row);
+            let value = i64::try_from(value).map_err(|_| {
+                HopsworksError::Validation(format!(
+                    "UInt64 value {value} does not fit in a bigint feature."
+                ))
+            })?;
+            write_long(value, buf)
+        }
+        DataType::Float32 => {
+            buf.extend_from_slice(&array.as_primitive::<Float32Type>().value(row).to_le_bytes())
+        }
+        DataType::Float64 => {
+            buf.extend_from_slice(&array.as_primitive::<Float64Type>().value(row).to_le_bytes())
+        }
+        DataType::Utf8 => write_bytes(array.as_string::<i32>().value(row).as_bytes(), buf),
+        DataType::LargeUtf8 => write_bytes(array.as_string::<i64>().value(row).as_bytes(), buf),
+        DataType::Utf8View => write_bytes(array.as_string_view().value(row).as_bytes(), buf),
+        DataType::Binary => write_bytes(array.as_binary::<i32>().value(row), buf),
+        DataType::LargeBinary => write_bytes(array.as_binary::<i64>().value(row), buf),
+        DataType::BinaryView => write_bytes(array.as_binary_view().value(row), buf),
+        // Days since epoch
+        DataType::Date32 => write_long(array.as_primitive::<Date32Type>().value(row) as i64, buf),
+        // Microseconds since epoch
+        DataType::Timestamp(unit, _) => {
+            let micros = match unit {
+                TimeUnit::Second => {
+                    array.as_primitive::<TimestampSecondType>().value(row) * 1_000_000
+                }
+                TimeUnit::Millisecond => {
+                    array.as_primitive::<TimestampMillisecondType>().value(row) * 1_000
+                }
+                TimeUnit::Microsecond => {
+                    array.as_primitive::<TimestampMicrosecondType>().value(row)
+                }
+                TimeUnit::Nanosecond => {
+                    array.as_primitive::<TimestampNanosecondType>().value(row) / 1_000
+                }
+            };
+            write_long(micros, buf)
+        }
+        // Two's complement big-endian unscaled value
+        DataType::Decimal128(_, _) => write_bytes(
+            &array
+                .as_primitive::<Decimal128Type>()
+                .value(row)
+                .to_be_bytes(),
+            buf,
+        ),
+        DataType::List(_) => write_items(array.as_list::<i32>().value(row).as_ref(), buf)?,
+        DataType::LargeList(_) => write_items(array.as_list::<i64>().value(row).as_ref(), buf)?,
+        DataType::FixedSizeList(_, _) => {
+            write_items(array.as_fixed_size_list().value(row).as_ref(), buf)?
+        }
+        DataType::Struct(_) => {
+            for column in array.as_struct().columns() {
+                write_nullable_value(column.as_ref(), row, buf)?;
+            }
+        }
+        other => {
+            return Err(HopsworksError::Validation(format!(
+                "Arrow data type {other} cannot be inserted into a feature group."
+            ))
+            .into())
+        }
+    }
+    Ok(())
+}
+
+// Avro array written as a single block followed by the empty block
+fn write_items(items: &dyn Array, buf: &mut Vec<u8>) -> Result<()> {
+    if !items.is_empty() {
+        write_long(items.len() as i64, buf);
+        for idx in 0..items.len() {
+            write_nullable_value(items, idx, buf)?;
+        }
+    }
+    write_long(0, buf);
+    Ok(())
+}
+
+// Zigzag encoded variable-length integer, used by Avro for int and long
+fn write_long(value: i64, buf: &mut Vec<u8>) {
+    let mut n = ((value << 1) ^ (value >> 63)) as u64;
+    while n & !0x7F != 0 {
+        buf.push(((n & 0x7F) | 0x80) as u8);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+fn write_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    write_long(bytes.len() as i64, buf);
+    buf.extend_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{
+        Float32Array, Float64Array, Int64Array, ListArray, StringArray, UInt64Array,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_serialize_row() {
+        let values = ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+        ]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(vec![1, -1])) as ArrayRef),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![Some("a"), None])) as ArrayRef,
+            ),
+            ("values", Arc::new(values) as ArrayRef),
+        ])
+        .unwrap();
+
+        let mut first_row = vec![];
+        serialize_row(batch.columns(), 0, &mut first_row).unwrap();
+        // id, name, then values encoded on their own as bytes
+        assert_eq!(
+            first_row,
+            vec![2, 2, 2, 2, b'a', 2, 14, 2, 4, 2, 2, 2, 4, 0]
+        );
+        let mut second_row = vec![];
+        serialize_row(batch.columns(), 1, &mut second_row).unwrap();
+        assert_eq!(second_row, vec![2, 1, 0, 0]);
+
+        let id_column = batch.column(0);
+        assert_eq!(composite_key(&[id_column], 1).unwrap(), "-1");
+    }
+
+    #[test]
+    fn test_project_to_features() {
+        let batch = RecordBatch::try_from_iter(vec![
+            ("name", Arc::new(StringArray::from(vec!["a"])) as ArrayRef),
+            ("id", Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+        ])
+        .unwrap();
+
+        let projected = project_to_features(batch.clone(), &["id", "name"]).unwrap();
+
+        assert_eq!(projected.schema().field(0).name(), "id");
+        assert_eq!(projected.schema().field(1).name(), "name");
+        assert!(project_to_features(batch.clone(), &["id", "name", "missing"]).is_err());
+        assert!(project_to_features(batch, &["id"]).is_err());
+    }
+
+    #[test]
+    fn test_serialize_row_rejects_uint64_overflow() {
+        let column = Arc::new(UInt64Array::from(vec![u64::MAX])) as ArrayRef;
+
+        assert!(serialize_row(&[column], 0, &mut vec![]).is_err());
+    }
+
+    #[test]
+    fn test_cast_to_feature_types() {
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+            ("value", Arc::new(Float64Array::from(vec![0.5])) as ArrayRef),
+            (
+                "ratio",
+                Arc::new(Float32Array::from(vec![0.25])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let casted = cast_to_feature_types(batch.clone(), &["bigint", "float", "double"]).unwrap();
+
+        assert_eq!(casted.schema().field(1).data_type(), &DataType::Float32);
+        assert_eq!(casted.schema().field(2).data_type(), &DataType::Float64);
+        let mut row = vec![];
+        serialize_row(casted.columns(), 0, &mut row).unwrap();
+        // id, then a 4 bytes float and an 8 bytes double
+        let mut expected = vec![2, 2, 2];
+        expected.extend_from_slice(&0.5f32.to_le_bytes());
+        expected.push(2);
+        expected.extend_from_slice(&0.25f64.to_le_bytes());
+        assert_eq!(row, expected);
+    }
+
+    #[test]
+    fn test_cast_to_feature_types_rejects_incompatible_columns() {
+        let batch = RecordBatch::try_from_iter(vec![(
+            "id",
+            Arc::new(StringArray::from(vec!["a"])) as ArrayRef,
+        )])
+        .unwrap();
+        let overflow = RecordBatch::try_from_iter(vec![(
+            "id",
+            Arc::new(Int64Array::from(vec![i64::MAX])) as ArrayRef,
+        )])
+        .unwrap();
+
+        assert!(matches!(
+            cast_to_feature_types(batch, &["bigint"]),
+            Err(ArrowError::SchemaError(_))
+        ));
+        assert!(matches!(
+            cast_to_feature_types(overflow, &["int"]),
+            Err(ArrowError::SchemaError(_))
+        ));
+    }
+}
@@ -1,5 +1,7 @@
-use color_eyre::Result;
-use hopsworks_core::get_threaded_runtime_num_worker_threads;
+use crate::error::{HopsworksKafkaError, Result};
+use crate::report::{send_with_retry, DeliveryRetryPolicy, InsertReport};
+use hopsworks_core::runtime::get_threaded_runtime_num_worker_threads;
+use hopsworks_core::HopsworksError;
 use polars::lazy::dsl::Expr;
 use polars::prelude::*;
 use polars_arrow::io::avro::avro_schema::schema::Record;
@@ -9,26 +11,60 @@ use std::sync::Arc;
 use std::time::Duration;
 use std::vec;
 
+/// Produce the rows of `df` on `topic_name`, keyed by the concatenation of their primary key values.
+/// Failed deliveries are retried following `retry_policy` and reported in the returned [`InsertReport`].
+/// With `multi_producer`, each chunk of `df` produced concurrently gets its own producer.
 #[tracing::instrument(skip(df, producer_config))]
 pub async fn produce_df(
     headers: rdkafka::message::OwnedHeaders,
     topic_name: Arc<String>,
     primary_keys: Vec<&str>,
     producer_config: ClientConfig,
+    multi_producer: bool,
+    retry_policy: &DeliveryRetryPolicy,
     df: &mut polars::prelude::DataFrame,
-) -> Result<()> {
+) -> Result<InsertReport> {
+    let producer_0: FutureProducer = producer_config.create()?;
+    produce_df_with_producers(
+        headers,
+        topic_name,
+        primary_keys,
+        |idx| {
+            if multi_producer || idx != 0 {
+                Ok(producer_config.create()?)
+            } else {
+                Ok(producer_0.clone())
+            }
+        },
+        retry_policy,
+        df,
+    )
+    .await
+}
+
+/// Same as [`produce_df`], `producer_for_chunk` provides the producer of each chunk of `df`
+/// produced concurrently, e.g. to reuse a long-lived producer across calls.
+#[tracing::instrument(skip(df, producer_for_chunk))]
+pub(crate) async fn produce_df_with_producers(
+    headers: rdkafka::message::OwnedHeaders,
+    topic_name: Arc<String>,
+    primary_keys: Vec<&str>,
+    producer_for_chunk: impl Fn(usize) -> Result<FutureProducer>,
+    retry_policy: &DeliveryRetryPolicy,
+    df: &mut polars::prelude::DataFrame,
+) -> Result<InsertReport> {
     tracing::debug!("Rechunks to a single chunk");
     df.as_single_chunk_par();
+    let df = encode_complex_features(&cast_to_avro_types(df)?)?;
 
     let the_start_time = std::time::Instant::now();
-    let mut join_set_workers: tokio::task::JoinSet<Result<usize>> = tokio::task::JoinSet::new();
+    let mut join_set_workers: tokio::task::JoinSet<Result<(usize, InsertReport)>> =
+        tokio::task::JoinSet::new();
     let schema = df.schema().to_arrow(false);
     let record = Arc::new(polars_arrow::io::avro::write::to_record(
         &schema,
         "".to_string(),
     )?);
-    let multi_producer =
-        std::env::var("HOPSWORKS_KAFKA_MULTI_PRODUCER").unwrap_or("false".to_string()) == "true";
 
     let pk_series_expr = pk_series_lazy_expr(schema, primary_keys)?;
     let dfs = df
@@ -38,42 +74,192 @@ pub async fn produce_df(
         .collect()?
         .split_chunks_by_n(get_threaded_runtime_num_worker_threads(), true);
 
-    let producer_0: FutureProducer = producer_config.clone().create()?;
     for (idx, frame) in dfs.iter().enumerate() {
-        let producer = if multi_producer || idx != 0 {
-            producer_config.clone().create()?
-        } else {
-            producer_0.clone()
-        };
+        let producer = producer_for_chunk(idx)?;
         let topic_name = topic_name.clone();
         let headers = headers.clone();
         let record = record.clone();
+        let retry_policy = retry_policy.clone();
 
         let (tx, rx) = tokio::sync::oneshot::channel::<DataFrame>();
         tx.send(frame.clone())
-            .map_err(|e| color_eyre::Report::msg(e.to_string()))?;
+            .map_err(|_| HopsworksKafkaError::WorkerChannel(format!("chunk {idx} was not sent")))?;
 
         join_set_workers
             .build_task()
             .name(format!("hopsworks_insert_worker_{idx}").as_str())
             .spawn(async move {
-                serialize_and_produce_chunk(idx, record, producer, rx, topic_name, headers).await
+                serialize_and_produce_chunk(
+                    idx,
+                    record,
+                    producer,
+                    rx,
+                    topic_name,
+                    headers,
+                    retry_policy,
+                )
+                .await
             })?;
     }
+    let mut report = InsertReport::default();
     while let Some(res) = join_set_workers.join_next().await {
-        let idx = res??;
+        let (idx, chunk_report) = res??;
         tracing::debug!(
             "Closing hopsworks_insert_worker_{} in {:?}",
             idx,
             the_start_time.elapsed()
         );
+        report.merge(chunk_report);
     }
+    report.elapsed = the_start_time.elapsed();
+
+    Ok(report)
+}
+
+/// Produce a tombstone, i.e. a message without payload, keyed by the primary key of each row of `df`.
+#[tracing::instrument(skip(df, producer_config))]
+pub async fn produce_tombstones(
+    headers: rdkafka::message::OwnedHeaders,
+    topic_name: &str,
+    primary_keys: Vec<&str>,
+    producer_config: ClientConfig,
+    df: &DataFrame,
+) -> Result<()> {
+    let start_time = std::time::Instant::now();
+    let pk_series_expr = pk_series_lazy_expr(df.schema().to_arrow(false), primary_keys)?;
+    let keys = df.clone().lazy().select([pk_series_expr]).collect()?;
+    let null_keys = keys.column("hopsworks_pk")?.null_count();
+    if null_keys > 0 {
+        return Err(HopsworksError::Validation(format!(
+            "{null_keys} of {} rows to delete have a null primary key.",
+            keys.height()
+        ))
+        .into());
+    }
+    let producer: FutureProducer = producer_config.create()?;
+
+    let mut produced_handles = tokio::task::JoinSet::new();
+    for composite_key in keys.column("hopsworks_pk")?.str()?.into_iter().flatten() {
+        let producer = producer.clone();
+        let topic_name = topic_name.to_string();
+        let composite_key = composite_key.to_string();
+        let headers = headers.clone();
 
+        produced_handles.spawn(async move {
+            let produce_future = producer.send(
+                FutureRecord::<String, [u8]>::to(topic_name.as_str())
+                    .key(&composite_key)
+                    .headers(headers),
+                Duration::from_secs(120),
+            );
+
+            match produce_future.await {
+                Ok(_delivery) => Ok(()),
+                Err((e, _)) => Err(HopsworksKafkaError::from(e)),
+            }
+        });
+    }
+    while let Some(res) = produced_handles.join_next().await {
+        res??;
+    }
+    producer.flush(Duration::from_secs(120))?;
+    tracing::debug!(
+        "Produced {} tombstones in {:?}",
+        keys.height(),
+        start_time.elapsed()
+    );
     Ok(())
 }
 
 use std::ops::Add;
 
+/// Cast the columns of `df` whose type the Avro writer cannot serialize to the type of the feature
+/// they are inserted in, e.g. `UInt8` to `Int32` for an `int` feature or `Time` to its nanoseconds
+/// since midnight for a `bigint` feature. Fails if a value does not fit in the feature type.
+fn cast_to_avro_types(df: &DataFrame) -> Result<DataFrame> {
+    let mut casted_df = df.clone();
+    for series in df.get_columns() {
+        if let Some(dtype) = avro_data_type(series.dtype()) {
+            casted_df.replace(series.name(), series.strict_cast(&dtype)?)?;
+        }
+    }
+    Ok(casted_df)
+}
+
+// Avro serializable type of the values of `dtype`, None if it is already serializable
+fn avro_data_type(dtype: &DataType) -> Option<DataType> {
+    match dtype {
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 | DataType::UInt16 => {
+            Some(DataType::Int32)
+        }
+        DataType::UInt32 | DataType::UInt64 | DataType::Time | DataType::Duration(_) => {
+            Some(DataType::Int64)
+        }
+        // Time zone aware timestamps are stored in UTC
+        DataType::Datetime(TimeUnit::Nanoseconds, _) | DataType::Datetime(_, Some(_)) => {
+            Some(DataType::Datetime(TimeUnit::Microseconds, None))
+        }
+        DataType::Categorical(_, _) | DataType::Enum(_, _) => Some(DataType::String),
+        DataType::Array(inner, _) | DataType::List(inner) => Some(DataType::List(Box::new(
+            avro_data_type(inner).unwrap_or_else(|| inner.as_ref().clone()),
+        )))
+        .filter(|list| list != dtype),
+        DataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| match avro_data_type(field.data_type()) {
+                    Some(dtype) => Field::new(field.name(), dtype),
+                    None => field.clone(),
+                })
+                .collect::<Vec<_>>();
+            Some(DataType::Struct(fields)).filter(|strct| strct != dtype)
+        }
+        _ => None,
+    }
+}
+
+/// Replace the complex columns of `df`, i.e. lists, arrays and structs, by binary columns holding
+/// the Avro encoding of each value with the schema of the column in a union with null.
+/// Hopsworks stores complex features encoded on their own, the record schema only sees bytes.
+fn encode_complex_features(df: &DataFrame) -> Result<DataFrame> {
+    let mut encoded_df = df.clone();
+    for series in df.get_columns() {
+        let series = match series.dtype() {
+            DataType::Array(inner, _) => series.cast(&DataType::List(inner.clone()))?,
+            DataType::List(_) | DataType::Struct(_) => series.clone(),
+            _ => continue,
+        };
+        let field = series.field().to_arrow(false);
+        if !polars_arrow::io::avro::write::can_serialize(field.data_type()) {
+            return Err(PolarsError::ComputeError(
+                format!(
+                    "Complex feature {} has type {} which cannot be encoded to Avro",
+                    series.name(),
+                    series.dtype()
+                )
+                .into(),
+            )
+            .into());
+        }
+        let record = polars_arrow::io::avro::write::to_record(
+            &ArrowSchema::from(vec![field]),
+            "".to_string(),
+        )?;
+        let array = series.rechunk().to_arrow(0, false);
+        let mut serializer =
+            polars_arrow::io::avro::write::new_serializer(array.as_ref(), &record.fields[0].schema);
+        let encoded: BinaryChunked = (0..array.len())
+            .map(|idx| {
+                // Advance the serializer on null values as well to stay aligned with the rows
+                let value = serializer.next().map(|bytes| bytes.to_vec());
+                value.filter(|_| array.is_valid(idx))
+            })
+            .collect();
+        encoded_df.replace(series.name(), encoded.into_series())?;
+    }
+    Ok(encoded_df)
+}
+
 #[tracing::instrument]
 fn pk_series_lazy_expr(schema: ArrowSchema, primary_keys: Vec<&str>) -> Result<Expr> {
     let mut polars_expr: Vec<Expr> = schema
@@ -108,17 +294,21 @@ async fn serialize_and_produce_chunk(
     rx: tokio::sync::oneshot::Receiver<DataFrame>,
     topic_name: Arc<String>,
     headers: rdkafka::message::OwnedHeaders,
-) -> Result<usize> {
+    retry_policy: DeliveryRetryPolicy,
+) -> Result<(usize, InsertReport)> {
     tracing::debug!("Processing chunk: {}", idx);
-    let frame: DataFrame = rx.await?;
+    let frame: DataFrame = rx
+        .await
+        .map_err(|e| HopsworksKafkaError::WorkerChannel(e.to_string()))?;
     let mut produced_handles = tokio::task::JoinSet::new();
     let start_time = std::time::Instant::now();
+    let retry_policy = Arc::new(retry_policy);
     let chunk = frame
         .select(
             frame
                 .get_column_names()
                 .into_iter()
-                .filter(|name| name.eq(&"hopsworks_pk")),
+                .filter(|name| name.ne(&"hopsworks_pk")),
         )
         .unwrap()
         .iter_chunks(false, true)
@@ -145,20 +335,18 @@ async fn serialize_and_produce_chunk(
         let producer = producer.clone();
         let topic_name = topic_name.clone();
         let headers = headers.clone();
+        let retry_policy = retry_policy.clone();
 
         produced_handles.spawn(async move {
-            let produce_future = producer.send(
-                FutureRecord::to(topic_name.as_str())
-                    .payload(&data)
-                    .key(&composite_key)
-                    .headers(headers),
-                Duration::from_secs(120),
-            );
-
-            match produce_future.await {
-                Ok(_delivery) => Ok(()),
-                Err((e, _)) => Err(color_eyre::Report::msg(e.to_string())),
-            }
+            send_with_retry(
+                &producer,
+                topic_name.as_str(),
+                composite_key,
+                &data,
+                headers,
+                &retry_policy,
+            )
+            .await
         });
     }
     tracing::debug!(
@@ -167,11 +355,83 @@ async fn serialize_and_produce_chunk(
         chunk.len(),
         start_time.elapsed()
     );
+    let mut report = InsertReport::default();
     while let Some(res) = produced_handles.join_next().await {
-        res??;
+        report.record(res?);
     }
     tracing::debug!("Flushing producer after {:?}", start_time.elapsed());
     producer.flush(Duration::from_secs(120))?;
+    report.elapsed = start_time.elapsed();
     tracing::debug!("Produced chunk {} in {:?} ", idx, start_time.elapsed());
-    Ok(idx)
+    Ok((idx, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_produce_tombstones_rejects_null_primary_keys() {
+        let df = DataFrame::new(vec![Series::new("id", [Some(1i64), None, None])]).unwrap();
+
+        let result = produce_tombstones(
+            rdkafka::message::OwnedHeaders::new(),
+            "topic",
+            vec!["id"],
+            ClientConfig::new(),
+            &df,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(HopsworksKafkaError::Hopsworks(HopsworksError::Validation(message)))
+                if message.starts_with("2 of 3 rows")
+        ));
+    }
+
+    #[test]
+    fn test_encode_complex_features() {
+        let values = Series::new(
+            "values",
+            [Some(Series::new("", [1i64, 2i64])), None::<Series>],
+        );
+        let df = DataFrame::new(vec![Series::new("id", [1i64, 2i64]), values]).unwrap();
+
+        let encoded = encode_complex_features(&df).unwrap();
+
+        assert_eq!(encoded.column("id").unwrap(), df.column("id").unwrap());
+        let values = encoded.column("values").unwrap().binary().unwrap();
+        // Union index of the array, block of 2 nullable longs, end of the array
+        assert_eq!(values.get(0), Some([2u8, 4, 2, 2, 2, 4, 0].as_slice()));
+        assert_eq!(values.get(1), None);
+    }
+
+    #[test]
+    fn test_cast_to_avro_types() {
+        let time = Series::new("time", [1_000i64])
+            .cast(&DataType::Time)
+            .unwrap();
+        let nested = Series::new("nested", [Series::new("", [1u8, 2u8])]);
+        let df = DataFrame::new(vec![Series::new("id", [7u64]), time, nested]).unwrap();
+
+        let casted = cast_to_avro_types(&df).unwrap();
+
+        assert_eq!(casted.column("id").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(
+            casted.column("time").unwrap().i64().unwrap().get(0),
+            Some(1_000)
+        );
+        assert_eq!(
+            casted.column("nested").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::Int32))
+        );
+    }
+
+    #[test]
+    fn test_cast_to_avro_types_rejects_overflow() {
+        let df = df!("id" => [u64::MAX]).unwrap();
+
+        assert!(cast_to_avro_types(&df).is_err());
+    }
 }
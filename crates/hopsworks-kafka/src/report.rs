@@ -0,0 +1,278 @@
+//! Delivery accounting of the rows produced on the online topic of a feature group.
+use hopsworks_core::RetryPolicy;
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::OwnedHeaders;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Outcome of producing the rows of an insert, returned alongside the materialization job execution.
+///
+/// Rows whose delivery failed after exhausting the [`DeliveryRetryPolicy`] are listed in `failed`,
+/// they are neither in the online feature store nor materialized in the offline feature store.
+#[derive(Debug, Clone, Default)]
+pub struct InsertReport {
+    /// Number of rows serialized and produced, delivered or not.
+    pub rows: usize,
+    /// Size of the produced payloads.
+    pub bytes: usize,
+    pub delivered: usize,
+    pub failed: Vec<FailedDelivery>,
+    /// Number of deliveries attempted again after a retriable failure.
+    pub retries: usize,
+    /// Offsets of the delivered messages, by partition of the topic.
+    pub partition_offsets: BTreeMap<i32, PartitionOffsets>,
+    pub elapsed: Duration,
+}
+
+/// Row which could not be delivered.
+#[derive(Debug, Clone)]
+pub struct FailedDelivery {
+    /// Concatenation of the primary key values of the row, the key of the message.
+    pub key: String,
+    pub error: KafkaError,
+    pub attempts: u32,
+}
+
+/// Range of the offsets of the messages delivered to a partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartitionOffsets {
+    pub first: i64,
+    pub last: i64,
+    pub delivered: usize,
+}
+
+impl InsertReport {
+    /// Whether every produced row was delivered.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && self.delivered == self.rows
+    }
+
+    /// Add the counts of `other` to this report, e.g. the report of another chunk of the same insert.
+    pub fn merge(&mut self, other: InsertReport) {
+        self.rows += other.rows;
+        self.bytes += other.bytes;
+        self.delivered += other.delivered;
+        self.failed.extend(other.failed);
+        self.retries += other.retries;
+        for (partition, offsets) in other.partition_offsets {
+            self.record_offsets(partition, offsets);
+        }
+        self.elapsed = self.elapsed.max(other.elapsed);
+    }
+
+    pub(crate) fn record(&mut self, delivery: Delivery) {
+        self.rows += 1;
+        self.bytes += delivery.bytes;
+        self.retries += delivery.attempts.saturating_sub(1) as usize;
+        match delivery.result {
+            Ok((partition, offset)) => {
+                self.delivered += 1;
+                self.record_offsets(
+                    partition,
+                    PartitionOffsets {
+                        first: offset,
+                        last: offset,
+                        delivered: 1,
+                    },
+                );
+            }
+            Err(error) => self.failed.push(FailedDelivery {
+                key: delivery.key,
+                error,
+                attempts: delivery.attempts,
+            }),
+        }
+    }
+
+    fn record_offsets(&mut self, partition: i32, offsets: PartitionOffsets) {
+        self.partition_offsets
+            .entry(partition)
+            .and_modify(|current| {
+                current.first = current.first.min(offsets.first);
+                current.last = current.last.max(offsets.last);
+                current.delivered += offsets.delivered;
+            })
+            .or_insert(offsets);
+    }
+}
+
+/// Retry of the messages whose delivery failed with a retriable error, e.g. a full producer queue,
+/// a timeout or a partition leader election. The attempts and the delay between them follow a
+/// [`RetryPolicy`], by default 3 attempts with a backoff growing from 100ms up to 5s.
+///
+/// Retried messages may be delivered out of order with respect to the other rows of the insert.
+#[derive(Debug, Clone)]
+pub struct DeliveryRetryPolicy {
+    retry_policy: RetryPolicy,
+}
+
+impl Default for DeliveryRetryPolicy {
+    fn default() -> Self {
+        DeliveryRetryPolicy {
+            retry_policy: RetryPolicy::new()
+                .with_max_attempts(3)
+                .with_backoff(Duration::from_millis(100), Duration::from_secs(5)),
+        }
+    }
+}
+
+impl From<RetryPolicy> for DeliveryRetryPolicy {
+    fn from(retry_policy: RetryPolicy) -> Self {
+        DeliveryRetryPolicy { retry_policy }
+    }
+}
+
+impl DeliveryRetryPolicy {
+    pub fn new() -> Self {
+        DeliveryRetryPolicy::default()
+    }
+
+    /// Policy producing every message exactly once.
+    pub fn disabled() -> Self {
+        RetryPolicy::disabled().into()
+    }
+
+    /// Attempts and backoff of the deliveries, the settings specific to HTTP requests are ignored.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.retry_policy.max_attempts()
+    }
+
+    fn is_retriable(&self, error: &KafkaError) -> bool {
+        matches!(
+            error.rdkafka_error_code(),
+            Some(
+                RDKafkaErrorCode::QueueFull
+                    | RDKafkaErrorCode::MessageTimedOut
+                    | RDKafkaErrorCode::RequestTimedOut
+                    | RDKafkaErrorCode::BrokerTransportFailure
+                    | RDKafkaErrorCode::AllBrokersDown
+                    | RDKafkaErrorCode::NetworkException
+                    | RDKafkaErrorCode::LeaderNotAvailable
+                    | RDKafkaErrorCode::NotLeaderForPartition
+                    | RDKafkaErrorCode::NotEnoughReplicas
+            )
+        )
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_policy.attempt_backoff(attempt)
+    }
+}
+
+/// Delivery of a single message, recorded in an [`InsertReport`].
+pub(crate) struct Delivery {
+    pub(crate) key: String,
+    pub(crate) bytes: usize,
+    pub(crate) attempts: u32,
+    pub(crate) result: std::result::Result<(i32, i64), KafkaError>,
+}
+
+/// Produce `payload` keyed by `key`, producing it again while it fails with an error the
+/// `retry_policy` considers retriable.
+pub(crate) async fn send_with_retry(
+    producer: &FutureProducer,
+    topic_name: &str,
+    key: String,
+    payload: &[u8],
+    headers: OwnedHeaders,
+    retry_policy: &DeliveryRetryPolicy,
+) -> Delivery {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let record = FutureRecord::to(topic_name)
+            .payload(payload)
+            .key(&key)
+            .headers(headers.clone());
+        let result = producer
+            .send(record, Duration::from_secs(120))
+            .await
+            .map_err(|(e, _)| e);
+        match result {
+            Err(e) if attempts < retry_policy.max_attempts() && retry_policy.is_retriable(&e) => {
+                tracing::debug!("Retrying delivery of message {key} after error: {e}");
+                tokio::time::sleep(retry_policy.backoff(attempts)).await;
+            }
+            result => {
+                return Delivery {
+                    bytes: payload.len(),
+                    key,
+                    attempts,
+                    result,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_report_accounting() {
+        let mut report = InsertReport::default();
+        report.record(Delivery {
+            key: "1".to_string(),
+            bytes: 10,
+            attempts: 1,
+            result: Ok((0, 42)),
+        });
+        let mut other = InsertReport::default();
+        other.record(Delivery {
+            key: "2".to_string(),
+            bytes: 12,
+            attempts: 2,
+            result: Ok((0, 40)),
+        });
+        other.record(Delivery {
+            key: "3".to_string(),
+            bytes: 8,
+            attempts: 3,
+            result: Err(KafkaError::MessageProduction(
+                RDKafkaErrorCode::MessageTimedOut,
+            )),
+        });
+
+        report.merge(other);
+
+        assert_eq!((report.rows, report.bytes, report.delivered), (3, 30, 2));
+        assert_eq!(report.retries, 3);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].key, "3");
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.partition_offsets[&0],
+            PartitionOffsets {
+                first: 40,
+                last: 42,
+                delivered: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_delivery_retry_policy() {
+        let retry_policy = DeliveryRetryPolicy::new().with_retry_policy(
+            RetryPolicy::new()
+                .with_jitter(false)
+                .with_backoff(Duration::from_millis(100), Duration::from_millis(300)),
+        );
+        assert!(
+            retry_policy.is_retriable(&KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull))
+        );
+        assert!(!retry_policy.is_retriable(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::MessageSizeTooLarge
+        )));
+        assert_eq!(retry_policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(retry_policy.backoff(4), Duration::from_millis(300));
+        assert_eq!(DeliveryRetryPolicy::disabled().max_attempts(), 1);
+        assert_eq!(DeliveryRetryPolicy::default().max_attempts(), 3);
+    }
+}
@@ -0,0 +1,33 @@
+//! Registered feature group whose online topic is written or read through Kafka.
+
+/// Identifiers of a registered feature group and of its online topic, with the directory
+/// of the client certificates used to connect to the Kafka brokers of the project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureGroupTarget {
+    pub feature_store_id: i32,
+    pub feature_group_id: i32,
+    pub feature_group_name: String,
+    pub feature_group_version: i32,
+    pub online_topic_name: String,
+    pub primary_keys: Vec<String>,
+    pub cert_dir: String,
+}
+
+impl FeatureGroupTarget {
+    pub fn primary_keys(&self) -> Vec<&str> {
+        self.primary_keys.iter().map(String::as_str).collect()
+    }
+
+    /// Subject of the Avro schema of the messages, `<name>_<version>`.
+    pub(crate) fn subject_name(&self) -> String {
+        format!("{}_{}", self.feature_group_name, self.feature_group_version)
+    }
+
+    /// Job materializing the online topic in the offline feature store.
+    pub(crate) fn materialization_job_name(&self) -> String {
+        format!(
+            "{}_{}_offline_fg_materialization",
+            self.feature_group_name, self.feature_group_version
+        )
+    }
+}
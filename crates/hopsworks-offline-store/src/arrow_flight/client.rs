@@ -1,14 +1,13 @@
+use crate::error::{OfflineStoreError, Result};
 use arrow_flight::decode::FlightRecordBatchStream;
 use arrow_flight::{Action, FlightClient, FlightDescriptor};
 use bytes::Bytes;
-use color_eyre::eyre::Ok;
-use color_eyre::Result;
 use futures::stream::{StreamExt, TryStreamExt};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use crate::cluster_api::payloads::RegisterArrowFlightClientCertificatePayload;
 use crate::cluster_api::payloads::{QueryArrowFlightPayload, TrainingDatasetArrowFlightPayload};
-use hopsworks_core::{get_hopsworks_client, util};
+use hopsworks_core::{get_hopsworks_client, util, TlsConfig};
 use std::time::Duration;
 use std::vec;
 use tonic::transport::{channel::ClientTlsConfig, Certificate, Endpoint, Identity};
@@ -22,15 +21,26 @@ use hopsworks_core::feature_store::{
 pub struct HopsworksArrowFlightClientBuilder {}
 
 impl HopsworksArrowFlightClientBuilder {
-    async fn build_client_tls_config(&self, cert_dir: &str) -> Result<ClientTlsConfig> {
+    async fn build_client_tls_config(
+        &self,
+        cert_dir: &str,
+        tls: &TlsConfig,
+    ) -> Result<ClientTlsConfig> {
         debug!("my_cert_dir: {}/{}", cert_dir, "client_cert.pem");
         let client_cert_content =
             tokio::fs::read(format!("{}/{}", cert_dir, "client_cert.pem")).await?;
         let client_key_content =
             tokio::fs::read(format!("{}/{}", cert_dir, "client_key.pem")).await?;
-        let ca_chain_content = Certificate::from_pem(
-            tokio::fs::read(format!("{}/{}", cert_dir, "ca_chain.pem")).await?,
-        );
+        let mut ca_chain_pem = tokio::fs::read(format!("{}/{}", cert_dir, "ca_chain.pem")).await?;
+        if let Some(ca_cert_pem) = tls.read_ca_cert_pem()? {
+            ca_chain_pem.push(b'\n');
+            ca_chain_pem.extend(ca_cert_pem);
+        }
+        if tls.accept_invalid_certs() {
+            // tonic does not support skipping verification, the project CA chain is still used.
+            warn!("Accepting invalid certificates is not supported by the Arrow Flight client.");
+        }
+        let ca_chain_content = Certificate::from_pem(ca_chain_pem);
 
         let identity = Identity::from_pem(client_cert_content, client_key_content);
         let tls_config = ClientTlsConfig::new()
@@ -44,7 +54,7 @@ impl HopsworksArrowFlightClientBuilder {
     async fn check_flyingduck_enabled(&self) -> Result<()> {
         let is_enabled = variables::get_flyingduck_enabled().await?;
         if !is_enabled {
-            return Err(color_eyre::Report::msg("Flying Duck is not enabled"));
+            return Err(OfflineStoreError::FlyingDuckDisabled);
         }
         Ok(())
     }
@@ -60,13 +70,16 @@ impl HopsworksArrowFlightClientBuilder {
     pub async fn build(self) -> Result<HopsworksArrowFlightClient> {
         self.check_flyingduck_enabled().await?;
 
-        let hopsworks_client = get_hopsworks_client().await;
+        let hopsworks_client = get_hopsworks_client().await?;
         let arrow_flight_url = self.get_arrow_flight_url().await?;
 
         let endpoint = Endpoint::from_shared(arrow_flight_url)?
             .tls_config(
-                self.build_client_tls_config(hopsworks_client.get_cert_dir().lock().await.as_str())
-                    .await?,
+                self.build_client_tls_config(
+                    hopsworks_client.get_cert_dir().lock().await.as_str(),
+                    hopsworks_client.get_tls_config(),
+                )
+                .await?,
             )?
             .connect_timeout(Duration::from_secs(20))
             .timeout(Duration::from_secs(20))
@@ -77,10 +90,7 @@ impl HopsworksArrowFlightClientBuilder {
             .keep_alive_while_idle(true);
 
         debug!("Arrow flight endpoint: {:#?}", endpoint.uri().host());
-        let channel = endpoint
-            .connect()
-            .await
-            .expect("Tonic channel failed to connect to Arrow Flight server");
+        let channel = endpoint.connect().await?;
 
         let mut hopsworks_arrow_client = HopsworksArrowFlightClient {
             client: FlightClient::new(channel),
@@ -180,13 +190,15 @@ impl HopsworksArrowFlightClient {
                     flight_descriptor_cmd = "(No flight descriptor in flight info)".to_string();
                 }
 
-                Err(color_eyre::Report::msg(format!(
+                Err(OfflineStoreError::InvalidFlightInfo(format!(
                     "No ticket found in flight {} endpoint.",
                     flight_descriptor_cmd
                 )))
             }
         } else {
-            Err(color_eyre::Report::msg("No endpoint found"))
+            Err(OfflineStoreError::InvalidFlightInfo(
+                "No endpoint found".to_string(),
+            ))
         }
     }
 
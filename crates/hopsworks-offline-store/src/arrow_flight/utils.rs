@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{OfflineStoreError, Result};
 use std::collections::HashMap;
 use tracing::{debug, info};
 
@@ -16,7 +16,7 @@ use hopsworks_core::feature_store::{
 pub fn create_flight_query(
     query: Query,
     query_str: String,
-    on_demand_fg_aliases: Vec<String>,
+    on_demand_fg_aliases: HashMap<i32, String>,
 ) -> Result<QueryArrowFlightPayload> {
     info!(
         "Creating arrow flight query payload for query with left_feature_group {}",
@@ -35,7 +35,7 @@ pub fn create_flight_query(
                 .collect(),
         );
         let fg_connector =
-            serialize_feature_group_connector(feature_group, &query, on_demand_fg_aliases.clone())?;
+            serialize_feature_group_connector(feature_group, &query, &on_demand_fg_aliases)?;
         connectors.insert(fg_name, fg_connector);
     }
     let filters = match query.filters() {
@@ -50,12 +50,56 @@ pub fn create_flight_query(
     ))
 }
 
+/// Cached feature groups are read from their Hudi table, external feature groups from their
+/// storage connector, with the filters of the (sub-)query they are the left feature group of.
 pub(super) fn serialize_feature_group_connector(
-    _feature_group: &FeatureGroup,
-    _query: &Query,
-    _on_demand_fg_aliases: Vec<String>,
+    feature_group: &FeatureGroup,
+    query: &Query,
+    on_demand_fg_aliases: &HashMap<i32, String>,
 ) -> Result<FeatureGroupConnectorArrowFlightPayload> {
-    Ok(FeatureGroupConnectorArrowFlightPayload::new_hudi_connector())
+    if !feature_group.is_external() {
+        return Ok(FeatureGroupConnectorArrowFlightPayload::new_hudi_connector());
+    }
+    let storage_connector = feature_group.storage_connector().ok_or_else(|| {
+        OfflineStoreError::InvalidQuery(format!(
+            "External feature group {} has no storage connector",
+            feature_group.name()
+        ))
+    })?;
+    let alias = feature_group
+        .id()
+        .and_then(|id| on_demand_fg_aliases.get(&id))
+        .cloned();
+
+    let mut options = storage_connector.connector_options().clone();
+    if let Some(path) = feature_group.path() {
+        options.insert("path".to_string(), path.to_string());
+    }
+    if let Some(data_format) = feature_group.data_format() {
+        options.insert("format".to_string(), data_format.to_string());
+    }
+
+    let sub_query = std::iter::once(query)
+        .chain(query.joins().into_iter().flatten().map(|join| join.query()))
+        .find(|sub_query| sub_query.left_feature_group().id() == feature_group.id());
+    let filters = match sub_query.and_then(|sub_query| Some((sub_query, sub_query.filters()?))) {
+        Some((sub_query, filters)) => {
+            serialize_filter_expression(filters.clone(), sub_query, true)?
+        }
+        None => None,
+    };
+
+    Ok(
+        FeatureGroupConnectorArrowFlightPayload::new_external_connector(
+            storage_connector.storage_connector_type(),
+            options,
+            feature_group
+                .query()
+                .map(|query| query.trim_end().trim_end_matches(';').to_string()),
+            alias,
+            filters,
+        ),
+    )
 }
 
 pub(super) fn serialize_feature_group_name(feature_group: &FeatureGroup) -> String {
@@ -82,7 +126,7 @@ pub(super) fn serialize_feature_name(
             debug!("Serializing full feature name: {}", name);
             Ok(name)
         } else {
-            Err(color_eyre::Report::msg(format!(
+            Err(OfflineStoreError::InvalidQuery(format!(
                 "Feature {} not found in query object",
                 feature.name()
             )))
@@ -23,6 +23,22 @@ impl FeatureGroupConnectorArrowFlightPayload {
             filters: None,
         }
     }
+
+    pub fn new_external_connector(
+        connector_type: &str,
+        options: HashMap<String, String>,
+        query: Option<String>,
+        alias: Option<String>,
+        filters: Option<Vec<QueryFilterOrLogicArrowFlightPayload>>,
+    ) -> Self {
+        Self {
+            connector_type: connector_type.to_string(),
+            options: Some(options),
+            query,
+            alias,
+            filters,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
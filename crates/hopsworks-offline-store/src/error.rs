@@ -0,0 +1,48 @@
+use hopsworks_core::HopsworksError;
+
+pub type Result<T, E = OfflineStoreError> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OfflineStoreError {
+    #[error(transparent)]
+    Hopsworks(#[from] HopsworksError),
+    #[error(transparent)]
+    Flight(#[from] arrow_flight::error::FlightError),
+    #[error(transparent)]
+    Tonic(#[from] tonic::transport::Error),
+    #[error(transparent)]
+    InvalidUri(#[from] tonic::codegen::http::uri::InvalidUri),
+    #[error(transparent)]
+    Arrow(#[from] arrow::error::ArrowError),
+    #[error(transparent)]
+    Polars(#[from] polars::error::PolarsError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Flying Duck is not enabled")]
+    FlyingDuckDisabled,
+    #[error("Arrow Flight server response is invalid: {0}")]
+    InvalidFlightInfo(String),
+    #[error("Query is invalid: {0}")]
+    InvalidQuery(String),
+}
+
+impl From<OfflineStoreError> for HopsworksError {
+    fn from(err: OfflineStoreError) -> Self {
+        match err {
+            OfflineStoreError::Hopsworks(e) => e,
+            OfflineStoreError::Flight(e) => HopsworksError::transport(e),
+            OfflineStoreError::Tonic(e) => HopsworksError::transport(e),
+            OfflineStoreError::InvalidUri(e) => HopsworksError::Validation(e.to_string()),
+            OfflineStoreError::Json(e) => HopsworksError::serialization(e),
+            OfflineStoreError::Arrow(e) => HopsworksError::other(e),
+            OfflineStoreError::Polars(e) => HopsworksError::other(e),
+            OfflineStoreError::Io(e) => HopsworksError::Io(e),
+            OfflineStoreError::FlyingDuckDisabled | OfflineStoreError::InvalidFlightInfo(_) => {
+                HopsworksError::transport(err)
+            }
+            OfflineStoreError::InvalidQuery(e) => HopsworksError::Validation(e),
+        }
+    }
+}
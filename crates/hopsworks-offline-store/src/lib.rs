@@ -1,4 +1,5 @@
 pub mod arrow_flight;
 pub mod cluster_api;
+pub mod error;
 pub mod read;
 pub mod write;
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 
 use hopsworks_core::controller::feature_store::query::construct_query;
 use hopsworks_core::feature_store::query::Query;
@@ -27,11 +27,11 @@ pub async fn build_flight_query(
             )
         });
 
-    // Extract on-demand feature group aliases
+    // Extract on-demand feature group aliases, by feature group id
     let on_demand_fg_aliases = feature_store_query_dto
         .on_demand_feature_groups
         .iter()
-        .map(|fg| fg.name.clone())
+        .map(|fg_alias| (fg_alias.on_demand_feature_group.id, fg_alias.alias.clone()))
         .collect();
 
     // Use arrow flight client methods to convert query to arrow flight payload
@@ -1,5 +1,5 @@
+use crate::error::Result;
 use arrow::record_batch::RecordBatch;
-use color_eyre::Result;
 use futures::StreamExt;
 use polars::prelude::*;
 use polars_core::utils::accumulate_dataframes_vertical;
@@ -1,5 +1,5 @@
+use crate::error::Result;
 use arrow::record_batch::RecordBatch;
-use color_eyre::Result;
 use futures::StreamExt;
 
 use hopsworks_core::feature_store::query::Query;
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 use reqwest::{header::HeaderValue, Method};
 use tracing::{debug, instrument};
 use typed_builder::TypedBuilder;
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::Result;
 use tracing;
 
 use super::{
@@ -20,10 +20,53 @@ pub struct BatchFeatureVectors {
     detailed_status: Option<Vec<DetailedStatus>>,
 }
 
+impl SingleFeatureVector {
+    pub fn features(&self) -> &[serde_json::Value] {
+        &self.features
+    }
+
+    /// Name and type of the features, if requested in the read options.
+    pub fn metadata(&self) -> Option<&[MetadataFeatureVector]> {
+        self.metadata.as_deref()
+    }
+
+    pub fn features_mut(&mut self) -> &mut FeatureValues {
+        &mut self.features
+    }
+}
+
+impl BatchFeatureVectors {
+    pub fn features(&self) -> &[FeatureValues] {
+        &self.features
+    }
+
+    /// Name and type of the features of each vector, if requested in the read options.
+    pub fn metadata(&self) -> Option<&[MetadataFeatureVector]> {
+        self.metadata.as_deref()
+    }
+
+    pub fn features_mut(&mut self) -> &mut Vec<FeatureValues> {
+        &mut self.features
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct MetadataFeatureVector {
-    feature_name: String,
-    feature_type: String,
+    #[serde(alias = "feature_name")]
+    feature_name: Option<String>,
+    #[serde(alias = "feature_type")]
+    feature_type: Option<String>,
+}
+
+impl MetadataFeatureVector {
+    pub fn feature_name(&self) -> Option<&str> {
+        self.feature_name.as_deref()
+    }
+
+    pub fn feature_type(&self) -> Option<&str> {
+        self.feature_type.as_deref()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -0,0 +1,25 @@
+use hopsworks_core::HopsworksError;
+
+pub type Result<T, E = OnlineStoreRestError> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OnlineStoreRestError {
+    #[error(transparent)]
+    Hopsworks(#[from] HopsworksError),
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    #[error(
+        "Online Store Rest Client not initialized. Call init_online_store_rest_client() first."
+    )]
+    NotInitialized,
+}
+
+impl From<OnlineStoreRestError> for HopsworksError {
+    fn from(err: OnlineStoreRestError) -> Self {
+        match err {
+            OnlineStoreRestError::Hopsworks(e) => e,
+            OnlineStoreRestError::Reqwest(e) => HopsworksError::from(e),
+            OnlineStoreRestError::NotInitialized => HopsworksError::Validation(err.to_string()),
+        }
+    }
+}
@@ -1,11 +1,12 @@
 mod client;
 pub mod controller;
 pub mod entities;
+pub mod error;
 mod payload;
 pub mod rest_read_options;
 mod rondb_feature_store_api;
 
-use color_eyre::Result;
+use error::{OnlineStoreRestError, Result};
 use reqwest::header::HeaderValue;
 use std::sync::OnceLock;
 
@@ -15,9 +16,7 @@ static ONLINE_STORE_REST_CLIENT: OnceLock<client::OnlineStoreRestClient> = OnceL
 fn get_online_store_rest_client() -> Result<&'static client::OnlineStoreRestClient> {
     match ONLINE_STORE_REST_CLIENT.get() {
         Some(the_client) => Ok(the_client),
-        None => color_eyre::eyre::bail!(
-            "Online Store Rest Client not initialized. Call init_online_store_rest_client() first."
-        ),
+        None => Err(OnlineStoreRestError::NotInitialized),
     }
 }
 
@@ -16,6 +16,12 @@ pub struct FeatureVectorRestReadOptions {
 }
 
 impl FeatureVectorRestReadOptions {
+    /// Return the name of the features in the metadata of the feature vectors.
+    pub fn with_feature_name(mut self, feature_name: bool) -> Self {
+        self.feature_name = feature_name;
+        self
+    }
+
     pub(crate) fn to_metadata_options_payload(&self) -> Option<MetadataOptionsPayload> {
         if self.feature_name || self.feature_type {
             Some(MetadataOptionsPayload {
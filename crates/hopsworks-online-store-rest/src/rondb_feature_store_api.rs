@@ -1,4 +1,5 @@
-use color_eyre::Result;
+use crate::error::Result;
+use hopsworks_core::HopsworksError;
 use reqwest::Method;
 
 use super::{
@@ -26,7 +27,11 @@ pub async fn get_single_feature_vector(payload: SingleEntryPayload) -> Result<Si
 
     match resp.status() {
         reqwest::StatusCode::ACCEPTED => Ok(resp.json::<SingleFeatureVector>().await?),
-        _ => todo!(),
+        _ => Err(
+            HopsworksError::from_response("get_single_feature_vector", resp)
+                .await
+                .into(),
+        ),
     }
 }
 
@@ -42,6 +47,10 @@ pub async fn get_batch_feature_vectors(
 
     match resp.status() {
         reqwest::StatusCode::ACCEPTED => Ok(resp.json::<BatchFeatureVectors>().await?),
-        _ => todo!(),
+        _ => Err(
+            HopsworksError::from_response("get_batch_feature_vectors", resp)
+                .await
+                .into(),
+        ),
     }
 }
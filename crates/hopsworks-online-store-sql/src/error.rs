@@ -0,0 +1,35 @@
+use hopsworks_core::HopsworksError;
+
+use crate::mysql2arrow::arrowstream::ArrowDestinationError;
+use crate::mysql2arrow::mysql::MySQLSourceError;
+use crate::mysql2arrow::mysql_arrowstream::MySQLArrowTransportError;
+
+pub type Result<T, E = OnlineStoreSqlError> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OnlineStoreSqlError {
+    #[error(transparent)]
+    Hopsworks(#[from] HopsworksError),
+    #[error(transparent)]
+    Source(#[from] MySQLSourceError),
+    #[error(transparent)]
+    Transport(#[from] MySQLArrowTransportError),
+    #[error(transparent)]
+    Destination(#[from] ArrowDestinationError),
+    #[cfg(feature = "sql2polars")]
+    #[error(transparent)]
+    Polars(#[from] polars::error::PolarsError),
+    #[error("No data returned from the query")]
+    NoData,
+}
+
+impl From<OnlineStoreSqlError> for HopsworksError {
+    fn from(err: OnlineStoreSqlError) -> Self {
+        match err {
+            OnlineStoreSqlError::Hopsworks(e) => e,
+            OnlineStoreSqlError::Source(e) => HopsworksError::transport(e),
+            OnlineStoreSqlError::NoData => HopsworksError::NotFound(err.to_string()),
+            _ => HopsworksError::other(err),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+pub mod error;
 mod mysql2arrow;
 pub mod read;
 
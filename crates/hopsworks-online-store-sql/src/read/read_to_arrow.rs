@@ -1,5 +1,5 @@
+use crate::error::Result;
 use arrow::{datatypes::Schema, record_batch::RecordBatch};
-use color_eyre::Result;
 use connectorx::prelude::Dispatcher;
 use connectorx::sql::CXQuery;
 use std::sync::Arc;
@@ -23,7 +23,7 @@ pub async fn read_query_from_online_feature_store(
         query.left_feature_group().feature_store_id(),
     )
     .await?;
-    let builder = MySQLSource::<BinaryProtocol>::new(connection_string.as_str(), 2).unwrap();
+    let builder = MySQLSource::<BinaryProtocol>::new(connection_string.as_str(), 2)?;
 
     let constructed_query = construct_query(query).await?;
     let queries = vec![CXQuery::from(&constructed_query.query_online)];
@@ -34,7 +34,7 @@ pub async fn read_query_from_online_feature_store(
         ArrowDestination,
         MySQLArrowTransport<BinaryProtocol>,
     >::new(builder, &mut destination, &queries, None);
-    dispatcher.run().unwrap();
+    dispatcher.run()?;
 
     let schema = destination.arrow_schema();
     let record_batches = destination.arrow()?;
@@ -1,5 +1,5 @@
+use crate::error::{OnlineStoreSqlError, Result};
 use arrow::record_batch::RecordBatch;
-use color_eyre::Result;
 use connectorx::prelude::Dispatcher;
 use connectorx::sql::CXQuery;
 use polars::prelude::*;
@@ -24,7 +24,7 @@ pub async fn read_polars_from_online_feature_store(
         query.left_feature_group().feature_store_id(),
     )
     .await?;
-    let builder = MySQLSource::<BinaryProtocol>::new(connection_string.as_str(), 2).unwrap();
+    let builder = MySQLSource::<BinaryProtocol>::new(connection_string.as_str(), 2)?;
 
     let constructed_query = construct_query(query).await?;
     let queries = vec![CXQuery::from(&constructed_query.query_online)];
@@ -35,7 +35,7 @@ pub async fn read_polars_from_online_feature_store(
         ArrowDestination,
         MySQLArrowTransport<BinaryProtocol>,
     >::new(builder, &mut destination, &queries, None);
-    dispatcher.run().unwrap();
+    dispatcher.run()?;
 
     let mut dfs = vec![];
     while let Ok(Some(rb)) = destination.record_batch() {
@@ -43,7 +43,7 @@ pub async fn read_polars_from_online_feature_store(
     }
 
     match dfs.is_empty() {
-        true => Err(color_eyre::eyre::eyre!("No data returned from the query")),
+        true => Err(OnlineStoreSqlError::NoData),
         false => Ok(accumulate_dataframes_vertical(dfs)?),
     }
 }
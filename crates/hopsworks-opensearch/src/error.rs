@@ -0,0 +1,32 @@
+use hopsworks_core::HopsworksError;
+
+pub type Result<T, E = HopsworksOpenSearchError> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HopsworksOpenSearchError {
+    #[error(transparent)]
+    Hopsworks(#[from] HopsworksError),
+    #[error(transparent)]
+    OpenSearch(#[from] opensearch::Error),
+    #[error(transparent)]
+    Transport(#[from] opensearch::http::transport::BuildError),
+    #[error("Invalid OpenSearch url: {0}")]
+    InvalidUrl(String),
+    #[error("OpenSearch request failed with status {status}: {message}")]
+    Search { status: u16, message: String },
+    #[error("Hopsworks OpenSearch Client not initialized. Call init_hopsworks_opensearch_client() first.")]
+    NotInitialized,
+}
+
+impl From<HopsworksOpenSearchError> for HopsworksError {
+    fn from(err: HopsworksOpenSearchError) -> Self {
+        match err {
+            HopsworksOpenSearchError::Hopsworks(e) => e,
+            HopsworksOpenSearchError::OpenSearch(e) => HopsworksError::transport(e),
+            HopsworksOpenSearchError::Transport(e) => HopsworksError::transport(e),
+            HopsworksOpenSearchError::InvalidUrl(_) => HopsworksError::Validation(err.to_string()),
+            HopsworksOpenSearchError::Search { .. } => HopsworksError::transport(err),
+            HopsworksOpenSearchError::NotInitialized => HopsworksError::Validation(err.to_string()),
+        }
+    }
+}
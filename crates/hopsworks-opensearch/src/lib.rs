@@ -1,25 +1,44 @@
-use color_eyre::Result;
 use opensearch::OpenSearch;
+use opensearch::cert::{Certificate, CertificateValidation};
 use opensearch::http::{transport::{SingleNodeConnectionPool, TransportBuilder}, Url};
 use std::sync::OnceLock;
 
+use hopsworks_core::TlsConfig;
+
+pub mod error;
 pub mod vector_db;
 
+use error::{HopsworksOpenSearchError, Result};
+
 static HOPSWORKS_OPENSEARCH_CLIENT : OnceLock<OpenSearch> = OnceLock::new();
 
 fn get_hopsworks_opensearch_client() -> Result<&'static OpenSearch> {
     match HOPSWORKS_OPENSEARCH_CLIENT.get() {
         Some(the_client) => Ok(the_client),
-        None => color_eyre::eyre::bail!("Hopsworks OpenSearch Client not initialized. Call init_hopsworks_opensearch_client() first.")
+        None => Err(HopsworksOpenSearchError::NotInitialized),
+    }
+}
+
+fn cert_validation(tls: &TlsConfig) -> Result<CertificateValidation> {
+    if tls.accept_invalid_certs() {
+        return Ok(CertificateValidation::None);
+    }
+    match tls.read_ca_cert_pem()? {
+        Some(ca_cert_pem) => Ok(CertificateValidation::Full(Certificate::from_pem(&ca_cert_pem)?)),
+        None => Ok(CertificateValidation::Default),
     }
 }
 
 #[tracing::instrument(skip(token))]
-pub fn init_hopsworks_opensearch_client(url: &str, token: &str) -> Result<()> {
-    let url = Url::parse(url)?;
+pub fn init_hopsworks_opensearch_client(url: &str, token: &str, tls: &TlsConfig) -> Result<()> {
+    let url = Url::parse(url).map_err(|e| HopsworksOpenSearchError::InvalidUrl(e.to_string()))?;
     let conn_pool = SingleNodeConnectionPool::new(url);
     let bearer_token = opensearch::auth::Credentials::Bearer(token.to_string());
-    let transport = TransportBuilder::new(conn_pool).auth(bearer_token).disable_proxy().build()?;
+    let transport = TransportBuilder::new(conn_pool)
+        .auth(bearer_token)
+        .cert_validation(cert_validation(tls)?)
+        .disable_proxy()
+        .build()?;
     HOPSWORKS_OPENSEARCH_CLIENT.get_or_init(|| OpenSearch::new(transport));
     Ok(())
 }
\ No newline at end of file
@@ -1,6 +1,4 @@
-use color_eyre::Result;
-
-use hopsworks_core::controller::feature_store::query;
+use crate::error::Result;
 use serde_json::json;
 
 #[tracing::instrument]
@@ -1,4 +1,4 @@
-use color_eyre::Result;
+use crate::error::{HopsworksOpenSearchError, Result};
 use opensearch::SearchParts;
 
 use crate::get_hopsworks_opensearch_client;
@@ -20,7 +20,10 @@ async fn find_neighbors(
             let hits = body["hits"]["hits"].as_array().unwrap();
             Ok(hits.to_owned())
         }
-        false => todo!(),
+        false => Err(HopsworksOpenSearchError::Search {
+            status: response.status_code().as_u16(),
+            message: response.text().await?,
+        }),
     }
 }
 
@@ -41,6 +44,9 @@ async fn get_feature_vectors(
             let hits = body["hits"]["hits"].as_array().unwrap();
             Ok(hits[0].to_owned())
         }
-        false => todo!(),
+        false => Err(HopsworksOpenSearchError::Search {
+            status: response.status_code().as_u16(),
+            message: response.text().await?,
+        }),
     }
 }
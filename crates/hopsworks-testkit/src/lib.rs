@@ -0,0 +1,209 @@
+//! # Hopsworks Testkit
+//!
+//! In-process stand-in for the REST API of a Hopsworks cluster, to run end-to-end tests of code built on
+//! the Hopsworks SDK without network access or credentials.
+//!
+//! The mock serves the endpoints of projects, feature stores, feature groups, feature views,
+//! storage connectors, jobs, executions and datasets. Entities are added with the builders of
+//! the [`fixtures`] module, entities created through the SDK are stored and returned by later requests.
+//! Ingestion into the online or offline feature store (Kafka, Arrow Flight) is not emulated.
+//!
+//! ```no_run
+//! use hopsworks_testkit::{
+//!     fixtures::{FeatureGroupFixture, ProjectFixture},
+//!     MockHopsworks,
+//! };
+//!
+//! # async fn run() -> hopsworks_core::Result<()> {
+//! let mock = MockHopsworks::start().await?;
+//! mock.add_project(ProjectFixture::new("demo"));
+//! mock.add_feature_group(
+//!     "demo",
+//!     FeatureGroupFixture::new("transactions", 1)
+//!         .with_primary_key("id", "bigint")
+//!         .with_event_time("ts"),
+//! );
+//!
+//! let project = mock.connect(Some("demo")).await?;
+//! let fs = project.get_feature_store().await?;
+//! let fg = fs.get_feature_group("transactions", Some(1)).await?;
+//! assert!(fg.is_some());
+//! # Ok(())
+//! # }
+//! ```
+pub mod fixtures;
+mod routes;
+mod server;
+mod state;
+
+pub use server::{MockHopsworks, RecordedRequest, DEFAULT_TESTKIT_API_KEY};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::{FeatureGroupFixture, JobFixture, ProjectFixture, StorageConnectorFixture};
+    use futures::TryStreamExt;
+    use hopsworks_core::platform::file_system::{
+        download_from_hopsworks, mkdir_in_hopsworks, move_file_or_dir_in_hopsworks,
+    };
+    use hopsworks_core::{HopsworksError, ListOptions, RuntimeConfig};
+
+    #[tokio::test]
+    async fn test_feature_store_round_trip() {
+        let mock = MockHopsworks::start().await.unwrap();
+        mock.add_project(ProjectFixture::new("test_project"));
+        mock.add_feature_group(
+            "test_project",
+            FeatureGroupFixture::new("fg", 1).with_primary_key("id", "bigint"),
+        );
+        mock.add_feature_group(
+            "test_project",
+            FeatureGroupFixture::new("fg", 2).with_primary_key("id", "bigint"),
+        );
+
+        let project = mock.connect(None).await.unwrap();
+        assert_eq!(project.name(), "test_project");
+        let fs = project.get_feature_store().await.unwrap();
+        assert_eq!(fs.feature_store_name(), "test_project_featurestore");
+
+        let latest = fs.get_feature_group("fg", None).await.unwrap().unwrap();
+        assert_eq!(latest.version(), 2);
+        assert!(fs
+            .get_feature_group("missing", None)
+            .await
+            .unwrap()
+            .is_none());
+
+        let mut new_fg = fs
+            .create_feature_group("new_fg", 1, None, vec!["id"], None, false)
+            .unwrap();
+        new_fg
+            .register_feature_group(
+                &["id".to_string(), "value".to_string()],
+                &["bigint".to_string(), "double".to_string()],
+            )
+            .await
+            .unwrap();
+        assert!(new_fg.id().is_some());
+        assert!(mock.feature_group("test_project", "new_fg", 1).is_some());
+
+        let fv = fs
+            .create_feature_view("fv", 1, latest.select_all(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(fv.name(), "fv");
+        assert!(fs.get_feature_view("fv", None).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_job_run() {
+        let mock = MockHopsworks::start().await.unwrap();
+        mock.add_project(ProjectFixture::new("test_project"));
+        mock.add_job("test_project", JobFixture::new("test_job"));
+
+        let project = mock.connect(Some("test_project")).await.unwrap();
+        let job = project.get_job("test_job").await.unwrap();
+        job.run(Some("--arg 1"), false).await.unwrap();
+
+        let executions = mock.job_executions("test_project", "test_job");
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0]["args"], "--arg 1");
+        assert_eq!(job.get_executions().await.unwrap().len(), 1);
+        assert!(matches!(
+            project.get_job("missing_job").await,
+            Err(HopsworksError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_pagination() {
+        let mock = MockHopsworks::start().await.unwrap();
+        mock.add_project(ProjectFixture::new("test_project"));
+        mock.add_job("test_project", JobFixture::new("test_job"));
+        mock.add_storage_connector(
+            "test_project",
+            StorageConnectorFixture::hopsfs("data", "/Projects/test_project/data"),
+        );
+
+        let project = mock.connect(None).await.unwrap();
+        let job = project.get_job("test_job").await.unwrap();
+        for i in 0..5 {
+            job.run(Some(&i.to_string()), false).await.unwrap();
+        }
+
+        let executions: Vec<_> = job
+            .executions_stream(ListOptions::new().with_page_size(2).with_sort_by("id:asc"))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(executions.len(), 5);
+        let page_requests = mock
+            .received_requests()
+            .into_iter()
+            .filter(|request| request.method == "GET" && request.path.ends_with("executions"))
+            .count();
+        assert_eq!(page_requests, 3);
+
+        let page = job
+            .get_executions_with_options(&ListOptions::new().with_offset(4).with_limit(2))
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+
+        let fs = project.get_feature_store().await.unwrap();
+        let connectors = fs
+            .get_storage_connectors(&ListOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(connectors[0].storage_connector_type(), "HOPSFS");
+    }
+
+    #[tokio::test]
+    async fn test_dataset_download_and_move() {
+        let mock = MockHopsworks::start().await.unwrap();
+        mock.add_project(ProjectFixture::new("test_project"));
+        mock.add_file("test_project", "Resources/input.csv", b"id,value\n1,2.0\n");
+
+        let project = mock.connect(None).await.unwrap();
+        let local_dir =
+            std::env::temp_dir().join(format!("hopsworks-testkit-download-{}", std::process::id()));
+        std::fs::create_dir_all(&local_dir).unwrap();
+        let local_path = local_dir.join("input.csv");
+
+        let client = project.hopsworks_client().unwrap();
+        client
+            .scope(async {
+                download_from_hopsworks("Resources/input.csv", local_path.to_str(), true).await?;
+                mkdir_in_hopsworks("Resources/archive").await?;
+                move_file_or_dir_in_hopsworks(
+                    "Resources/input.csv",
+                    "Resources/archive/input.csv",
+                    false,
+                )
+                .await
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&local_path).unwrap(), b"id,value\n1,2.0\n");
+        assert!(mock.file("test_project", "Resources/input.csv").is_none());
+        assert!(mock
+            .file("test_project", "Resources/archive/input.csv")
+            .is_some());
+        std::fs::remove_dir_all(local_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_api_key() {
+        let mock = MockHopsworks::start().await.unwrap();
+        mock.add_project(ProjectFixture::new("test_project"));
+
+        let result = hopsworks_core::connect(
+            Some(mock.client_builder().with_api_key("wrong_api_key")),
+            RuntimeConfig::Current,
+        )
+        .await;
+        assert!(matches!(result, Err(HopsworksError::Auth(_))));
+        assert_eq!(mock.received_requests()[0].path, "project");
+    }
+}
@@ -0,0 +1,163 @@
+//! The dataset API serves files, uploads and downloads under the same prefix,
+//! requests are dispatched on the first segment of the path.
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+use super::{not_found, project_or_not_found, rest_error, ApiResult};
+use crate::server::normalize_path;
+use crate::state::{lock, DatasetEntry, MockState, SharedState};
+
+const UPLOAD_PREFIX: &str = "upload/";
+const DOWNLOAD_PREFIX: &str = "download/with_auth/";
+
+pub(crate) async fn get(
+    State(state): State<SharedState>,
+    Path((project_id, path)): Path<(i32, String)>,
+) -> ApiResult {
+    let state = lock(&state);
+    project_or_not_found(&state, project_id)?;
+
+    if let Some(path) = path.strip_prefix(DOWNLOAD_PREFIX) {
+        let path = normalize_path(path);
+        return match state.datasets.get(&(project_id, path.clone())) {
+            Some(entry) if !entry.is_dir => Ok((
+                [(header::CONTENT_TYPE, "application/octet-stream")],
+                entry.content(),
+            )
+                .into_response()),
+            _ => Err(not_found(&format!("File {path}"))),
+        };
+    }
+
+    let path = normalize_path(&path);
+    let (size, is_dir) = match state.datasets.get(&(project_id, path.clone())) {
+        Some(entry) => (entry.content().len(), entry.is_dir),
+        None if has_children(&state, project_id, &path) => (0, true),
+        None => return Err(not_found(&format!("Path {path}"))),
+    };
+    Ok(Json(json!({
+        "attributes": {
+            "name": path.rsplit('/').next().unwrap_or_default(),
+            "path": format!("/Projects/{}/{}", state.project(project_id).unwrap().name, path),
+            "size": size,
+            "dir": is_dir,
+        }
+    }))
+    .into_response())
+}
+
+pub(crate) async fn post(
+    State(state): State<SharedState>,
+    Path((project_id, path)): Path<(i32, String)>,
+    Query(params): Query<HashMap<String, String>>,
+    body: Bytes,
+) -> ApiResult {
+    let mut state = lock(&state);
+    project_or_not_found(&state, project_id)?;
+
+    if let Some(dir) = path.strip_prefix(UPLOAD_PREFIX) {
+        let (Some(file_name), Some(chunk_number)) = (
+            params.get("flowFilename"),
+            params
+                .get("flowChunkNumber")
+                .and_then(|number| number.parse::<usize>().ok()),
+        ) else {
+            return Err(rest_error(
+                StatusCode::BAD_REQUEST,
+                110001,
+                "Missing flow parameters.",
+            ));
+        };
+        let path = normalize_path(&format!("{dir}/{file_name}"));
+        state
+            .datasets
+            .entry((project_id, path))
+            .or_default()
+            .chunks
+            .insert(chunk_number, body.to_vec());
+        return Ok(StatusCode::OK.into_response());
+    }
+
+    let path = normalize_path(&path);
+    match params.get("action").map(String::as_str) {
+        Some("create") => {
+            state
+                .datasets
+                .insert((project_id, path), DatasetEntry::dir());
+        }
+        Some(action @ ("move" | "copy")) => {
+            let Some(destination) = params.get("destination") else {
+                return Err(rest_error(
+                    StatusCode::BAD_REQUEST,
+                    110002,
+                    "Missing destination.",
+                ));
+            };
+            let destination = normalize_path(destination);
+            let entries = subtree(&state, project_id, &path);
+            if entries.is_empty() {
+                return Err(not_found(&format!("Path {path}")));
+            }
+            for (source, entry) in entries {
+                if action == "move" {
+                    state.datasets.remove(&(project_id, source.clone()));
+                }
+                let target = format!("{destination}{}", &source[path.len()..]);
+                state.datasets.insert((project_id, target), entry);
+            }
+        }
+        _ => {
+            return Err(rest_error(
+                StatusCode::BAD_REQUEST,
+                110003,
+                "Unsupported dataset action.",
+            ))
+        }
+    }
+    Ok(StatusCode::OK.into_response())
+}
+
+pub(crate) async fn delete(
+    State(state): State<SharedState>,
+    Path((project_id, path)): Path<(i32, String)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    project_or_not_found(&state, project_id)?;
+    let path = normalize_path(&path);
+    let entries = subtree(&state, project_id, &path);
+    if entries.is_empty() {
+        return Err(not_found(&format!("Path {path}")));
+    }
+    for (source, _) in entries {
+        state.datasets.remove(&(project_id, source));
+    }
+    Ok(StatusCode::OK.into_response())
+}
+
+fn has_children(state: &MockState, project_id: i32, path: &str) -> bool {
+    let prefix = format!("{path}/");
+    state
+        .datasets
+        .keys()
+        .any(|(id, entry_path)| *id == project_id && entry_path.starts_with(&prefix))
+}
+
+/// The entry at `path` and every entry below it.
+fn subtree(state: &MockState, project_id: i32, path: &str) -> Vec<(String, DatasetEntry)> {
+    let prefix = format!("{path}/");
+    state
+        .datasets
+        .iter()
+        .filter(|((id, entry_path), _)| {
+            *id == project_id && (entry_path == path || entry_path.starts_with(&prefix))
+        })
+        .map(|((_, entry_path), entry)| (entry_path.clone(), entry.clone()))
+        .collect()
+}
@@ -0,0 +1,556 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use super::{
+    feature_store_or_not_found, list_page, not_found, project_or_not_found, rest_error, ApiResult,
+};
+use crate::fixtures::{FeatureGroupFixture, FeatureViewFixture, EXTERNAL_FEATURE_GROUP_TYPE};
+use crate::state::{lock, MockState, Scoped, SharedState};
+
+pub(crate) async fn get_feature_store(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store)): Path<(i32, String)>,
+) -> ApiResult {
+    let state = lock(&state);
+    let project = project_or_not_found(&state, project_id)?;
+    if feature_store == project.feature_store_name() || feature_store == project_id.to_string() {
+        Ok(Json(project.feature_store_dto(project_id)).into_response())
+    } else {
+        Err(not_found(&format!("Featurestore {feature_store}")))
+    }
+}
+
+/// Render the SQL of a query on a single feature group, joins and filters are ignored
+/// except to list the aliases of the joined feature groups.
+pub(crate) async fn construct_query(
+    State(state): State<SharedState>,
+    Path(project_id): Path<i32>,
+    Json(payload): Json<Value>,
+) -> ApiResult {
+    project_or_not_found(&lock(&state), project_id)?;
+    let feature_group = &payload["leftFeatureGroup"];
+    let table = format!(
+        "`{}`.`{}_{}`",
+        feature_group["featurestoreName"]
+            .as_str()
+            .unwrap_or_default(),
+        feature_group["name"].as_str().unwrap_or_default(),
+        feature_group["version"].as_i64().unwrap_or_default(),
+    );
+    let columns = payload["leftFeatures"]
+        .as_array()
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(|feature| feature["name"].as_str())
+                .map(|name| format!("`fg0`.`{name}`"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        })
+        .unwrap_or_default();
+    let query = format!("SELECT {columns}\nFROM {table} `fg0`");
+
+    // Feature groups are aliased in the order of the query, as in the SQL
+    let state = lock(&state);
+    let mut hudi_cached_feature_groups = vec![];
+    let mut on_demand_feature_groups = vec![];
+    let feature_groups = std::iter::once(feature_group).chain(
+        payload["joins"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|join| &join["query"]["leftFeatureGroup"]),
+    );
+    for (index, feature_group) in feature_groups.enumerate() {
+        let alias = format!("fg{index}");
+        let feature_group = state
+            .feature_groups
+            .iter()
+            .find(|fg| fg.project_id == project_id && json!(fg.id) == feature_group["id"])
+            .map_or_else(|| feature_group.clone(), |fg| fg.value.clone());
+        if feature_group["type"] == EXTERNAL_FEATURE_GROUP_TYPE {
+            on_demand_feature_groups
+                .push(json!({ "onDemandFeatureGroup": feature_group, "alias": alias }));
+        } else {
+            hudi_cached_feature_groups.push(json!({
+                "featureGroup": feature_group,
+                "alias": alias,
+                "leftFeatureGroupStartTimestamp": null,
+                "leftFeatureGroupEndTimestamp": null,
+            }));
+        }
+    }
+
+    Ok(Json(json!({
+        "href": null,
+        "query": query,
+        "queryOnline": query,
+        "pitQuery": null,
+        "pitQueryAsof": null,
+        "hudiCachedFeatureGroups": hudi_cached_feature_groups,
+        "onDemandFeatureGroups": on_demand_feature_groups,
+    }))
+    .into_response())
+}
+
+pub(crate) async fn create_feature_group(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store)): Path<(i32, String)>,
+    Json(payload): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    let project = feature_store_or_not_found(&state, project_id, &feature_store)?;
+    let feature_group = FeatureGroupFixture::from_payload(&payload);
+    if state
+        .feature_group_versions(project_id, &feature_group.name)
+        .iter()
+        .any(|fg| fg["version"] == feature_group.version)
+    {
+        return Err(rest_error(
+            StatusCode::BAD_REQUEST,
+            270089,
+            &format!(
+                "Feature group {} with version {} already exists.",
+                feature_group.name, feature_group.version
+            ),
+        ));
+    }
+
+    let id = state.next_id();
+    let mut dto = feature_group.feature_group_dto(id, &project, project_id);
+    // External feature groups are returned with their full storage connector
+    if let Some(storage_connector) = state.storage_connectors.iter().find(|sc| {
+        sc.project_id == project_id
+            && Some(sc.id) == dto["storageConnector"]["id"].as_i64().map(|id| id as i32)
+    }) {
+        dto["storageConnector"] = storage_connector.value.clone();
+    }
+    state.feature_groups.push(Scoped {
+        project_id,
+        id,
+        value: dto.clone(),
+    });
+    Ok((StatusCode::CREATED, Json(dto)).into_response())
+}
+
+/// Every version of every feature group, as a bare array.
+pub(crate) async fn get_feature_groups(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store)): Path<(i32, String)>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    let feature_groups: Vec<Value> = state
+        .feature_groups
+        .iter()
+        .filter(|fg| fg.project_id == project_id)
+        .map(|fg| fg.value.clone())
+        .collect();
+    Ok(Json(feature_groups).into_response())
+}
+
+/// Feature groups are fetched by id as a single object, by name as the list of their versions.
+pub(crate) async fn get_feature_group(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, name)): Path<(i32, String, String)>,
+    Query(params): Query<HashMap<String, String>>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+
+    if let Ok(id) = name.parse::<i32>() {
+        return state
+            .feature_groups
+            .iter()
+            .find(|fg| fg.project_id == project_id && fg.id == id)
+            .map(|fg| Json(fg.value.clone()).into_response())
+            .ok_or_else(|| not_found(&format!("Featuregroup {id}")));
+    }
+
+    let mut feature_groups = state.feature_group_versions(project_id, &name);
+    if let Some(version) = params.get("version").and_then(|v| v.parse::<i64>().ok()) {
+        feature_groups.retain(|fg| fg["version"].as_i64() == Some(version));
+    }
+    if feature_groups.is_empty() {
+        Err(not_found(&format!("Featuregroup {name}")))
+    } else {
+        Ok(Json(feature_groups).into_response())
+    }
+}
+
+/// Update a feature group by id, the query parameters select the updated metadata.
+pub(crate) async fn update_feature_group(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, id)): Path<(i32, String, i32)>,
+    Query(params): Query<HashMap<String, String>>,
+    Json(payload): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    let feature_group = state
+        .feature_groups
+        .iter_mut()
+        .find(|fg| fg.project_id == project_id && fg.id == id)
+        .ok_or_else(|| not_found(&format!("Featuregroup {id}")))?;
+
+    let enabled = |param: &str| params.get(param).is_some_and(|value| value == "true");
+    let fg = &mut feature_group.value;
+    if enabled("appendFeatures") {
+        let mut features = payload["features"].as_array().cloned().unwrap_or_default();
+        for feature in &mut features {
+            feature["featureGroupId"] = json!(id);
+        }
+        fg["features"] = json!(features);
+    }
+    if enabled("updateMetadata") {
+        fg["description"] = payload["description"].clone();
+        let descriptions: HashMap<&str, &Value> = payload["features"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|feature| Some((feature["name"].as_str()?, &feature["description"])))
+            .collect();
+        for feature in fg["features"].as_array_mut().into_iter().flatten() {
+            let name = feature["name"].as_str().unwrap_or_default().to_string();
+            if let Some(description) = descriptions.get(name.as_str()) {
+                feature["description"] = (*description).clone();
+            }
+        }
+    }
+    if enabled("updateStatsConfig") {
+        fg["statisticsConfig"] = payload["statisticsConfig"].clone();
+    }
+    if enabled("enableOnline") || enabled("disableOnline") {
+        fg["onlineEnabled"] = json!(enabled("enableOnline"));
+    }
+    Ok(Json(fg.clone()).into_response())
+}
+
+pub(crate) async fn delete_feature_group(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, id)): Path<(i32, String, String)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    let count = state.feature_groups.len();
+    state
+        .feature_groups
+        .retain(|fg| !(fg.project_id == project_id && fg.id.to_string() == id));
+    if state.feature_groups.len() == count {
+        Err(not_found(&format!("Featuregroup {id}")))
+    } else {
+        Ok(StatusCode::OK.into_response())
+    }
+}
+
+/// Expectation suite of the feature group, an empty body if none is attached.
+pub(crate) async fn get_expectation_suite(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, fg_id)): Path<(i32, String, i32)>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_group_or_not_found(&state, project_id, &feature_store, fg_id)?;
+    match state
+        .expectation_suites
+        .iter()
+        .find(|suite| suite.project_id == project_id && suite.value["featureGroupId"] == fg_id)
+    {
+        Some(suite) => Ok(Json(suite.value.clone()).into_response()),
+        None => Ok(StatusCode::OK.into_response()),
+    }
+}
+
+/// Attach the suite to the feature group, replacing the attached suite but keeping its id.
+pub(crate) async fn save_expectation_suite(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, fg_id)): Path<(i32, String, i32)>,
+    Json(mut payload): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_group_or_not_found(&state, project_id, &feature_store, fg_id)?;
+    let previous = state
+        .expectation_suites
+        .iter()
+        .position(|suite| suite.project_id == project_id && suite.value["featureGroupId"] == fg_id);
+    let id = match previous {
+        Some(index) => state.expectation_suites.remove(index).id,
+        None => state.next_id(),
+    };
+    payload["id"] = json!(id);
+    payload["featureGroupId"] = json!(fg_id);
+    for expectation in payload["expectations"].as_array_mut().into_iter().flatten() {
+        if expectation["id"].is_null() {
+            expectation["id"] = json!(state.next_id());
+        }
+    }
+    state.expectation_suites.push(Scoped {
+        project_id,
+        id,
+        value: payload.clone(),
+    });
+    Ok(Json(payload).into_response())
+}
+
+pub(crate) async fn delete_expectation_suite(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, fg_id, id)): Path<(i32, String, i32, i32)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_group_or_not_found(&state, project_id, &feature_store, fg_id)?;
+    let count = state.expectation_suites.len();
+    state
+        .expectation_suites
+        .retain(|suite| !(suite.project_id == project_id && suite.id == id));
+    if state.expectation_suites.len() == count {
+        Err(not_found(&format!("Expectation suite {id}")))
+    } else {
+        Ok(StatusCode::NO_CONTENT.into_response())
+    }
+}
+
+pub(crate) async fn save_validation_report(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, fg_id)): Path<(i32, String, i32)>,
+    Json(mut payload): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_group_or_not_found(&state, project_id, &feature_store, fg_id)?;
+    let id = state.next_id();
+    payload["id"] = json!(id);
+    payload["featureGroupId"] = json!(fg_id);
+    payload["validationTime"] = json!("2024-01-01T00:00:00.000Z");
+    state.validation_reports.push(Scoped {
+        project_id,
+        id,
+        value: payload.clone(),
+    });
+    Ok((StatusCode::CREATED, Json(payload)).into_response())
+}
+
+/// Statistics of the feature group, the most recent computation first.
+pub(crate) async fn get_statistics(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, fg_id)): Path<(i32, String, i32)>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_group_or_not_found(&state, project_id, &feature_store, fg_id)?;
+    let mut items: Vec<Value> = state
+        .statistics
+        .iter()
+        .filter(|stats| stats.project_id == project_id && stats.value["featureGroupId"] == fg_id)
+        .map(|stats| stats.value.clone())
+        .collect();
+    items.sort_by_key(|stats| std::cmp::Reverse(stats["computationTime"].as_i64()));
+    Ok(Json(json!({ "count": items.len(), "items": items })).into_response())
+}
+
+pub(crate) async fn save_statistics(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, fg_id)): Path<(i32, String, i32)>,
+    Json(mut payload): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_group_or_not_found(&state, project_id, &feature_store, fg_id)?;
+    let id = state.next_id();
+    payload["featureGroupId"] = json!(fg_id);
+    state.statistics.push(Scoped {
+        project_id,
+        id,
+        value: payload.clone(),
+    });
+    Ok(Json(payload).into_response())
+}
+
+fn feature_group_or_not_found(
+    state: &MockState,
+    project_id: i32,
+    feature_store: &str,
+    fg_id: i32,
+) -> Result<(), axum::response::Response> {
+    feature_store_or_not_found(state, project_id, feature_store)?;
+    if state
+        .feature_groups
+        .iter()
+        .any(|fg| fg.project_id == project_id && fg.id == fg_id)
+    {
+        Ok(())
+    } else {
+        Err(not_found(&format!("Featuregroup {fg_id}")))
+    }
+}
+
+pub(crate) async fn create_feature_view(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store)): Path<(i32, String)>,
+    Json(payload): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    let name = payload["name"].as_str().unwrap_or_default().to_string();
+    let versions = state.feature_view_versions(project_id, &name);
+    let version = match payload["version"].as_i64() {
+        Some(version) => version as i32,
+        None => versions
+            .last()
+            .and_then(|fv| fv["version"].as_i64())
+            .map_or(1, |version| version as i32 + 1),
+    };
+    if versions.iter().any(|fv| fv["version"] == version) {
+        return Err(rest_error(
+            StatusCode::BAD_REQUEST,
+            270179,
+            &format!("Feature view {name} with version {version} already exists."),
+        ));
+    }
+
+    let id = state.next_id();
+    let dto = FeatureViewFixture::feature_view_dto(
+        id,
+        &name,
+        version,
+        payload["description"].as_str(),
+        payload["query"].clone(),
+    );
+    state.feature_views.push(Scoped {
+        project_id,
+        id,
+        value: dto.clone(),
+    });
+    Ok((StatusCode::CREATED, Json(dto)).into_response())
+}
+
+pub(crate) async fn get_feature_view(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, name)): Path<(i32, String, String)>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    state
+        .feature_view_versions(project_id, &name)
+        .pop()
+        .map(|fv| Json(fv).into_response())
+        .ok_or_else(|| not_found(&format!("Feature view {name}")))
+}
+
+pub(crate) async fn get_feature_view_version(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, name, version)): Path<(i32, String, String, i32)>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    state
+        .feature_view_versions(project_id, &name)
+        .into_iter()
+        .find(|fv| fv["version"] == version)
+        .map(|fv| Json(fv).into_response())
+        .ok_or_else(|| not_found(&format!("Feature view {name} with version {version}")))
+}
+
+pub(crate) async fn delete_feature_view(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, name)): Path<(i32, String, String)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    let count = state.feature_views.len();
+    state
+        .feature_views
+        .retain(|fv| !(fv.project_id == project_id && fv.value["name"] == name));
+    if state.feature_views.len() == count {
+        Err(not_found(&format!("Feature view {name}")))
+    } else {
+        Ok(StatusCode::OK.into_response())
+    }
+}
+
+pub(crate) async fn delete_feature_view_version(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, name, version)): Path<(i32, String, String, i32)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    let count = state.feature_views.len();
+    state.feature_views.retain(|fv| {
+        !(fv.project_id == project_id && fv.value["name"] == name && fv.value["version"] == version)
+    });
+    if state.feature_views.len() == count {
+        Err(not_found(&format!(
+            "Feature view {name} with version {version}"
+        )))
+    } else {
+        Ok(StatusCode::OK.into_response())
+    }
+}
+
+/// Storage connectors are listed as a bare array.
+pub(crate) async fn get_storage_connectors(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store)): Path<(i32, String)>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    let storage_connectors: Vec<Value> = state
+        .storage_connectors
+        .iter()
+        .filter(|sc| sc.project_id == project_id)
+        .map(|sc| sc.value.clone())
+        .collect();
+    Ok(Json(list_page(storage_connectors, &params).0).into_response())
+}
+
+pub(crate) async fn get_storage_connector(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store, name)): Path<(i32, String, String)>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    state
+        .storage_connectors
+        .iter()
+        .find(|sc| sc.project_id == project_id && sc.value["name"] == name)
+        .map(|sc| Json(sc.value.clone()).into_response())
+        .ok_or_else(|| not_found(&format!("Storage connector {name}")))
+}
+
+pub(crate) async fn get_online_storage_connector(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store)): Path<(i32, String)>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    state
+        .storage_connectors
+        .iter()
+        .find(|sc| {
+            sc.project_id == project_id
+                && sc.value["type"] == "JDBC"
+                && sc.value["name"]
+                    .as_str()
+                    .is_some_and(|name| name.ends_with("_onlinefeaturestore"))
+        })
+        .map(|sc| Json(sc.value.clone()).into_response())
+        .ok_or_else(|| not_found("Online feature store storage connector"))
+}
+
+pub(crate) async fn get_kafka_storage_connector(
+    State(state): State<SharedState>,
+    Path((project_id, feature_store)): Path<(i32, String)>,
+) -> ApiResult {
+    let state = lock(&state);
+    feature_store_or_not_found(&state, project_id, &feature_store)?;
+    state
+        .storage_connectors
+        .iter()
+        .find(|sc| {
+            sc.project_id == project_id && sc.value["type"] == "featurestoreKafkaConnectorDTO"
+        })
+        .map(|sc| Json(sc.value.clone()).into_response())
+        .ok_or_else(|| not_found("Kafka storage connector"))
+}
@@ -0,0 +1,245 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde_json::{json, Value};
+
+use super::{list_page, not_found, project_or_not_found, rest_error, ApiResult};
+use crate::fixtures::JobFixture;
+use crate::state::{lock, JobEntry, MockState, Scoped, SharedState};
+
+pub(crate) async fn get_jobs(
+    State(state): State<SharedState>,
+    Path(project_id): Path<i32>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> ApiResult {
+    let state = lock(&state);
+    project_or_not_found(&state, project_id)?;
+    let jobs: Vec<Value> = state
+        .jobs
+        .iter()
+        .filter(|job| job.project_id == project_id)
+        .map(|job| job.value.dto.clone())
+        .collect();
+    let (jobs, count) = list_page(jobs, &params);
+    Ok(Json(json!({
+        "href": format!("/hopsworks-api/api/project/{project_id}/jobs"),
+        "count": count,
+        "items": jobs,
+    }))
+    .into_response())
+}
+
+pub(crate) async fn get_job(
+    State(state): State<SharedState>,
+    Path((project_id, name)): Path<(i32, String)>,
+) -> ApiResult {
+    let state = lock(&state);
+    let job = job_or_not_found(&state, project_id, &name)?;
+    Ok(Json(job.value.dto.clone()).into_response())
+}
+
+pub(crate) async fn create_job(
+    State(state): State<SharedState>,
+    Path((project_id, name)): Path<(i32, String)>,
+    Json(config): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    project_or_not_found(&state, project_id)?;
+    if state.job(project_id, &name).is_some() {
+        return Err(rest_error(
+            StatusCode::CONFLICT,
+            130012,
+            &format!("Job {name} already exists."),
+        ));
+    }
+    let dto = upsert_job(&mut state, project_id, &name, config);
+    Ok((StatusCode::CREATED, Json(dto)).into_response())
+}
+
+pub(crate) async fn update_job(
+    State(state): State<SharedState>,
+    Path((project_id, name)): Path<(i32, String)>,
+    Json(config): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    project_or_not_found(&state, project_id)?;
+    let dto = upsert_job(&mut state, project_id, &name, config);
+    Ok(Json(dto).into_response())
+}
+
+pub(crate) async fn delete_job(
+    State(state): State<SharedState>,
+    Path((project_id, name)): Path<(i32, String)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    job_or_not_found(&state, project_id, &name)?;
+    state
+        .jobs
+        .retain(|job| !(job.project_id == project_id && job.value.fixture.name == name));
+    state
+        .executions
+        .retain(|exec| !(exec.project_id == project_id && exec.value["jobName"] == name));
+    Ok(StatusCode::OK.into_response())
+}
+
+/// Default configuration of a job type, e.g. `PYTHON` or `SPARK`.
+pub(crate) async fn get_job_configuration(
+    State(state): State<SharedState>,
+    Path((project_id, job_type)): Path<(i32, String)>,
+) -> ApiResult {
+    project_or_not_found(&lock(&state), project_id)?;
+    let config_type = match job_type.to_uppercase().as_str() {
+        "PYTHON" => "pythonJobConfiguration",
+        "SPARK" | "PYSPARK" => "sparkJobConfiguration",
+        "DOCKER" => "dockerJobConfiguration",
+        _ => return Err(not_found(&format!("Job type {job_type}"))),
+    };
+    Ok(Json(json!({
+        "type": config_type,
+        "jobType": job_type.to_uppercase(),
+        "appName": null,
+        "defaultArgs": null,
+        "appPath": null,
+        "resourceConfig": { "cores": 1, "memory": 2048, "gpus": 0 },
+    }))
+    .into_response())
+}
+
+/// Start an execution, the arguments are sent as plain text.
+pub(crate) async fn start_execution(
+    State(state): State<SharedState>,
+    Path((project_id, name)): Path<(i32, String)>,
+    args: String,
+) -> ApiResult {
+    let mut state = lock(&state);
+    let job = job_or_not_found(&state, project_id, &name)?
+        .value
+        .fixture
+        .clone();
+    let id = state.next_id();
+    let dto = job.execution_dto(id, project_id, &args);
+    state.executions.push(Scoped {
+        project_id,
+        id,
+        value: dto.clone(),
+    });
+    Ok((StatusCode::CREATED, Json(dto)).into_response())
+}
+
+pub(crate) async fn get_executions(
+    State(state): State<SharedState>,
+    Path((project_id, name)): Path<(i32, String)>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> ApiResult {
+    let state = lock(&state);
+    job_or_not_found(&state, project_id, &name)?;
+    let executions: Vec<Value> = state
+        .executions
+        .iter()
+        .filter(|exec| exec.project_id == project_id && exec.value["jobName"] == name)
+        .map(|exec| exec.value.clone())
+        .collect();
+    let (executions, count) = list_page(executions, &params);
+    Ok(Json(json!({
+        "href": format!("/hopsworks-api/api/project/{project_id}/jobs/{name}/executions"),
+        "count": count,
+        "items": executions,
+    }))
+    .into_response())
+}
+
+pub(crate) async fn get_execution(
+    State(state): State<SharedState>,
+    Path((project_id, name, id)): Path<(i32, String, i32)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    let execution = execution_or_not_found(&mut state, project_id, &name, id)?;
+    Ok(Json(execution.value.clone()).into_response())
+}
+
+/// Stopped executions are reported as failed with final status `KILLED`.
+pub(crate) async fn stop_execution(
+    State(state): State<SharedState>,
+    Path((project_id, name, id)): Path<(i32, String, i32)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    let execution = execution_or_not_found(&mut state, project_id, &name, id)?;
+    execution.value["state"] = json!("FAILED");
+    execution.value["finalStatus"] = json!("KILLED");
+    Ok(Json(execution.value.clone()).into_response())
+}
+
+pub(crate) async fn delete_execution(
+    State(state): State<SharedState>,
+    Path((project_id, name, id)): Path<(i32, String, i32)>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    execution_or_not_found(&mut state, project_id, &name, id)?;
+    state
+        .executions
+        .retain(|exec| !(exec.project_id == project_id && exec.id == id));
+    Ok(StatusCode::OK.into_response())
+}
+
+fn job_or_not_found<'a>(
+    state: &'a MockState,
+    project_id: i32,
+    name: &str,
+) -> Result<&'a Scoped<JobEntry>, axum::response::Response> {
+    project_or_not_found(state, project_id)?;
+    state
+        .job(project_id, name)
+        .ok_or_else(|| not_found(&format!("Job {name}")))
+}
+
+fn execution_or_not_found<'a>(
+    state: &'a mut MockState,
+    project_id: i32,
+    name: &str,
+    id: i32,
+) -> Result<&'a mut Scoped<Value>, axum::response::Response> {
+    job_or_not_found(state, project_id, name)?;
+    state
+        .executions
+        .iter_mut()
+        .find(|exec| {
+            exec.project_id == project_id && exec.id == id && exec.value["jobName"] == name
+        })
+        .ok_or_else(|| not_found(&format!("Execution {id} of job {name}")))
+}
+
+/// Create the job or replace its configuration, returns the job dto.
+fn upsert_job(state: &mut MockState, project_id: i32, name: &str, config: Value) -> Value {
+    let mut fixture = JobFixture::new(name).with_config(config.clone());
+    if let Some(job_type) = config["jobType"].as_str() {
+        fixture = fixture.with_job_type(job_type);
+    }
+
+    if let Some(job) = state
+        .jobs
+        .iter_mut()
+        .find(|job| job.project_id == project_id && job.value.fixture.name == name)
+    {
+        // Keep the execution behaviour configured by the test.
+        fixture.execution_state = job.value.fixture.execution_state.clone();
+        fixture.execution_final_status = job.value.fixture.execution_final_status.clone();
+        job.value.dto = fixture.job_dto(job.id, project_id);
+        job.value.fixture = fixture;
+        return job.value.dto.clone();
+    }
+
+    let id = state.next_id();
+    let dto = fixture.job_dto(id, project_id);
+    state.jobs.push(Scoped {
+        project_id,
+        id,
+        value: JobEntry {
+            fixture,
+            dto: dto.clone(),
+        },
+    });
+    dto
+}
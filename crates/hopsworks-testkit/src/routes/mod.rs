@@ -0,0 +1,279 @@
+//! Handlers of the REST API endpoints called by the `cluster_api` services of hopsworks-core.
+//!
+//! Errors are returned with the body of the Hopsworks REST API, the error codes are
+//! indicative and do not match the ones of a real cluster.
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+
+use crate::fixtures::ProjectFixture;
+use crate::server::{RecordedRequest, API_PREFIX};
+use crate::state::{lock, MockState, SharedState};
+
+mod dataset;
+mod feature_store;
+mod job;
+mod project;
+
+pub(crate) type ApiResult = std::result::Result<Response, Response>;
+
+pub(crate) fn router(state: SharedState) -> Router {
+    let project_api = Router::new()
+        .route("/credentials", get(project::get_credentials))
+        .route("/featurestores/query", put(feature_store::construct_query))
+        .route("/featurestores/:fs", get(feature_store::get_feature_store))
+        .route(
+            "/featurestores/:fs/featuregroups",
+            get(feature_store::get_feature_groups).post(feature_store::create_feature_group),
+        )
+        .route(
+            "/featurestores/:fs/featuregroups/",
+            post(feature_store::create_feature_group),
+        )
+        .route(
+            "/featurestores/:fs/featuregroups/:name",
+            get(feature_store::get_feature_group)
+                .put(feature_store::update_feature_group)
+                .delete(feature_store::delete_feature_group),
+        )
+        .route(
+            "/featurestores/:fs/featuregroups/:name/expectationsuite",
+            get(feature_store::get_expectation_suite).put(feature_store::save_expectation_suite),
+        )
+        .route(
+            "/featurestores/:fs/featuregroups/:name/expectationsuite/:id",
+            delete(feature_store::delete_expectation_suite),
+        )
+        .route(
+            "/featurestores/:fs/featuregroups/:name/validationreport",
+            put(feature_store::save_validation_report),
+        )
+        .route(
+            "/featurestores/:fs/featuregroups/:name/statistics",
+            get(feature_store::get_statistics).post(feature_store::save_statistics),
+        )
+        .route(
+            "/featurestores/:fs/featureview",
+            post(feature_store::create_feature_view),
+        )
+        .route(
+            "/featurestores/:fs/featureview/:name",
+            get(feature_store::get_feature_view).delete(feature_store::delete_feature_view),
+        )
+        .route(
+            "/featurestores/:fs/featureview/:name/version/:version",
+            get(feature_store::get_feature_view_version)
+                .delete(feature_store::delete_feature_view_version),
+        )
+        .route(
+            "/featurestores/:fs/storageconnectors",
+            get(feature_store::get_storage_connectors),
+        )
+        .route(
+            "/featurestores/:fs/storageconnectors/:name",
+            get(feature_store::get_storage_connector),
+        )
+        .route(
+            "/featurestores/:fs/storageconnectors/onlinefeaturestore",
+            get(feature_store::get_online_storage_connector),
+        )
+        .route(
+            "/featurestores/:fs/storageconnectors/kafka_connector/byok",
+            get(feature_store::get_kafka_storage_connector),
+        )
+        .route("/jobs", get(job::get_jobs))
+        .route(
+            "/jobs/:name",
+            get(job::get_job)
+                .post(job::create_job)
+                .put(job::update_job)
+                .delete(job::delete_job),
+        )
+        .route("/jobs/:name/configuration", get(job::get_job_configuration))
+        .route(
+            "/jobs/:name/executions",
+            get(job::get_executions).post(job::start_execution),
+        )
+        .route(
+            "/jobs/:name/executions/:id",
+            get(job::get_execution)
+                .put(job::stop_execution)
+                .delete(job::delete_execution),
+        )
+        .route(
+            "/dataset/*path",
+            get(dataset::get)
+                .post(dataset::post)
+                .delete(dataset::delete),
+        );
+
+    let api = Router::new()
+        .route(
+            "/project",
+            get(project::get_projects).post(project::create_project),
+        )
+        .route("/variables/:name", get(project::get_variable))
+        .nest("/project/:project_id", project_api);
+
+    Router::new()
+        .nest(API_PREFIX, api)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            authenticate_and_record,
+        ))
+        .with_state(state)
+}
+
+/// Record every request and reject the ones without the API key of the mock.
+async fn authenticate_and_record(
+    State(state): State<SharedState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => return rest_error(StatusCode::BAD_REQUEST, 100000, &e.to_string()),
+    };
+
+    let authorized = {
+        let mut state = lock(&state);
+        state.requests.push(RecordedRequest {
+            method: parts.method.to_string(),
+            path: parts
+                .uri
+                .path()
+                .trim_start_matches(API_PREFIX)
+                .trim_start_matches('/')
+                .to_string(),
+            query: parts.uri.query().map(String::from),
+            body: body.to_vec(),
+        });
+        let expected = format!("ApiKey {}", state.api_key);
+        parts
+            .headers
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            == Some(expected.as_str())
+    };
+
+    if !authorized {
+        return rest_error(
+            StatusCode::UNAUTHORIZED,
+            200003,
+            "Authorization header not set or invalid API key.",
+        );
+    }
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+pub(crate) fn rest_error(status: StatusCode, error_code: i64, message: &str) -> Response {
+    (
+        status,
+        Json(json!({
+            "errorCode": error_code,
+            "errorMsg": message,
+            "usrMsg": message,
+        })),
+    )
+        .into_response()
+}
+
+pub(crate) fn not_found(what: &str) -> Response {
+    rest_error(
+        StatusCode::NOT_FOUND,
+        100001,
+        &format!("{what} wasn't found."),
+    )
+}
+
+pub(crate) fn project_or_not_found(
+    state: &MockState,
+    project_id: i32,
+) -> Result<ProjectFixture, Response> {
+    state
+        .project(project_id)
+        .cloned()
+        .ok_or_else(|| not_found("Project"))
+}
+
+/// Apply the `filter_by`, `sort_by`, `offset` and `limit` query parameters of a list request,
+/// returns the page and the number of items matching the filters.
+///
+/// Expressions are matched case-insensitively against the top level fields of the items,
+/// e.g. `state:RUNNING` or `submissiontime:desc`.
+pub(crate) fn list_page(mut items: Vec<Value>, params: &[(String, String)]) -> (Vec<Value>, usize) {
+    for (_, filter) in params.iter().filter(|(key, _)| key == "filter_by") {
+        if let Some((field, value)) = filter.split_once(':') {
+            items.retain(|item| {
+                field_value(item, field).is_some_and(|v| v.eq_ignore_ascii_case(value))
+            });
+        }
+    }
+    for (_, sort_by) in params.iter().filter(|(key, _)| key == "sort_by") {
+        // The first expression has the highest priority, sort by the last one first
+        for expression in sort_by.split(',').rev() {
+            let (field, order) = expression.split_once(':').unwrap_or((expression, "asc"));
+            let descending = order.eq_ignore_ascii_case("desc");
+            items.sort_by(|a, b| {
+                let ordering = compare_fields(field_value(a, field), field_value(b, field));
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+    }
+
+    let count = items.len();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+    };
+    let offset = param("offset").unwrap_or(0);
+    let limit = param("limit").unwrap_or(usize::MAX);
+    (items.into_iter().skip(offset).take(limit).collect(), count)
+}
+
+fn compare_fields(a: Option<String>, b: Option<String>) -> Ordering {
+    let as_number = |value: &Option<String>| value.as_ref()?.parse::<f64>().ok();
+    match (as_number(&a), as_number(&b)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        _ => a.cmp(&b),
+    }
+}
+
+fn field_value(item: &Value, field: &str) -> Option<String> {
+    item.as_object()?
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(field))
+        .map(|(_, value)| match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        })
+}
+
+/// Check that `feature_store_id` is the default feature store of the project.
+pub(crate) fn feature_store_or_not_found(
+    state: &MockState,
+    project_id: i32,
+    feature_store_id: &str,
+) -> Result<ProjectFixture, Response> {
+    let project = project_or_not_found(state, project_id)?;
+    if feature_store_id.parse::<i32>() == Ok(project_id) {
+        Ok(project)
+    } else {
+        Err(not_found("Featurestore"))
+    }
+}
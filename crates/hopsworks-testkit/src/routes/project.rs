@@ -0,0 +1,73 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde_json::{json, Value};
+
+use super::{not_found, project_or_not_found, rest_error, ApiResult};
+use crate::fixtures::{credentials_dto, ProjectFixture};
+use crate::state::{lock, SharedState};
+
+pub(crate) async fn get_projects(State(state): State<SharedState>) -> ApiResult {
+    let state = lock(&state);
+    let projects: Vec<Value> = state
+        .projects
+        .iter()
+        .map(|(id, project)| project.project_and_user_dto(*id))
+        .collect();
+    Ok(Json(projects).into_response())
+}
+
+pub(crate) async fn create_project(
+    State(state): State<SharedState>,
+    Json(payload): Json<Value>,
+) -> ApiResult {
+    let mut state = lock(&state);
+    let Some(name) = payload["name"].as_str() else {
+        return Err(rest_error(
+            StatusCode::BAD_REQUEST,
+            150001,
+            "Project name is missing.",
+        ));
+    };
+    if state.project_id(name).is_some() {
+        return Err(rest_error(
+            StatusCode::CONFLICT,
+            150013,
+            &format!("Project {name} already exists."),
+        ));
+    }
+
+    let mut project = ProjectFixture::new(name);
+    if let Some(description) = payload["description"].as_str() {
+        project = project.with_description(description);
+    }
+    let id = state.add_project(project.clone());
+    Ok((
+        StatusCode::CREATED,
+        Json(json!([project.project_and_user_dto(id)])),
+    )
+        .into_response())
+}
+
+pub(crate) async fn get_credentials(
+    State(state): State<SharedState>,
+    Path(project_id): Path<i32>,
+) -> ApiResult {
+    project_or_not_found(&lock(&state), project_id)?;
+    Ok(Json(credentials_dto()).into_response())
+}
+
+pub(crate) async fn get_variable(
+    State(state): State<SharedState>,
+    Path(name): Path<String>,
+) -> ApiResult {
+    let state = lock(&state);
+    let value = state
+        .variables
+        .get(&name)
+        .ok_or_else(|| not_found(&format!("Variable {name}")))?;
+    Ok(Json(json!({ "successMessage": value })).into_response())
+}
@@ -0,0 +1,293 @@
+use hopsworks_core::{HopsworksClientBuilder, Project, Result, RetryPolicy, RuntimeConfig};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+use tracing::debug;
+
+use crate::fixtures::{
+    FeatureGroupFixture, FeatureViewFixture, JobFixture, ProjectFixture, StorageConnectorFixture,
+};
+use crate::state::{lock, DatasetEntry, JobEntry, MockState, Scoped, SharedState};
+
+pub const DEFAULT_TESTKIT_API_KEY: &str = "testkit_api_key";
+pub(crate) const API_PREFIX: &str = "/hopsworks-api/api";
+
+/// Request received by the mock server, with the path relative to the REST API root.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn json(&self) -> Option<Value> {
+        serde_json::from_slice(&self.body).ok()
+    }
+}
+
+/// In-process stand-in for a Hopsworks cluster, listening on a random local port.
+///
+/// The server is stopped and the certificates written by the SDK on login are removed on drop.
+pub struct MockHopsworks {
+    addr: SocketAddr,
+    state: SharedState,
+    cert_dir: PathBuf,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockHopsworks {
+    /// Start an empty cluster accepting the [`DEFAULT_TESTKIT_API_KEY`].
+    pub async fn start() -> Result<Self> {
+        MockHopsworks::start_with_api_key(DEFAULT_TESTKIT_API_KEY).await
+    }
+
+    pub async fn start_with_api_key(api_key: &str) -> Result<Self> {
+        let state: SharedState = Arc::new(Mutex::new(MockState::new(api_key)));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+
+        let router = crate::routes::router(state.clone());
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_signal.await;
+                })
+                .await;
+        });
+        debug!("Mock Hopsworks listening on {}", addr);
+
+        // The SDK writes the project certificates in a sub-directory of cert_dir on login.
+        let cert_dir = std::env::temp_dir().join(format!("hopsworks-testkit-{}", addr.port()));
+        std::fs::create_dir_all(&cert_dir)?;
+
+        Ok(MockHopsworks {
+            addr,
+            state,
+            cert_dir,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// Url of the REST API, to pass to [`HopsworksClientBuilder::with_url`].
+    pub fn url(&self) -> String {
+        format!("http://{}{}", self.addr, API_PREFIX)
+    }
+
+    pub fn api_key(&self) -> String {
+        lock(&self.state).api_key.clone()
+    }
+
+    /// Client builder targeting the mock, with retries disabled and a temporary cert_dir.
+    pub fn client_builder(&self) -> HopsworksClientBuilder {
+        HopsworksClientBuilder::new()
+            .with_url(&self.url())
+            .with_api_key(&self.api_key())
+            .with_cert_dir(self.cert_dir.to_str().unwrap())
+            .with_retry_policy(RetryPolicy::disabled())
+    }
+
+    /// Login to `project_name`, or the first project if None, with a client independent
+    /// of the global one so that tests can run in parallel.
+    pub async fn connect(&self, project_name: Option<&str>) -> Result<Project> {
+        let mut builder = self.client_builder();
+        if let Some(project_name) = project_name {
+            builder = builder.with_project_name(project_name);
+        }
+        hopsworks_core::connect(Some(builder), RuntimeConfig::Current).await
+    }
+
+    /// Add a project and its default feature store, returns the project id.
+    pub fn add_project(&self, project: ProjectFixture) -> i32 {
+        lock(&self.state).add_project(project)
+    }
+
+    /// Add a feature group to the default feature store of `project_name`, returns its id.
+    ///
+    /// # Panics
+    /// If the project does not exist.
+    pub fn add_feature_group(&self, project_name: &str, feature_group: FeatureGroupFixture) -> i32 {
+        let mut state = lock(&self.state);
+        let project_id = expect_project(&state, project_name);
+        let id = state.next_id();
+        let project = state.project(project_id).unwrap();
+        let dto = feature_group.feature_group_dto(id, project, project_id);
+        state.feature_groups.push(Scoped {
+            project_id,
+            id,
+            value: dto,
+        });
+        id
+    }
+
+    /// Add a feature view to the default feature store of `project_name`, returns its id.
+    ///
+    /// # Panics
+    /// If the project or the feature group of the feature view does not exist.
+    pub fn add_feature_view(&self, project_name: &str, feature_view: FeatureViewFixture) -> i32 {
+        let mut state = lock(&self.state);
+        let project_id = expect_project(&state, project_name);
+        let feature_group = state
+            .feature_group_versions(project_id, &feature_view.feature_group_name)
+            .into_iter()
+            .find(|fg| fg["version"] == feature_view.feature_group_version)
+            .unwrap_or_else(|| {
+                panic!(
+                    "No feature group {} with version {} in project {}",
+                    feature_view.feature_group_name,
+                    feature_view.feature_group_version,
+                    project_name
+                )
+            });
+        let id = state.next_id();
+        let dto = FeatureViewFixture::feature_view_dto(
+            id,
+            &feature_view.name,
+            feature_view.version,
+            feature_view.description.as_deref(),
+            FeatureViewFixture::query_dto(&feature_group),
+        );
+        state.feature_views.push(Scoped {
+            project_id,
+            id,
+            value: dto,
+        });
+        id
+    }
+
+    /// Add a job to `project_name`, returns its id.
+    ///
+    /// # Panics
+    /// If the project does not exist.
+    pub fn add_job(&self, project_name: &str, job: JobFixture) -> i32 {
+        let mut state = lock(&self.state);
+        let project_id = expect_project(&state, project_name);
+        let id = state.next_id();
+        let dto = job.job_dto(id, project_id);
+        state.jobs.push(Scoped {
+            project_id,
+            id,
+            value: JobEntry { fixture: job, dto },
+        });
+        id
+    }
+
+    /// Add a storage connector to the default feature store of `project_name`, returns its id.
+    ///
+    /// # Panics
+    /// If the project does not exist.
+    pub fn add_storage_connector(
+        &self,
+        project_name: &str,
+        storage_connector: StorageConnectorFixture,
+    ) -> i32 {
+        let mut state = lock(&self.state);
+        let project_id = expect_project(&state, project_name);
+        let id = state.next_id();
+        let dto = storage_connector.storage_connector_dto(id, project_id);
+        debug!("Adding storage connector {}", storage_connector.name());
+        state.storage_connectors.push(Scoped {
+            project_id,
+            id,
+            value: dto,
+        });
+        id
+    }
+
+    /// Add a file to the datasets of `project_name`, e.g. `Resources/data.csv`.
+    ///
+    /// # Panics
+    /// If the project does not exist.
+    pub fn add_file(&self, project_name: &str, path: &str, content: &[u8]) {
+        let mut state = lock(&self.state);
+        let project_id = expect_project(&state, project_name);
+        state.datasets.insert(
+            (project_id, normalize_path(path)),
+            DatasetEntry::file(content.to_vec()),
+        );
+    }
+
+    /// Content of a file of the datasets of `project_name`, e.g. one uploaded by the SDK.
+    pub fn file(&self, project_name: &str, path: &str) -> Option<Vec<u8>> {
+        let state = lock(&self.state);
+        let project_id = state.project_id(project_name)?;
+        state
+            .datasets
+            .get(&(project_id, normalize_path(path)))
+            .filter(|entry| !entry.is_dir)
+            .map(DatasetEntry::content)
+    }
+
+    /// Set a variable served by the `variables` endpoint, e.g. `loadbalancer_external_domain_mysqld`.
+    pub fn set_variable(&self, name: &str, value: &str) {
+        lock(&self.state)
+            .variables
+            .insert(name.to_string(), value.to_string());
+    }
+
+    /// Feature group as stored by the mock, e.g. after the SDK registered it.
+    pub fn feature_group(&self, project_name: &str, name: &str, version: i32) -> Option<Value> {
+        let state = lock(&self.state);
+        let project_id = state.project_id(project_name)?;
+        state
+            .feature_group_versions(project_id, name)
+            .into_iter()
+            .find(|fg| fg["version"] == version)
+    }
+
+    /// Executions started for a job of `project_name`, in submission order.
+    pub fn job_executions(&self, project_name: &str, job_name: &str) -> Vec<Value> {
+        let state = lock(&self.state);
+        let Some(project_id) = state.project_id(project_name) else {
+            return vec![];
+        };
+        state
+            .executions
+            .iter()
+            .filter(|exec| exec.project_id == project_id && exec.value["jobName"] == job_name)
+            .map(|exec| exec.value.clone())
+            .collect()
+    }
+
+    /// Validation reports uploaded for the feature groups of `project_name`, in upload order.
+    pub fn validation_reports(&self, project_name: &str) -> Vec<Value> {
+        let state = lock(&self.state);
+        let Some(project_id) = state.project_id(project_name) else {
+            return vec![];
+        };
+        state
+            .validation_reports
+            .iter()
+            .filter(|report| report.project_id == project_id)
+            .map(|report| report.value.clone())
+            .collect()
+    }
+
+    /// Requests received so far, in order of arrival.
+    pub fn received_requests(&self) -> Vec<RecordedRequest> {
+        lock(&self.state).requests.clone()
+    }
+}
+
+impl Drop for MockHopsworks {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        let _ = std::fs::remove_dir_all(&self.cert_dir);
+    }
+}
+
+fn expect_project(state: &MockState, project_name: &str) -> i32 {
+    state
+        .project_id(project_name)
+        .unwrap_or_else(|| panic!("No project {} in the mock, add it first", project_name))
+}
+
+pub(crate) fn normalize_path(path: &str) -> String {
+    path.trim_matches('/').to_string()
+}
@@ -0,0 +1,153 @@
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::fixtures::{JobFixture, ProjectFixture};
+use crate::server::RecordedRequest;
+
+pub(crate) type SharedState = Arc<Mutex<MockState>>;
+
+pub(crate) fn lock(state: &SharedState) -> MutexGuard<'_, MockState> {
+    // A panicking test must not prevent the other requests from being served.
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Entities owned by a project, the default feature store id is the project id.
+#[derive(Debug, Clone)]
+pub(crate) struct Scoped<T> {
+    pub(crate) project_id: i32,
+    pub(crate) id: i32,
+    pub(crate) value: T,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct JobEntry {
+    pub(crate) fixture: JobFixture,
+    pub(crate) dto: Value,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DatasetEntry {
+    /// Chunks received by the upload endpoint, indexed by flow chunk number.
+    pub(crate) chunks: BTreeMap<usize, Vec<u8>>,
+    pub(crate) is_dir: bool,
+}
+
+impl DatasetEntry {
+    pub(crate) fn file(content: Vec<u8>) -> Self {
+        DatasetEntry {
+            chunks: BTreeMap::from([(1, content)]),
+            is_dir: false,
+        }
+    }
+
+    pub(crate) fn dir() -> Self {
+        DatasetEntry {
+            chunks: BTreeMap::new(),
+            is_dir: true,
+        }
+    }
+
+    pub(crate) fn content(&self) -> Vec<u8> {
+        self.chunks.values().flatten().copied().collect()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct MockState {
+    pub(crate) api_key: String,
+    next_id: i32,
+    pub(crate) projects: Vec<(i32, ProjectFixture)>,
+    pub(crate) feature_groups: Vec<Scoped<Value>>,
+    pub(crate) feature_views: Vec<Scoped<Value>>,
+    pub(crate) storage_connectors: Vec<Scoped<Value>>,
+    /// Expectation suites and validation reports, with the id of their feature group in `featureGroupId`.
+    pub(crate) expectation_suites: Vec<Scoped<Value>>,
+    pub(crate) validation_reports: Vec<Scoped<Value>>,
+    /// Statistics of the feature groups, with the id of their feature group in `featureGroupId`.
+    pub(crate) statistics: Vec<Scoped<Value>>,
+    pub(crate) jobs: Vec<Scoped<JobEntry>>,
+    pub(crate) executions: Vec<Scoped<Value>>,
+    /// Files and directories of the project datasets, indexed by path relative to the project.
+    pub(crate) datasets: BTreeMap<(i32, String), DatasetEntry>,
+    pub(crate) variables: HashMap<String, String>,
+    pub(crate) requests: Vec<RecordedRequest>,
+}
+
+impl MockState {
+    pub(crate) fn new(api_key: &str) -> Self {
+        MockState {
+            api_key: api_key.to_string(),
+            next_id: 1,
+            projects: vec![],
+            feature_groups: vec![],
+            feature_views: vec![],
+            storage_connectors: vec![],
+            expectation_suites: vec![],
+            validation_reports: vec![],
+            statistics: vec![],
+            jobs: vec![],
+            executions: vec![],
+            datasets: BTreeMap::new(),
+            variables: HashMap::from([("enable_flyingduck".to_string(), "false".to_string())]),
+            requests: vec![],
+        }
+    }
+
+    pub(crate) fn next_id(&mut self) -> i32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    pub(crate) fn project(&self, project_id: i32) -> Option<&ProjectFixture> {
+        self.projects
+            .iter()
+            .find(|(id, _)| *id == project_id)
+            .map(|(_, project)| project)
+    }
+
+    pub(crate) fn project_id(&self, project_name: &str) -> Option<i32> {
+        self.projects
+            .iter()
+            .find(|(_, project)| project.name == project_name)
+            .map(|(id, _)| *id)
+    }
+
+    pub(crate) fn add_project(&mut self, project: ProjectFixture) -> i32 {
+        let id = self.next_id();
+        self.projects.push((id, project));
+        id
+    }
+
+    /// Feature groups of a feature store sorted by increasing version.
+    pub(crate) fn feature_group_versions(&self, feature_store_id: i32, name: &str) -> Vec<Value> {
+        let mut feature_groups: Vec<Value> = self
+            .feature_groups
+            .iter()
+            .filter(|fg| fg.project_id == feature_store_id && fg.value["name"] == name)
+            .map(|fg| fg.value.clone())
+            .collect();
+        feature_groups.sort_by_key(|fg| fg["version"].as_i64());
+        feature_groups
+    }
+
+    pub(crate) fn feature_view_versions(&self, feature_store_id: i32, name: &str) -> Vec<Value> {
+        let mut feature_views: Vec<Value> = self
+            .feature_views
+            .iter()
+            .filter(|fv| fv.project_id == feature_store_id && fv.value["name"] == name)
+            .map(|fv| fv.value.clone())
+            .collect();
+        feature_views.sort_by_key(|fv| fv["version"].as_i64());
+        feature_views
+    }
+
+    pub(crate) fn job(&self, project_id: i32, name: &str) -> Option<&Scoped<JobEntry>> {
+        self.jobs
+            .iter()
+            .find(|job| job.project_id == project_id && job.value.fixture.name == name)
+    }
+}
@@ -1,6 +1,6 @@
 from __future__ import annotations
 
-from typing import TYPE_CHECKING, List, Literal, Union
+from typing import TYPE_CHECKING, Dict, List, Literal, Optional, Union
 
 from hopsworks_sdk import job_execution, query
 from hopsworks_sdk.hopsworks_rs import PyFeatureGroup
@@ -25,8 +25,12 @@ class FeatureGroup:
         fg_obj._fg = fg
         return fg_obj
 
-    def save_using_polars_dataframe_schema(self, dataframe: pl.DataFrame) -> None:
-        self._fg.register_feature_group_from_polars(dataframe)
+    def save_using_polars_dataframe_schema(
+        self,
+        dataframe: pl.DataFrame,
+        type_overrides: Optional[Dict[str, str]] = None,
+    ) -> None:
+        self._fg.register_feature_group_from_polars(dataframe, type_overrides)
 
     def read_from_offline_store(
         self, return_type: Literal["polars", "pyarrow"] = "polars"
@@ -63,9 +67,13 @@ class FeatureGroup:
 
         return df
 
-    def insert(self, dataframe: pl.DataFrame) -> job_execution.JobExecution:
+    def insert(
+        self,
+        dataframe: pl.DataFrame,
+        producer_config: Optional[Dict[str, str]] = None,
+    ) -> job_execution.JobExecution:
         return job_execution.JobExecution._from_pyjobexec(
-            self._fg.insert_polars_df_into_kafka(dataframe)
+            self._fg.insert_polars_df_into_kafka(dataframe, producer_config)
         )
 
     def delete(self) -> None:
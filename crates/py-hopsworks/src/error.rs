@@ -0,0 +1,19 @@
+use hopsworks_api::HopsworksError;
+use pyo3::exceptions::{
+    PyConnectionError, PyLookupError, PyOSError, PyPermissionError, PyRuntimeError, PyValueError,
+};
+use pyo3::PyErr;
+
+/// Map the Rust error onto the closest built-in Python exception,
+/// so that Python callers can catch e.g. a `LookupError` for missing entities.
+pub(crate) fn to_py_err(err: HopsworksError) -> PyErr {
+    let message = err.to_string();
+    match err {
+        HopsworksError::NotFound(_) => PyLookupError::new_err(message),
+        HopsworksError::Auth(_) => PyPermissionError::new_err(message),
+        HopsworksError::Validation(_) => PyValueError::new_err(message),
+        HopsworksError::Transport(_) => PyConnectionError::new_err(message),
+        HopsworksError::Io(_) => PyOSError::new_err(message),
+        _ => PyRuntimeError::new_err(message),
+    }
+}
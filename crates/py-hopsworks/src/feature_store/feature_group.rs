@@ -1,9 +1,13 @@
 use crate::platform::job_execution::PyJobExecution;
 use arrow::pyarrow::ToPyArrow;
 use polars::prelude::DataFrame;
+use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
+
+use crate::error::to_py_err;
 use pyo3_polars::PyDataFrame;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tracing::debug;
 
 use super::query::PyQuery;
@@ -55,7 +59,7 @@ impl PyFeatureGroup {
 
     fn select(&self, features: Vec<String>) -> PyResult<PyQuery> {
         let features: Vec<&str> = features.iter().map(|s| s.as_str()).collect();
-        let query = self.fg.select(&features)?;
+        let query = self.fg.select(&features).map_err(to_py_err)?;
         Ok(PyQuery::from(query))
     }
 
@@ -65,14 +69,13 @@ impl PyFeatureGroup {
         feature_names: Vec<String>,
         feature_dtypes: Vec<String>,
     ) -> PyResult<()> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let registered_fg =
             hopsworks_api::blocking::feature_group::register_feature_group_if_needed_blocking(
                 &self.fg,
                 &feature_names,
                 &feature_dtypes,
-                multithreaded,
-            )?;
+            )
+            .map_err(to_py_err)?;
         if let Some(fg) = registered_fg {
             self.fg = fg;
             debug!("Registered Feature Group: {:?}", self.fg);
@@ -81,28 +84,34 @@ impl PyFeatureGroup {
     }
 
     #[tracing::instrument(skip(self, df), fields(fg_name = self.fg.name(), fg_version=self.fg.version(), schema))]
-    fn register_feature_group_from_polars(&mut self, df: PyDataFrame) -> PyResult<()> {
+    #[pyo3(signature = (df, type_overrides=None))]
+    fn register_feature_group_from_polars(
+        &mut self,
+        df: PyDataFrame,
+        type_overrides: Option<HashMap<String, String>>,
+    ) -> PyResult<()> {
         let schema = df.0.schema();
         let (feature_names, feature_types) =
-            hopsworks_api::polars::extract_features_from_polars_schema(schema)?;
+            hopsworks_api::polars::extract_features_from_polars_schema_with_overrides(
+                schema,
+                &type_overrides.unwrap_or_default(),
+            )
+            .map_err(to_py_err)?;
         self.register_feature_group(feature_names, feature_types)
     }
 
     fn delete(&self) -> PyResult<()> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
-        hopsworks_api::blocking::feature_group::delete_blocking(&self.fg, multithreaded)?;
+        hopsworks_api::blocking::feature_group::delete_blocking(&self.fg).map_err(to_py_err)?;
         Ok(())
     }
 
     #[cfg(feature = "read_arrow_flight_offline_store")]
     fn read_polars_from_offline_store(&self) -> PyResult<PyDataFrame> {
         let before = std::time::Instant::now();
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let df = hopsworks_api::offline_store::read_polars_from_offline_feature_store_blocking(
-            &self.fg,
-            None,
-            multithreaded,
-        )?;
+            &self.fg, None,
+        )
+        .map_err(to_py_err)?;
         debug!(
             "Reading from offline store via rust took: {:?}",
             before.elapsed()
@@ -113,12 +122,10 @@ impl PyFeatureGroup {
     #[cfg(feature = "read_arrow_flight_offline_store")]
     fn read_arrow_from_offline_store(&self, py: Python) -> PyResult<PyObject> {
         let before = std::time::Instant::now();
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let batches = hopsworks_api::offline_store::read_arrow_from_offline_feature_store_blocking(
-            &self.fg,
-            None,
-            multithreaded,
-        )?;
+            &self.fg, None,
+        )
+        .map_err(to_py_err)?;
         debug!(
             "Reading from offline store via rust took: {:?}",
             before.elapsed()
@@ -132,12 +139,9 @@ impl PyFeatureGroup {
     #[cfg(feature = "read_sql_online_store")]
     fn read_arrow_from_sql_online_store(&self, py: Python) -> PyResult<PyObject> {
         let before = std::time::Instant::now();
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let (batches, _) =
-            hopsworks_api::online_store::read_arrow_from_online_store_via_sql_blocking(
-                &self.fg,
-                multithreaded,
-            )?;
+            hopsworks_api::online_store::read_arrow_from_online_store_via_sql_blocking(&self.fg)
+                .map_err(to_py_err)?;
         debug!(
             "Reading from online store via rust took: {:?}",
             before.elapsed()
@@ -148,11 +152,9 @@ impl PyFeatureGroup {
     #[cfg(feature = "read_sql_online_store")]
     fn read_polars_from_sql_online_store(&self) -> PyResult<PyDataFrame> {
         let before = std::time::Instant::now();
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
-        let df = hopsworks_api::online_store::read_polars_from_online_store_via_sql_blocking(
-            &self.fg,
-            multithreaded,
-        )?;
+        let df =
+            hopsworks_api::online_store::read_polars_from_online_store_via_sql_blocking(&self.fg)
+                .map_err(to_py_err)?;
         debug!(
             "Reading from online store via rust took: {:?}",
             before.elapsed()
@@ -161,22 +163,57 @@ impl PyFeatureGroup {
     }
 
     #[cfg(feature = "insert_into_kafka")]
+    #[pyo3(signature = (df, producer_config=None))]
     fn insert_polars_df_into_kafka(
         &mut self,
         py: Python<'_>,
         df: PyDataFrame,
+        producer_config: Option<HashMap<String, String>>,
     ) -> PyResult<PyJobExecution> {
         let before = std::time::Instant::now();
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let mut dataframe: DataFrame = df.into();
-        let job_execution = py.allow_threads(move || {
-            hopsworks_api::kafka::insert_polars_df_into_kafka_blocking(
-                &mut dataframe,
-                &self.fg,
-                multithreaded,
+        let producer_options = producer_config.map(|config| {
+            config.into_iter().fold(
+                hopsworks_api::kafka::KafkaProducerOptions::new(),
+                |options, (key, value)| options.with_override(key, value),
             )
         });
-        debug!("Inserting into Kafka via rust took: {:?}", before.elapsed());
-        Ok(PyJobExecution::from(job_execution?))
+        let (job_execution, report) = py
+            .allow_threads(move || {
+                hopsworks_api::kafka::insert_polars_df_into_kafka_blocking(
+                    &mut dataframe,
+                    &self.fg,
+                    None,
+                    producer_options,
+                )
+            })
+            .map_err(to_py_err)?;
+        debug!(
+            "Inserting into Kafka via rust took: {:?}, delivered {} of {} rows",
+            before.elapsed(),
+            report.delivered,
+            report.rows
+        );
+        if let Some(failed) = report.failed.first() {
+            return Err(PyRuntimeError::new_err(format!(
+                "Failed to deliver {} of {} rows to Kafka, e.g. the row with key {} failed with: {}. \
+                 The delivered rows are materialized by the job execution {}.",
+                report.failed.len(),
+                report.rows,
+                failed.key,
+                failed.error,
+                job_execution.id()
+            )));
+        }
+        Ok(PyJobExecution::from(job_execution))
+    }
+
+    #[cfg(feature = "insert_into_kafka")]
+    fn delete_records(&self, py: Python<'_>, df: PyDataFrame) -> PyResult<PyJobExecution> {
+        let dataframe: DataFrame = df.into();
+        let job_execution = py.allow_threads(move || {
+            hopsworks_api::kafka::delete_records_from_kafka_blocking(&dataframe, &self.fg, None)
+        });
+        Ok(PyJobExecution::from(job_execution.map_err(to_py_err)?))
     }
 }
@@ -7,6 +7,8 @@ use pyo3::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::error::to_py_err;
+
 #[pyclass]
 #[repr(transparent)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -25,8 +27,7 @@ impl PyFeatureView {
     }
 
     fn delete(&self) -> PyResult<()> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
-        hopsworks_api::blocking::feature_view::delete_blocking(&self.fv, multithreaded)?;
+        hopsworks_api::blocking::feature_view::delete_blocking(&self.fv).map_err(to_py_err)?;
         Ok(())
     }
 
@@ -39,13 +40,12 @@ impl PyFeatureView {
         let api_key = api_key.as_str();
         let api_version = api_version.as_str();
         let reqwest_client = None; // TODO: Add possibility to pass a reqwest client/builder
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         hopsworks_api::online_store::rest_read::init_online_store_rest_client_blocking(
             api_key,
             api_version,
             reqwest_client,
-            multithreaded,
-        )?;
+        )
+        .map_err(to_py_err)?;
         Ok(())
     }
 
@@ -60,14 +60,13 @@ impl PyFeatureView {
         let entry_payload: EntryValuesPayload = entries_to_payload(entry)?;
         let passed_values: Option<PassedValuesPayload> = None;
         let rest_read_options = None;
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let sfv = hopsworks_api::online_store::rest_read::get_feature_vector_blocking(
             &self.fv,
             entry_payload,
             passed_values,
             rest_read_options,
-            multithreaded,
-        )?;
+        )
+        .map_err(to_py_err)?;
         tracing::info!("{:?}", sfv);
         Ok(())
     }
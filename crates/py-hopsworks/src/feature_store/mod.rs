@@ -1,4 +1,6 @@
 use pyo3::prelude::*;
+
+use crate::error::to_py_err;
 use query::PyQuery;
 
 pub mod embedding_feature;
@@ -37,13 +39,10 @@ impl PyFeatureStore {
         name: &str,
         version: Option<i32>,
     ) -> PyResult<Option<feature_group::PyFeatureGroup>> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let fg = hopsworks_api::blocking::feature_store::get_feature_group_blocking(
-            &self.fs,
-            name,
-            version,
-            multithreaded,
-        )?;
+            &self.fs, name, version,
+        )
+        .map_err(to_py_err)?;
         Ok(fg.map(feature_group::PyFeatureGroup::from))
     }
 
@@ -57,7 +56,6 @@ impl PyFeatureStore {
         event_time: Option<&str>,
         embedding_index: Option<embedding_index::PyEmbeddingIndex>,
     ) -> PyResult<feature_group::PyFeatureGroup> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let fg = hopsworks_api::blocking::feature_store::get_or_create_feature_group_blocking(
             &self.fs,
             name,
@@ -67,8 +65,8 @@ impl PyFeatureStore {
             event_time,
             online_enabled,
             embedding_index.map(|ei| ei.ei),
-            multithreaded,
-        )?;
+        )
+        .map_err(to_py_err)?;
         Ok(feature_group::PyFeatureGroup::from(fg))
     }
 
@@ -77,13 +75,10 @@ impl PyFeatureStore {
         name: &str,
         version: Option<i32>,
     ) -> PyResult<Option<feature_view::PyFeatureView>> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let fv = hopsworks_api::blocking::feature_store::get_feature_view_blocking(
-            &self.fs,
-            name,
-            version,
-            multithreaded,
-        )?;
+            &self.fs, name, version,
+        )
+        .map_err(to_py_err)?;
         Ok(fv.map(feature_view::PyFeatureView::from))
     }
 
@@ -94,15 +89,14 @@ impl PyFeatureStore {
         query: PyQuery,
         description: Option<&str>,
     ) -> PyResult<feature_view::PyFeatureView> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         let fv = hopsworks_api::blocking::feature_store::create_feature_view_blocking(
             &self.fs,
             name,
             version,
             query.into(),
             description,
-            multithreaded,
-        )?;
+        )
+        .map_err(to_py_err)?;
         Ok(feature_view::PyFeatureView::from(fv))
     }
 }
@@ -1,30 +1,21 @@
 use pyo3::prelude::*;
-use std::{sync::OnceLock, time::Duration};
+use std::time::Duration;
 use tracing::debug;
 use tracing_subscriber::{filter, prelude::*, Layer};
 
+mod error;
 pub mod feature_store;
 pub mod platform;
 
-use hopsworks_api::HopsworksClientBuilder;
+use error::to_py_err;
+use hopsworks_api::{HopsworksClientBuilder, RuntimeConfig};
 use platform::project::PyProject;
 
-static MULTITHREADED: OnceLock<bool> = OnceLock::new();
-
 #[pyfunction]
 pub fn version() -> &'static str {
     hopsworks_api::VERSION
 }
 
-#[pyfunction]
-pub fn set_multithreaded(multithreaded: bool) {
-    if MULTITHREADED.get().is_none() {
-        MULTITHREADED.get_or_init(|| multithreaded);
-    } else {
-        MULTITHREADED.set(multithreaded).unwrap();
-    }
-}
-
 #[pyfunction]
 pub fn login(
     py: Python<'_>,
@@ -33,11 +24,16 @@ pub fn login(
     project_name: Option<&str>,
     multithreaded: Option<bool>,
 ) -> PyResult<platform::project::PyProject> {
-    let multithreaded = multithreaded.unwrap_or(true);
+    let runtime = if multithreaded.unwrap_or(true) {
+        RuntimeConfig::MultiThreaded
+    } else {
+        RuntimeConfig::SingleThreaded
+    };
     let builder =
         HopsworksClientBuilder::new_provided_or_from_env(api_key_value, url, project_name);
-    let project =
-        py.allow_threads(|| hopsworks_api::login_blocking(Some(builder), multithreaded))?;
+    let project = py
+        .allow_threads(|| hopsworks_api::login_blocking(Some(builder), runtime))
+        .map_err(to_py_err)?;
     debug!("Logged in to project: {}", project.name());
     debug!("{:#?}", project);
     Ok(PyProject::from(project))
@@ -68,7 +64,6 @@ pub fn init_subscriber() {
 #[pymodule]
 fn hopsworks_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     init_subscriber();
-    set_multithreaded(true);
 
     feature_store::register_module(m)?;
     platform::register_module(m)?;
@@ -1,4 +1,6 @@
 use pyo3::prelude::*;
+
+use crate::error::to_py_err;
 use serde::{Deserialize, Serialize};
 
 #[pyclass]
@@ -27,47 +29,37 @@ impl PyJobExecution {
     }
 
     fn get_current_state(&self) -> PyResult<String> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         Ok(
-            hopsworks_api::blocking::job_execution::get_current_state_blocking(
-                &self.job_execution,
-                multithreaded,
-            )?
-            .to_string(),
+            hopsworks_api::blocking::job_execution::get_current_state_blocking(&self.job_execution)
+                .map_err(to_py_err)?
+                .to_string(),
         )
     }
 
     fn download_logs(&self, local_dir: Option<String>) -> PyResult<()> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
         hopsworks_api::blocking::job_execution::download_logs_blocking(
             &self.job_execution,
             local_dir.as_deref(),
-            multithreaded,
-        )?;
+        )
+        .map_err(to_py_err)?;
         Ok(())
     }
 
     fn delete(&self) -> PyResult<()> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
-        hopsworks_api::blocking::job_execution::delete_blocking(
-            &self.job_execution,
-            multithreaded,
-        )?;
+        hopsworks_api::blocking::job_execution::delete_blocking(&self.job_execution)
+            .map_err(to_py_err)?;
         Ok(())
     }
 
     fn stop(&self) -> PyResult<()> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
-        hopsworks_api::blocking::job_execution::stop_blocking(&self.job_execution, multithreaded)?;
+        hopsworks_api::blocking::job_execution::stop_blocking(&self.job_execution)
+            .map_err(to_py_err)?;
         Ok(())
     }
 
     fn await_termination(&self) -> PyResult<()> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
-        hopsworks_api::blocking::job_execution::await_termination_blocking(
-            &self.job_execution,
-            multithreaded,
-        )?;
+        hopsworks_api::blocking::job_execution::await_termination_blocking(&self.job_execution)
+            .map_err(to_py_err)?;
         Ok(())
     }
 }
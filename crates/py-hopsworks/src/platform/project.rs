@@ -1,5 +1,7 @@
 use pyo3::prelude::*;
 
+use crate::error::to_py_err;
+
 use crate::feature_store::PyFeatureStore;
 
 #[pyclass]
@@ -32,19 +34,14 @@ impl PyProject {
     }
 
     fn get_feature_store(&self) -> PyResult<PyFeatureStore> {
-        let multithreaded = *crate::MULTITHREADED.get().unwrap();
-        let fs = hopsworks_api::blocking::project::get_feature_store_blocking(
-            &self.project,
-            multithreaded,
-        )?;
+        let fs = hopsworks_api::blocking::project::get_feature_store_blocking(&self.project)
+            .map_err(to_py_err)?;
         Ok(PyFeatureStore::from(fs))
     }
 
     fn init_hopsworks_opensearch_client(&self) -> PyResult<()> {
-        hopsworks_api::opensearch::init_hopsworks_opensearch_client_blocking(
-            self.id(),
-            *crate::MULTITHREADED.get().unwrap_or(&true),
-        )?;
+        hopsworks_api::opensearch::init_hopsworks_opensearch_client_blocking(self.id())
+            .map_err(to_py_err)?;
         Ok(())
     }
 }